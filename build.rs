@@ -447,6 +447,9 @@ fn prepare_test_files() {
         &["--keep-symbol=the_ignored_answer"],
     );
 
+    let src = data_dir.join("test-so.c");
+    cc(&src, "test-so.o", &["-c", "-fPIC", "-ffunction-sections"]);
+
     let src = data_dir.join("test-exe.c");
     cc(&src, "test-no-debug.bin", &["-g0", "-Wl,--build-id=none"]);
     cc(&src, "test-dwarf-v2.bin", &["-gstrict-dwarf", "-gdwarf-2"]);
@@ -186,6 +186,15 @@ enum blaze_symbolize_reason
    * The address belonged to an entity that is currently unsupported.
    */
   BLAZE_SYMBOLIZE_REASON_UNSUPPORTED,
+  /**
+   * The symbolization source's symbol table is corrupt.
+   */
+  BLAZE_SYMBOLIZE_REASON_CORRUPT_SYMTAB,
+  /**
+   * The address belongs to an anonymous executable memory mapping
+   * for which no perf map or JIT dump was available.
+   */
+  BLAZE_SYMBOLIZE_REASON_ANON_MAPPING,
 };
 #ifndef __cplusplus
 typedef uint8_t blaze_symbolize_reason;
@@ -789,11 +798,17 @@ typedef struct blaze_symbolize_src_kernel {
    * to satisfy the request (if present).
    */
   bool debug_syms;
+  /**
+   * Whether or not to symbolize addresses inside loaded kernel
+   * modules using their object files, as found in
+   * `/lib/modules/<release>/`.
+   */
+  bool module_syms;
   /**
    * Unused member available for future expansion. Must be initialized
    * to zero.
    */
-  uint8_t reserved[7];
+  uint8_t reserved[6];
 } blaze_symbolize_src_kernel;
 
 /**
@@ -84,6 +84,7 @@ impl From<blaze_symbolize_src_elf> for Elf {
         Self {
             path: unsafe { from_cstr(path) },
             debug_syms,
+            base_addr: None,
             _non_exhaustive: (),
         }
     }
@@ -119,9 +120,13 @@ pub struct blaze_symbolize_src_kernel {
     /// Whether or not to consult debug symbols from `kernel_image`
     /// to satisfy the request (if present).
     pub debug_syms: bool,
+    /// Whether or not to symbolize addresses inside loaded kernel
+    /// modules using their object files, as found in
+    /// `/lib/modules/<release>/`.
+    pub module_syms: bool,
     /// Unused member available for future expansion. Must be initialized
     /// to zero.
-    pub reserved: [u8; 7],
+    pub reserved: [u8; 6],
 }
 
 impl Default for blaze_symbolize_src_kernel {
@@ -131,7 +136,8 @@ impl Default for blaze_symbolize_src_kernel {
             kallsyms: ptr::null(),
             kernel_image: ptr::null(),
             debug_syms: false,
-            reserved: [0; 7],
+            module_syms: false,
+            reserved: [0; 6],
         }
     }
 }
@@ -143,12 +149,14 @@ impl From<blaze_symbolize_src_kernel> for Kernel {
             kallsyms,
             kernel_image,
             debug_syms,
+            module_syms,
             reserved: _,
         } = kernel;
         Self {
             kallsyms: (!kallsyms.is_null()).then(|| unsafe { from_cstr(kallsyms) }),
             kernel_image: (!kernel_image.is_null()).then(|| unsafe { from_cstr(kernel_image) }),
             debug_syms,
+            module_syms,
             _non_exhaustive: (),
         }
     }
@@ -219,6 +227,7 @@ impl From<blaze_symbolize_src_process> for Process {
             debug_syms,
             perf_map,
             map_files,
+            maps_override: None,
             _non_exhaustive: (),
         }
     }
@@ -341,6 +350,11 @@ pub enum blaze_symbolize_reason {
     BLAZE_SYMBOLIZE_REASON_UNKNOWN_ADDR,
     /// The address belonged to an entity that is currently unsupported.
     BLAZE_SYMBOLIZE_REASON_UNSUPPORTED,
+    /// The symbolization source's symbol table is corrupt.
+    BLAZE_SYMBOLIZE_REASON_CORRUPT_SYMTAB,
+    /// The address belongs to an anonymous executable memory mapping
+    /// for which no perf map or JIT dump was available.
+    BLAZE_SYMBOLIZE_REASON_ANON_MAPPING,
 }
 
 impl From<Reason> for blaze_symbolize_reason {
@@ -354,6 +368,8 @@ impl From<Reason> for blaze_symbolize_reason {
             Reason::MissingSyms => BLAZE_SYMBOLIZE_REASON_MISSING_SYMS,
             Reason::Unsupported => BLAZE_SYMBOLIZE_REASON_UNSUPPORTED,
             Reason::UnknownAddr => BLAZE_SYMBOLIZE_REASON_UNKNOWN_ADDR,
+            Reason::CorruptSymtab => BLAZE_SYMBOLIZE_REASON_CORRUPT_SYMTAB,
+            Reason::AnonMapping => BLAZE_SYMBOLIZE_REASON_ANON_MAPPING,
             _ => unreachable!(),
         }
     }
@@ -386,6 +402,12 @@ pub extern "C" fn blaze_symbolize_reason_str(err: blaze_symbolize_reason) -> *co
         e if e == BLAZE_SYMBOLIZE_REASON_UNSUPPORTED as i32 => {
             Reason::Unsupported.as_bytes().as_ptr().cast()
         }
+        e if e == BLAZE_SYMBOLIZE_REASON_CORRUPT_SYMTAB as i32 => {
+            Reason::CorruptSymtab.as_bytes().as_ptr().cast()
+        }
+        e if e == BLAZE_SYMBOLIZE_REASON_ANON_MAPPING as i32 => {
+            Reason::AnonMapping.as_bytes().as_ptr().cast()
+        }
         _ => b"unknown reason\0".as_ptr().cast(),
     }
 }
@@ -1100,6 +1122,7 @@ mod tests {
     use blazesym::inspect;
     use blazesym::normalize;
     use blazesym::symbolize::Reason;
+    use blazesym::symbolize::SrcLang;
     use blazesym::Pid;
 
     use tempfile::tempdir;
@@ -1145,7 +1168,7 @@ mod tests {
         };
         assert_eq!(
             format!("{kernel:?}"),
-            "blaze_symbolize_src_kernel { type_size: 32, kallsyms: 0x0, kernel_image: 0x0, debug_syms: true, reserved: [0, 0, 0, 0, 0, 0, 0] }"
+            "blaze_symbolize_src_kernel { type_size: 32, kallsyms: 0x0, kernel_image: 0x0, debug_syms: true, module_syms: false, reserved: [0, 0, 0, 0, 0, 0] }"
         );
 
         let process = blaze_symbolize_src_process {
@@ -1250,6 +1273,8 @@ mod tests {
             (Reason::MissingSyms, BLAZE_SYMBOLIZE_REASON_MISSING_SYMS),
             (Reason::Unsupported, BLAZE_SYMBOLIZE_REASON_UNSUPPORTED),
             (Reason::UnknownAddr, BLAZE_SYMBOLIZE_REASON_UNKNOWN_ADDR),
+            (Reason::CorruptSymtab, BLAZE_SYMBOLIZE_REASON_CORRUPT_SYMTAB),
+            (Reason::AnonMapping, BLAZE_SYMBOLIZE_REASON_ANON_MAPPING),
         ];
 
         for (reason, expected) in data {
@@ -1360,28 +1385,39 @@ mod tests {
         // A single symbol with inlined function information.
         let results = vec![Symbolized::Sym(Sym {
             name: "test".into(),
+            mangled_name: None,
             addr: 0x1337,
             offset: 0x1338,
             size: Some(42),
+            lang: SrcLang::Unknown,
+            section: None,
+            label: None,
+            module: None,
+            build_id: None,
             code_info: Some(CodeInfo {
                 dir: None,
                 file: OsStr::new("a-file").into(),
                 line: Some(42),
                 column: Some(43),
+                discriminator: None,
                 _non_exhaustive: (),
             }),
             inlined: vec![InlinedFn {
                 name: "inlined_fn".into(),
+                addr: None,
+                offset: None,
                 code_info: Some(CodeInfo {
                     dir: Some(Path::new("/some/dir").into()),
                     file: OsStr::new("another-file").into(),
                     line: Some(42),
                     column: Some(43),
+                    discriminator: None,
                     _non_exhaustive: (),
                 }),
                 _non_exhaustive: (),
             }]
             .into_boxed_slice(),
+            other_syms: Box::new([]),
             _non_exhaustive: (),
         })];
         let syms = convert_symbolizedresults_to_c(results);
@@ -1394,16 +1430,25 @@ mod tests {
             Symbolized::Unknown(Reason::UnknownAddr),
             Symbolized::Sym(Sym {
                 name: "test".into(),
+                mangled_name: None,
                 addr: 0x1337,
                 offset: 0x1338,
                 size: None,
+                lang: SrcLang::Unknown,
+                section: None,
+                label: None,
+                module: None,
+                build_id: None,
                 code_info: None,
                 inlined: vec![InlinedFn {
                     name: "inlined_fn".into(),
+                    addr: None,
+                    offset: None,
                     code_info: None,
                     _non_exhaustive: (),
                 }]
                 .into_boxed_slice(),
+                other_syms: Box::new([]),
                 _non_exhaustive: (),
             }),
             Symbolized::Unknown(Reason::InvalidFileOffset),
@@ -10,10 +10,12 @@ use blazesym::helper::read_elf_build_id;
 use blazesym::helper::ElfResolver;
 use blazesym::symbolize;
 use blazesym::symbolize::CodeInfo;
+use blazesym::symbolize::DebugInfo;
 use blazesym::symbolize::Input;
 use blazesym::symbolize::ProcessMemberInfo;
 use blazesym::symbolize::ProcessMemberType;
 use blazesym::symbolize::Resolve;
+use blazesym::symbolize::Source;
 use blazesym::symbolize::Sym;
 use blazesym::symbolize::Symbolized;
 use blazesym::symbolize::Symbolizer;
@@ -30,6 +32,7 @@ use debuginfod::Client;
 use dirs::cache_dir;
 
 use tracing::subscriber::set_global_default as set_global_subscriber;
+use tracing::warn;
 use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::fmt::time::SystemTime;
 use tracing_subscriber::FmtSubscriber;
@@ -121,6 +124,7 @@ where
 fn dispatch_process(
     info: ProcessMemberInfo<'_>,
     client: &CachingClient,
+    inspector: &Symbolizer,
 ) -> Result<Option<Box<dyn Resolve>>, BlazeErr> {
     let ProcessMemberInfo {
         member_entry: entry,
@@ -129,6 +133,18 @@ fn dispatch_process(
 
     match entry {
         ProcessMemberType::Path(path) => {
+            let src = Source::from(symbolize::Elf::new(&path.maps_file));
+            match inspector.inspect(&src) {
+                // The binary carries debug information itself, so there
+                // is no need to fetch anything.
+                Ok(info) if matches!(info.debug_info, DebugInfo::Present) => return Ok(None),
+                Ok(..) => (),
+                Err(err) => {
+                    warn!("failed to inspect `{}`: {err}", path.maps_file.display());
+                    return Ok(None)
+                }
+            }
+
             let build_id = if let Some(build_id) = read_elf_build_id(&path.maps_file)? {
                 build_id
             } else {
@@ -138,12 +154,20 @@ fn dispatch_process(
                 return Ok(None)
             };
 
-            let path = if let Some(path) = client.fetch_debug_info(&build_id).map_err(Box::from)? {
-                path
-            } else {
+            let path = match client.fetch_debug_info(&build_id) {
+                Ok(Some(path)) => path,
                 // If we were unable to find debug information for the provided
                 // build ID we let the default resolver see what it can do.
-                return Ok(None)
+                Ok(None) => return Ok(None),
+                // Same for network and other errors, which should not
+                // prevent symbolization altogether.
+                Err(err) => {
+                    warn!(
+                        "failed to fetch debug information for `{}`: {err}",
+                        path.maps_file.display()
+                    );
+                    return Ok(None)
+                }
             };
 
             let resolver = ElfResolver::open(&path)
@@ -184,8 +208,9 @@ fn main() -> Result<()> {
     let client = CachingClient::new(client, cache_dir)?;
 
     let src = symbolize::Source::Process(symbolize::Process::new(Pid::from(args.pid)));
+    let inspector = Symbolizer::new();
     let symbolizer = Symbolizer::builder()
-        .set_process_dispatcher(move |info| dispatch_process(info, &client))
+        .set_process_dispatcher(move |info| dispatch_process(info, &client, &inspector))
         .build();
     let syms = symbolizer
         .symbolize(&src, Input::AbsAddr(&args.addrs))
@@ -0,0 +1,370 @@
+//! Support for `ar` archives (`.a` files), as produced by `ar(1)`.
+//!
+//! Both the GNU and BSD variants of the format are understood, as are
+//! GNU style thin archives, which only reference their members instead
+//! of embedding them.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::ops::Range;
+use std::path::Path;
+use std::str;
+
+use crate::mmap::Mmap;
+use crate::util::bytes_to_path;
+use crate::Error;
+use crate::IntoError as _;
+use crate::Result;
+
+/// The magic bytes at the start of a regular archive.
+const MAGIC: &[u8; 8] = b"!<arch>\n";
+/// The magic bytes at the start of a thin archive.
+const THIN_MAGIC: &[u8; 8] = b"!<thin>\n";
+/// The size of a member header.
+const HEADER_SIZE: usize = 60;
+/// The magic bytes terminating a member header.
+const HEADER_MAGIC: &[u8; 2] = b"`\n";
+
+
+/// Parse a space padded decimal number from a member header field.
+fn parse_decimal(field: &[u8]) -> Option<u64> {
+    let s = str::from_utf8(field).ok()?.trim_end_matches(' ');
+    s.parse().ok()
+}
+
+
+/// A member of an `ar` archive.
+#[derive(Debug)]
+pub(crate) struct Member<'archive> {
+    /// The name of the member.
+    ///
+    /// For members of thin archives, this is the path to the member,
+    /// relative to the directory containing the archive.
+    pub name: &'archive Path,
+    /// The range of the member's data inside the archive.
+    ///
+    /// This member is `None` for members of thin archives, which are
+    /// not stored inside the archive itself.
+    pub data: Option<Range<u64>>,
+}
+
+
+/// An iterator over the members of an `ar` archive.
+struct MemberIter<'archive> {
+    /// The archive's data.
+    data: &'archive [u8],
+    /// Whether the archive is a thin one.
+    thin: bool,
+    /// The offset of the next member header.
+    offset: usize,
+    /// The GNU long name table, if encountered already.
+    names: Option<&'archive [u8]>,
+}
+
+impl<'archive> MemberIter<'archive> {
+    /// Look up a name in the GNU long name table.
+    fn long_name(&self, offset: &[u8]) -> Result<&'archive [u8]> {
+        let offset = parse_decimal(offset)
+            .ok_or_invalid_data(|| "encountered invalid archive long name offset")?;
+        let names = self
+            .names
+            .ok_or_invalid_data(|| "archive long name table is missing")?;
+        let name = names
+            .get(offset as usize..)
+            .ok_or_invalid_data(|| format!("archive long name offset {offset} is out of bounds"))?;
+        let end = name
+            .windows(2)
+            .position(|window| window == b"/\n")
+            .ok_or_invalid_data(|| "archive long name is not terminated")?;
+        Ok(&name[..end])
+    }
+
+    /// Parse the next member, including special ones (such as symbol
+    /// or name tables).
+    fn parse_next(&mut self) -> Result<Option<(&'archive [u8], Option<Range<usize>>)>> {
+        // Members are aligned to even offsets.
+        self.offset += self.offset % 2;
+        if self.offset >= self.data.len() {
+            return Ok(None)
+        }
+
+        let header = self
+            .data
+            .get(self.offset..self.offset + HEADER_SIZE)
+            .ok_or_invalid_data(|| {
+                format!("archive member header at {} is truncated", self.offset)
+            })?;
+        if &header[58..] != HEADER_MAGIC {
+            return Err(Error::with_invalid_data(format!(
+                "archive member header at {} is invalid",
+                self.offset
+            )))
+        }
+
+        let name = {
+            let name = &header[..16];
+            let len = name
+                .iter()
+                .rposition(|b| *b != b' ')
+                .map_or(0, |idx| idx + 1);
+            &name[..len]
+        };
+        let size = parse_decimal(&header[48..58])
+            .ok_or_invalid_data(|| format!("archive member size at {} is invalid", self.offset))?;
+        let start = self.offset + HEADER_SIZE;
+        let is_special = name == b"/" || name == b"/SYM64/" || name == b"//";
+        // Members of thin archives are not stored inline, but special
+        // ones always are.
+        let stored = !self.thin || is_special;
+
+        let (name, range) = if let Some(len) = name.strip_prefix(b"#1/") {
+            // BSD style long names are stored at the start of the
+            // member data.
+            let len = parse_decimal(len)
+                .ok_or_invalid_data(|| "encountered invalid BSD archive name length")?;
+            let len = usize::try_from(len)
+                .ok()
+                .filter(|len| (*len as u64) <= size)
+                .ok_or_invalid_data(|| "BSD archive name length is out of bounds")?;
+            let name = self
+                .data
+                .get(start..start + len)
+                .ok_or_invalid_data(|| "BSD archive name is truncated")?;
+            // Names may be padded with NUL bytes.
+            let name_len = name.iter().position(|b| *b == b'\0').unwrap_or(len);
+            (&name[..name_len], start + len..start + size as usize)
+        } else if is_special {
+            (name, start..start + size as usize)
+        } else if let Some(offset) = name.strip_prefix(b"/") {
+            (self.long_name(offset)?, start..start + size as usize)
+        } else {
+            // GNU style short names are terminated by a slash.
+            let name = name.strip_suffix(b"/").unwrap_or(name);
+            (name, start..start + size as usize)
+        };
+
+        let range = if stored {
+            if range.end > self.data.len() {
+                return Err(Error::with_invalid_data(format!(
+                    "data of archive member at {} is truncated",
+                    self.offset
+                )))
+            }
+            self.offset = start + size as usize;
+            Some(range)
+        } else {
+            self.offset = start;
+            None
+        };
+
+        if name == b"//" {
+            // SANITY: The long name table is always stored.
+            self.names = range.as_ref().map(|range| &self.data[range.clone()]);
+        }
+
+        Ok(Some((name, range)))
+    }
+}
+
+impl<'archive> Iterator for MemberIter<'archive> {
+    type Item = Result<Member<'archive>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (name, range) = match self.parse_next() {
+                Ok(Some(member)) => member,
+                Ok(None) => return None,
+                Err(err) => {
+                    // Make sure to stop iteration on error.
+                    self.offset = self.data.len();
+                    return Some(Err(err))
+                }
+            };
+
+            match name {
+                b"/" | b"/SYM64/" | b"//" | b"__.SYMDEF" | b"__.SYMDEF SORTED" => continue,
+                _ => (),
+            }
+
+            let member = bytes_to_path(name).map_err(Error::from).map(|name| Member {
+                name,
+                data: range.map(|range| range.start as u64..range.end as u64),
+            });
+            return Some(member)
+        }
+    }
+}
+
+
+/// An `ar` archive.
+pub(crate) struct Archive {
+    /// The memory mapped archive data.
+    mmap: Mmap,
+    /// Whether the archive is a thin one.
+    thin: bool,
+}
+
+impl Archive {
+    /// Create an `Archive` from memory mapped archive data.
+    pub(crate) fn with_mmap(mmap: Mmap) -> Result<Self> {
+        let thin = match mmap.get(..MAGIC.len()) {
+            Some(magic) if magic == MAGIC => false,
+            Some(magic) if magic == THIN_MAGIC => true,
+            _ => {
+                return Err(Error::with_invalid_data(
+                    "file does not start with ar archive magic",
+                ))
+            }
+        };
+
+        Ok(Self { mmap, thin })
+    }
+
+    /// Retrieve the archive's memory mapped data.
+    #[inline]
+    pub(crate) fn mmap(&self) -> &Mmap {
+        &self.mmap
+    }
+
+    /// Iterate over all regular members of the archive, skipping
+    /// symbol and name tables.
+    pub(crate) fn members(&self) -> impl Iterator<Item = Result<Member<'_>>> {
+        MemberIter {
+            data: &self.mmap,
+            thin: self.thin,
+            offset: MAGIC.len(),
+            names: None,
+        }
+    }
+
+    /// Find the first member with the provided name.
+    pub(crate) fn find_member(&self, name: &Path) -> Result<Option<Member<'_>>> {
+        for member in self.members() {
+            let member = member?;
+            if member.name == name {
+                return Ok(Some(member))
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Debug for Archive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct(stringify!(Archive))
+            .field("thin", &self.thin)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    /// Create an archive member header.
+    fn header(name: &str, size: usize) -> Vec<u8> {
+        let header = format!("{name:<16}{:<12}{:<6}{:<6}{:<8}{size:<10}`\n", 0, 0, 0, 644);
+        assert_eq!(header.len(), HEADER_SIZE);
+        header.into_bytes()
+    }
+
+    /// Append a member to `archive`, padding it as necessary.
+    fn add_member(archive: &mut Vec<u8>, name: &str, data: &[u8]) {
+        let () = archive.extend_from_slice(&header(name, data.len()));
+        let () = archive.extend_from_slice(data);
+        if archive.len() % 2 == 1 {
+            let () = archive.push(b'\n');
+        }
+    }
+
+    /// Check that we can find members of a GNU style archive.
+    #[test]
+    fn gnu_archive_members() {
+        let long = "a-rather-long-member-name.o";
+        let names = format!("{long}/\n");
+
+        let mut archive = MAGIC.to_vec();
+        let () = add_member(&mut archive, "/", &[0; 4]);
+        let () = add_member(&mut archive, "//", names.as_bytes());
+        let () = add_member(&mut archive, "short.o/", b"abc");
+        let () = add_member(&mut archive, "/0", b"defgh");
+
+        let archive = Archive::with_mmap(Mmap::from_bytes(&archive).unwrap()).unwrap();
+        let members = archive
+            .members()
+            .map(|member| member.unwrap().name.to_path_buf())
+            .collect::<Vec<_>>();
+        assert_eq!(members, vec![Path::new("short.o"), Path::new(long)]);
+
+        let member = archive.find_member(Path::new(long)).unwrap().unwrap();
+        let data = member.data.unwrap();
+        assert_eq!(
+            &archive.mmap()[data.start as usize..data.end as usize],
+            b"defgh"
+        );
+
+        let member = archive.find_member(Path::new("short.o")).unwrap().unwrap();
+        let data = member.data.unwrap();
+        assert_eq!(
+            &archive.mmap()[data.start as usize..data.end as usize],
+            b"abc"
+        );
+
+        assert!(archive
+            .find_member(Path::new("missing.o"))
+            .unwrap()
+            .is_none());
+    }
+
+    /// Check that we can find members of a BSD style archive.
+    #[test]
+    fn bsd_archive_members() {
+        let mut archive = MAGIC.to_vec();
+        let () = add_member(&mut archive, "#1/20", b"__.SYMDEF SORTED\0\0\0\0");
+        let () = add_member(&mut archive, "#1/27", b"a-rather-long-member-name.oxyz");
+
+        let archive = Archive::with_mmap(Mmap::from_bytes(&archive).unwrap()).unwrap();
+        let members = archive
+            .members()
+            .map(|member| member.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name, Path::new("a-rather-long-member-name.o"));
+        let data = members[0].data.clone().unwrap();
+        assert_eq!(
+            &archive.mmap()[data.start as usize..data.end as usize],
+            b"xyz"
+        );
+    }
+
+    /// Check that members of thin archives are reported without data.
+    #[test]
+    fn thin_archive_members() {
+        let names = "sub/first.o/\nsecond.o/\n";
+        let mut archive = THIN_MAGIC.to_vec();
+        let () = add_member(&mut archive, "//", names.as_bytes());
+        let () = archive.extend_from_slice(&header("/0", 1234));
+        let () = archive.extend_from_slice(&header("/13", 42));
+
+        let archive = Archive::with_mmap(Mmap::from_bytes(&archive).unwrap()).unwrap();
+        let members = archive
+            .members()
+            .map(|member| member.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name, Path::new("sub/first.o"));
+        assert_eq!(members[0].data, None);
+        assert_eq!(members[1].name, Path::new("second.o"));
+        assert_eq!(members[1].data, None);
+    }
+
+    /// Make sure that we reject data that is not an archive.
+    #[test]
+    fn invalid_archive() {
+        let err = Archive::with_mmap(Mmap::from_bytes(b"\x7fELF").unwrap()).unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidData);
+    }
+}
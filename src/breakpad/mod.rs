@@ -2,5 +2,7 @@ mod file;
 mod parser;
 mod resolver;
 mod types;
+mod writer;
 
 pub use resolver::BreakpadResolver;
+pub(crate) use writer::write_elf;
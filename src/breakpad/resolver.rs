@@ -133,6 +133,7 @@ impl BreakpadResolver {
             file: Cow::Borrowed(file),
             line: Some(source_line.line),
             column: None,
+            discriminator: None,
             _non_exhaustive: (),
         };
 
@@ -147,6 +148,7 @@ impl BreakpadResolver {
                     file: Cow::Borrowed(file),
                     line: Some(inlinee.call_line),
                     column: None,
+                    discriminator: None,
                     _non_exhaustive: (),
                 });
 
@@ -158,6 +160,8 @@ impl BreakpadResolver {
 
                 let inlined_fn = InlinedFn {
                     name: Cow::Borrowed(name),
+                    addr: None,
+                    offset: None,
                     code_info,
                     _non_exhaustive: (),
                 };
@@ -194,6 +198,7 @@ impl Symbolize for BreakpadResolver {
             addr: func.addr,
             size: Some(func.size.try_into().unwrap_or(usize::MAX)),
             lang: SrcLang::Unknown,
+            section: None,
             code_info: None,
             inlined: Box::new([]),
         };
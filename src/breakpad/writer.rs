@@ -0,0 +1,204 @@
+//! Writer of the Breakpad symbol file format.
+//!
+//! See <https://chromium.googlesource.com/breakpad/breakpad/+/master/docs/symbol_files.md>
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use crate::elf::ElfResolver;
+use crate::inspect::FindAddrOpts;
+use crate::normalize::buildid::read_build_id;
+use crate::Addr;
+use crate::Result;
+use crate::SymType;
+
+
+/// Map an ELF `e_machine` value to the architecture name used by
+/// Breakpad.
+fn arch_name(machine: u16) -> &'static str {
+    match machine {
+        3 => "x86",
+        8 => "mips",
+        20 => "ppc",
+        21 => "ppc64",
+        40 => "arm",
+        62 => "x86_64",
+        183 => "arm64",
+        243 => "riscv64",
+        _ => "unknown",
+    }
+}
+
+/// Convert an ELF build ID into a Breakpad module ID.
+///
+/// Breakpad interprets the first 16 bytes of the build ID as a GUID,
+/// with its first three fields stored in little endian byte order,
+/// and appends an "age" of zero.
+fn module_id(build_id: Option<&[u8]>) -> String {
+    let mut guid = [0u8; 16];
+    if let Some(build_id) = build_id {
+        let len = build_id.len().min(guid.len());
+        let () = guid[..len].copy_from_slice(&build_id[..len]);
+    }
+
+    let () = guid[0..4].reverse();
+    let () = guid[4..6].reverse();
+    let () = guid[6..8].reverse();
+
+    let mut id = guid
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<String>();
+    let () = id.push('0');
+    id
+}
+
+
+/// Write the symbols of the ELF file represented by `resolver` in the
+/// Breakpad symbol file format.
+pub(crate) fn write_elf(resolver: &ElfResolver, writer: &mut dyn Write) -> Result<()> {
+    let parser = resolver.parser();
+    let arch = arch_name(parser.machine()?);
+    let build_id = read_build_id(parser)?;
+    let id = module_id(build_id.as_deref());
+    let name = resolver
+        .path()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let () = writeln!(writer, "MODULE Linux {arch} {id} {name}")?;
+
+    let opts = FindAddrOpts {
+        offset_in_file: true,
+        sym_type: SymType::Function,
+    };
+    let mut funcs = Vec::new();
+    let () = parser.for_each(&opts, &mut |sym| {
+        if let Some(offset) = sym.file_offset {
+            let () = funcs.push((sym.addr, offset, sym.size, sym.name.to_string()));
+        }
+    })?;
+    let () = funcs.sort_by_key(|(addr, ..)| *addr);
+    let () = funcs.dedup_by_key(|(addr, ..)| *addr);
+
+    let mut files = Vec::<PathBuf>::new();
+    let mut file_idxs = HashMap::<PathBuf, usize>::new();
+    let mut lines = Vec::<(Range<Addr>, usize, u32)>::new();
+    let () = resolver.for_each_line(&mut |range, dir, file, line| {
+        let path = dir.join(file);
+        let idx = *file_idxs.entry(path).or_insert_with_key(|path| {
+            let () = files.push(path.clone());
+            files.len() - 1
+        });
+        let () = lines.push((range, idx, line));
+    })?;
+    let () = lines.sort_by_key(|(range, ..)| range.start);
+
+    for (idx, path) in files.iter().enumerate() {
+        let () = writeln!(writer, "FILE {idx} {}", path.display())?;
+    }
+
+    for (addr, offset, size, name) in funcs {
+        if size == 0 {
+            let () = writeln!(writer, "PUBLIC {offset:x} 0 {name}")?;
+            continue
+        }
+
+        let () = writeln!(writer, "FUNC {offset:x} {size:x} 0 {name}")?;
+
+        let end = addr.saturating_add(size as Addr);
+        // Start at the last line record beginning before the
+        // function, as it may extend into it.
+        let start = lines
+            .partition_point(|(range, ..)| range.start < addr)
+            .saturating_sub(1);
+        for (range, file, line) in &lines[start..] {
+            if range.start >= end {
+                break
+            }
+
+            let line_start = range.start.max(addr);
+            let line_end = range.end.min(end);
+            if line_start >= line_end {
+                continue
+            }
+            let () = writeln!(
+                writer,
+                "{:x} {:x} {line} {file}",
+                offset + (line_start - addr),
+                line_end - line_start,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::path::Path;
+
+    use tempfile::NamedTempFile;
+
+    use crate::breakpad::BreakpadResolver;
+    use crate::symbolize::FindSymOpts;
+    use crate::symbolize::Symbolize as _;
+
+
+    /// Check that we convert build IDs into Breakpad module IDs
+    /// correctly.
+    #[test]
+    fn module_id_conversion() {
+        let build_id = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13,
+        ];
+        assert_eq!(
+            module_id(Some(build_id.as_slice())),
+            "030201000504070608090A0B0C0D0E0F0"
+        );
+        assert_eq!(module_id(None), "0".repeat(33));
+    }
+
+    /// Check that we can symbolize file offsets using Breakpad data
+    /// generated from an ELF file with DWARF information.
+    #[test]
+    fn breakpad_round_trip() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let elf = ElfResolver::open(&path).unwrap();
+
+        let mut file = NamedTempFile::new().unwrap();
+        let () = elf.write_breakpad(&mut file).unwrap();
+        let breakpad = BreakpadResolver::open(file.path()).unwrap();
+
+        let parser = elf.parser();
+        let addr = 0x2000100;
+        let offset = parser.find_file_offset(addr).unwrap().unwrap();
+
+        let elf_sym = elf
+            .find_sym(addr + 4, &FindSymOpts::CodeInfo)
+            .unwrap()
+            .unwrap();
+        let breakpad_sym = breakpad
+            .find_sym(offset + 4, &FindSymOpts::CodeInfo)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(breakpad_sym.name, "factorial");
+        assert_eq!(breakpad_sym.name, elf_sym.name);
+        assert_eq!(breakpad_sym.addr, offset);
+        assert_eq!(breakpad_sym.size, elf_sym.size);
+
+        let elf_info = elf_sym.code_info.unwrap();
+        let breakpad_info = breakpad_sym.code_info.unwrap();
+        assert_eq!(breakpad_info.to_path(), elf_info.to_path());
+        assert_eq!(breakpad_info.line, elf_info.line);
+    }
+}
@@ -18,6 +18,26 @@ macro_rules! cfg_breakpad {
     }
 }
 
+macro_rules! cfg_macho {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "macho")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "macho")))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_pe {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "pe")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "pe")))]
+            $item
+        )*
+    }
+}
+
 macro_rules! cfg_gsym {
     ($($item:item)*) => {
         $(
@@ -27,3 +47,13 @@ macro_rules! cfg_gsym {
         )*
     }
 }
+
+macro_rules! cfg_tokio {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "tokio")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+            $item
+        )*
+    }
+}
@@ -145,11 +145,16 @@ pub(crate) fn read_debug_link(parser: &ElfParser) -> Result<Option<(&OsStr, u32)
     // SANITY: We just found the index so the section should always be
     //         found.
     let data = parser.section_data(idx).unwrap();
-    parse_debug_link_section_data(data)
+    let swap = parser.is_byte_swapped()?;
+    parse_debug_link_section_data(data, swap)
 }
 
 
-fn parse_debug_link_section_data(mut data: &[u8]) -> Result<Option<(&OsStr, u32)>> {
+/// Parse the contents of a `.gnu_debuglink` section.
+///
+/// `swap` indicates whether the checksum has to be byte swapped, which
+/// is the case if the ELF file's byte order differs from the host's.
+fn parse_debug_link_section_data(mut data: &[u8], swap: bool) -> Result<Option<(&OsStr, u32)>> {
     let data_start = data;
     let file = data
         .read_cstr()
@@ -167,11 +172,11 @@ fn parse_debug_link_section_data(mut data: &[u8]) -> Result<Option<(&OsStr, u32)
         .ok_or_invalid_data(|| {
             "debug link section contains insufficient data: checksum not found"
         })?;
-    // TODO: The CRC value is in the same endianess as the ELF file itself. Once
-    //       we support non-host endianesses we need to take that into account.
+    // The CRC value is in the same endianess as the ELF file itself.
     let crc = data
         .read_u32()
         .ok_or_invalid_data(|| "failed to read debug link checksum")?;
+    let crc = if swap { crc.swap_bytes() } else { crc };
     Ok(Some((file, crc)))
 }
 
@@ -264,9 +269,19 @@ mod tests {
         // Now write the section data into it.
         let () = buffer.copy_from_slice(&section_data);
 
-        let (file, crc) = parse_debug_link_section_data(buffer).unwrap().unwrap();
+        let (file, crc) = parse_debug_link_section_data(buffer, false)
+            .unwrap()
+            .unwrap();
         assert_eq!(file, OsStr::new("program.debug"));
         assert_eq!(crc, 0xa6d4c469, "{crc:#x}");
+
+        // Also check that we honor a byte order different from the
+        // host's.
+        let (file, crc) = parse_debug_link_section_data(buffer, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(file, OsStr::new("program.debug"));
+        assert_eq!(crc, 0xa6d4c469u32.swap_bytes(), "{crc:#x}");
     }
 
     /// Check that we can successfully read an ELF file's debug link.
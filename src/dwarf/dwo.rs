@@ -0,0 +1,238 @@
+//! Support for split DWARF, i.e., debug information residing in
+//! separate `.dwo` files or `.dwp` packages and referenced by skeleton
+//! units.
+
+use std::mem;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::elf::ElfParser;
+use crate::log::debug;
+use crate::util::bytes_to_path;
+use crate::Result;
+
+use super::reader;
+use super::reader::R;
+
+
+/// Find the `.dwo` file with the given name, as referenced by a
+/// skeleton unit.
+///
+/// Relative names are interpreted relative to the compilation
+/// directory, if any. As a fall back, the file is looked for alongside
+/// the object file containing the skeleton unit.
+fn find_dwo_file(name: &Path, comp_dir: Option<&Path>, obj_dir: Option<&Path>) -> Option<PathBuf> {
+    let mut candidates = Vec::with_capacity(3);
+    if name.is_absolute() {
+        let () = candidates.push(name.to_path_buf());
+    } else if let Some(comp_dir) = comp_dir {
+        let () = candidates.push(comp_dir.join(name));
+    }
+
+    if let Some(obj_dir) = obj_dir {
+        if name.is_relative() {
+            let () = candidates.push(obj_dir.join(name));
+        }
+        if let Some(file_name) = name.file_name() {
+            let () = candidates.push(obj_dir.join(file_name));
+        }
+    }
+
+    let path = candidates.into_iter().find(|path| path.exists());
+    if let Some(path) = &path {
+        debug!("found split DWARF file at `{}`", path.display());
+    }
+    path
+}
+
+
+/// A split compilation unit, corresponding to a skeleton unit.
+pub(super) struct DwoUnit<'dwarf> {
+    /// The DWARF data of the `.dwo` file or `.dwp` package member.
+    dwarf: gimli::Dwarf<R<'dwarf>>,
+    /// The split compilation unit.
+    unit: gimli::Unit<R<'dwarf>>,
+    /// The parser of the file containing the data.
+    // SAFETY: This member has to be listed after `dwarf` and `unit` to
+    //         make sure we never end up with a dangling reference.
+    _parser: Rc<ElfParser>,
+}
+
+impl<'dwarf> DwoUnit<'dwarf> {
+    /// Find the split unit with the provided ID in `dwarf`, relocating
+    /// it based on the provided skeleton unit.
+    fn find(
+        dwarf: gimli::Dwarf<R<'dwarf>>,
+        dwo_id: gimli::DwoId,
+        skeleton: &gimli::Unit<R<'dwarf>>,
+        parser: Rc<ElfParser>,
+    ) -> Result<Option<Self>> {
+        let mut headers = dwarf.units();
+        while let Some(header) = headers.next()? {
+            let mut unit = dwarf.unit(header)?;
+            // For DWARF 4 based GNU split DWARF, the ID is provided as
+            // an attribute, which gimli takes care of for us.
+            if unit.dwo_id == Some(dwo_id) {
+                let () = unit.copy_relocated_attributes(skeleton);
+                let slf = Self {
+                    dwarf,
+                    unit,
+                    _parser: parser,
+                };
+                return Ok(Some(slf))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Load the split unit with the provided ID from the `.dwo` file
+    /// referenced by `skeleton`.
+    pub(super) fn load(
+        parent: &gimli::Dwarf<R<'dwarf>>,
+        skeleton: &gimli::Unit<R<'dwarf>>,
+        dwo_id: gimli::DwoId,
+        obj_dir: Option<&Path>,
+    ) -> Result<Option<Self>> {
+        let name = match skeleton.dwo_name()? {
+            Some(name) => parent.attr_string(skeleton, name)?,
+            None => return Ok(None),
+        };
+        let name = bytes_to_path(name.slice())?;
+        let comp_dir = skeleton
+            .comp_dir
+            .map(|dir| bytes_to_path(dir.slice()))
+            .transpose()?;
+
+        let path = match find_dwo_file(name, comp_dir, obj_dir) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        let parser = Rc::new(ElfParser::open(&path)?);
+        // SAFETY: The `DwoUnit` object owns the `ElfParser` and makes
+        //         sure that it stays around while the DWARF data are in
+        //         use. As such, it is fine to conjure up a 'dwarf
+        //         lifetime here.
+        let dwo_parser =
+            unsafe { mem::transmute::<&ElfParser, &'dwarf ElfParser>(parser.as_ref()) };
+        let mut dwarf =
+            gimli::Dwarf::load(|section| reader::load_dwo_section(dwo_parser, section))?;
+        let () = dwarf.make_dwo(parent);
+        Self::find(dwarf, dwo_id, skeleton, parser)
+    }
+
+    /// Retrieve a [`gimli::UnitRef`] for the split unit.
+    #[inline]
+    pub(super) fn unit_ref(&self) -> gimli::UnitRef<'_, R<'dwarf>> {
+        gimli::UnitRef::new(&self.dwarf, &self.unit)
+    }
+}
+
+
+/// A DWARF package (`.dwp`) file, combining the data of multiple
+/// `.dwo` files.
+pub(super) struct DwpPackage<'dwarf> {
+    /// The parsed package.
+    package: gimli::DwarfPackage<R<'dwarf>>,
+    /// The parser of the package file.
+    // SAFETY: This member has to be listed after `package` to make sure
+    //         we never end up with a dangling reference.
+    parser: Rc<ElfParser>,
+}
+
+impl<'dwarf> DwpPackage<'dwarf> {
+    /// Load the DWARF package for the object file at `path`, if
+    /// present.
+    ///
+    /// By convention, the package is located alongside the object file
+    /// and named after it, with a `.dwp` suffix appended.
+    pub(super) fn load(path: &Path) -> Result<Option<Self>> {
+        let mut dwp = path.as_os_str().to_os_string();
+        let () = dwp.push(".dwp");
+        let dwp = PathBuf::from(dwp);
+        if !dwp.exists() {
+            return Ok(None)
+        }
+        debug!("found DWARF package at `{}`", dwp.display());
+
+        let parser = Rc::new(ElfParser::open(&dwp)?);
+        // SAFETY: The `DwpPackage` object owns the `ElfParser` and
+        //         makes sure that it stays around while the DWARF data
+        //         are in use. As such, it is fine to conjure up a
+        //         'dwarf lifetime here.
+        let dwp_parser =
+            unsafe { mem::transmute::<&ElfParser, &'dwarf ElfParser>(parser.as_ref()) };
+        let package = gimli::DwarfPackage::load(
+            |section| reader::load_dwo_section(dwp_parser, section),
+            reader::empty(),
+        )?;
+        let slf = Self { package, parser };
+        Ok(Some(slf))
+    }
+
+    /// Find the split unit with the provided ID in the package.
+    pub(super) fn find_unit(
+        &self,
+        parent: &gimli::Dwarf<R<'dwarf>>,
+        skeleton: &gimli::Unit<R<'dwarf>>,
+        dwo_id: gimli::DwoId,
+    ) -> Result<Option<DwoUnit<'dwarf>>> {
+        match self.package.find_cu(dwo_id, parent)? {
+            Some(dwarf) => DwoUnit::find(dwarf, dwo_id, skeleton, Rc::clone(&self.parser)),
+            None => Ok(None),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::create_dir;
+    use std::fs::File;
+
+    use tempfile::tempdir;
+
+
+    /// Check that we look for `.dwo` files in the expected locations.
+    #[test]
+    fn dwo_file_search() {
+        let comp_dir = tempdir().unwrap();
+        let obj_dir = tempdir().unwrap();
+
+        let name = Path::new("sub/test.dwo");
+        assert_eq!(
+            find_dwo_file(name, Some(comp_dir.path()), Some(obj_dir.path())),
+            None
+        );
+
+        let () = create_dir(obj_dir.path().join("sub")).unwrap();
+        let _file = File::create(obj_dir.path().join("test.dwo")).unwrap();
+        assert_eq!(
+            find_dwo_file(name, Some(comp_dir.path()), Some(obj_dir.path())),
+            Some(obj_dir.path().join("test.dwo"))
+        );
+
+        let _file = File::create(obj_dir.path().join(name)).unwrap();
+        assert_eq!(
+            find_dwo_file(name, Some(comp_dir.path()), Some(obj_dir.path())),
+            Some(obj_dir.path().join(name))
+        );
+
+        let () = create_dir(comp_dir.path().join("sub")).unwrap();
+        let _file = File::create(comp_dir.path().join(name)).unwrap();
+        assert_eq!(
+            find_dwo_file(name, Some(comp_dir.path()), Some(obj_dir.path())),
+            Some(comp_dir.path().join(name))
+        );
+
+        // Absolute paths are used as-is if they exist.
+        let path = comp_dir.path().join(name);
+        assert_eq!(
+            find_dwo_file(&path, None, Some(obj_dir.path())),
+            Some(path.clone())
+        );
+    }
+}
@@ -186,16 +186,24 @@ impl<'dwarf> InlinedFunctions<'dwarf> {
         })
     }
 
-    /// Build the list of inlined functions that contain `probe`.
+    /// Build the list of inlined functions that contain `probe`, up to
+    /// a nesting depth of `max_depth`.
+    ///
+    /// Each function is reported along with the start address of its
+    /// address range covering `probe`.
     pub(super) fn find_inlined_functions(
         &self,
         probe: u64,
-    ) -> vec::IntoIter<&InlinedFunction<'dwarf>> {
+        max_depth: usize,
+    ) -> vec::IntoIter<(&InlinedFunction<'dwarf>, u64)> {
         // `inlined_functions` is ordered from outside to inside.
         let mut inlined_functions = Vec::new();
         let mut inlined_addresses = &self.inlined_addresses[..];
         loop {
             let current_depth = inlined_functions.len();
+            if current_depth >= max_depth {
+                break
+            }
             // Look up (probe, current_depth) in inline_ranges.
             // `inlined_addresses` is sorted in "breadth-first traversal order", i.e.
             // by `call_depth` first, and then by `range.begin`. See the comment at
@@ -214,8 +222,11 @@ impl<'dwarf> InlinedFunctions<'dwarf> {
                 }
             });
             if let Ok(index) = search {
-                let function_index = inlined_addresses[index].function;
-                inlined_functions.push(&self.inlined_functions[function_index]);
+                let address = &inlined_addresses[index];
+                inlined_functions.push((
+                    &self.inlined_functions[address.function],
+                    address.range.begin,
+                ));
                 inlined_addresses = &inlined_addresses[index + 1..];
             } else {
                 break
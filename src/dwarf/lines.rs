@@ -88,6 +88,8 @@ pub(crate) struct LineRow {
     pub(crate) file_index: u64,
     pub(crate) line: u32,
     pub(crate) column: u32,
+    pub(crate) discriminator: u32,
+    pub(crate) is_stmt: bool,
 }
 
 pub(crate) struct Lines<'dwarf> {
@@ -125,12 +127,16 @@ impl<'dwarf> Lines<'dwarf> {
                 gimli::ColumnType::LeftEdge => 0,
                 gimli::ColumnType::Column(x) => x.get() as u32,
             };
+            let discriminator = row.discriminator() as u32;
+            let is_stmt = row.is_stmt();
 
             if let Some(last_row) = sequence_rows.last_mut() {
                 if last_row.address == address {
                     last_row.file_index = file_index;
                     last_row.line = line;
                     last_row.column = column;
+                    last_row.discriminator = discriminator;
+                    last_row.is_stmt = is_stmt;
                     continue
                 }
             }
@@ -140,6 +146,8 @@ impl<'dwarf> Lines<'dwarf> {
                 file_index,
                 line,
                 column,
+                discriminator,
+                is_stmt,
             });
         }
         sequences.sort_by_key(|x| x.start);
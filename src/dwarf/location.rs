@@ -46,6 +46,8 @@ pub struct Location<'dwarf> {
     pub line: Option<u32>,
     /// The column number.
     pub column: Option<u32>,
+    /// The discriminator of the basic block.
+    pub discriminator: Option<u32>,
 }
 
 
@@ -144,6 +146,11 @@ impl<'unit, 'dwarf> Iterator for LocationRangeUnitIter<'unit, 'dwarf> {
                             } else {
                                 None
                             },
+                            discriminator: if row.discriminator != 0 {
+                                Some(row.discriminator)
+                            } else {
+                                None
+                            },
                         },
                     );
                     self.row_idx += 1;
@@ -1,7 +1,9 @@
 mod debug_link;
+mod dwo;
 mod function;
 mod lines;
 mod location;
+mod names;
 mod range;
 mod reader;
 mod resolver;
@@ -9,4 +11,6 @@ mod unit;
 mod units;
 
 
+pub(crate) use self::debug_link::read_debug_link;
 pub(crate) use self::resolver::DwarfResolver;
+pub use self::resolver::LineRow;
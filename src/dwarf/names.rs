@@ -0,0 +1,512 @@
+//! Support for the DWARF 5 `.debug_names` accelerated name index.
+//!
+//! We only use the index to narrow down the set of compilation units
+//! that have to be searched when looking up a function by name. Unit
+//! level data are then parsed as usual.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use gimli::Reader as _;
+
+use crate::elf::ElfParser;
+use crate::Result;
+
+use super::reader::R;
+
+
+/// The index attribute referring to the compilation unit of an entry.
+const DW_IDX_COMPILE_UNIT: u16 = 1;
+/// The index attribute referring to the type unit of an entry.
+const DW_IDX_TYPE_UNIT: u16 = 2;
+
+
+/// Compute the hash of a name, as used by the index' hash lookup
+/// table.
+///
+/// Names are hashed after case folding. We only support doing so for
+/// ASCII names and return `None` for everything else.
+fn hash(name: &str) -> Option<u32> {
+    if !name.is_ascii() {
+        return None
+    }
+
+    let hash = name.bytes().fold(5381u32, |hash, byte| {
+        hash.wrapping_mul(33)
+            .wrapping_add(u32::from(byte.to_ascii_lowercase()))
+    });
+    Some(hash)
+}
+
+/// Skip over an attribute value of the given form.
+fn skip_form(reader: &mut R<'_>, form: gimli::DwForm, format: gimli::Format) -> gimli::Result<()> {
+    let len = match form {
+        gimli::DW_FORM_flag_present => 0,
+        gimli::DW_FORM_data1 | gimli::DW_FORM_ref1 | gimli::DW_FORM_flag => 1,
+        gimli::DW_FORM_data2 | gimli::DW_FORM_ref2 => 2,
+        gimli::DW_FORM_data4 | gimli::DW_FORM_ref4 => 4,
+        gimli::DW_FORM_data8 | gimli::DW_FORM_ref8 | gimli::DW_FORM_ref_sig8 => 8,
+        gimli::DW_FORM_data16 => 16,
+        gimli::DW_FORM_strp | gimli::DW_FORM_sec_offset | gimli::DW_FORM_ref_addr => {
+            usize::from(format.word_size())
+        }
+        gimli::DW_FORM_udata | gimli::DW_FORM_ref_udata => {
+            let _val = reader.read_uleb128()?;
+            return Ok(())
+        }
+        gimli::DW_FORM_sdata => {
+            let _val = reader.read_sleb128()?;
+            return Ok(())
+        }
+        _ => return Err(gimli::Error::UnknownForm(form)),
+    };
+    reader.skip(len)
+}
+
+/// Read an unsigned attribute value of the given form.
+fn read_form(reader: &mut R<'_>, form: gimli::DwForm, format: gimli::Format) -> gimli::Result<u64> {
+    match form {
+        gimli::DW_FORM_data1 | gimli::DW_FORM_ref1 | gimli::DW_FORM_flag => {
+            reader.read_u8().map(u64::from)
+        }
+        gimli::DW_FORM_data2 | gimli::DW_FORM_ref2 => reader.read_u16().map(u64::from),
+        gimli::DW_FORM_data4 | gimli::DW_FORM_ref4 => reader.read_u32().map(u64::from),
+        gimli::DW_FORM_data8 | gimli::DW_FORM_ref8 => reader.read_u64(),
+        gimli::DW_FORM_udata | gimli::DW_FORM_ref_udata => reader.read_uleb128(),
+        gimli::DW_FORM_sec_offset => reader.read_offset(format).map(|offset| offset as u64),
+        _ => Err(gimli::Error::UnsupportedAttributeForm),
+    }
+}
+
+
+/// An abbreviation describing the attributes of index entries.
+#[derive(Debug)]
+struct Abbrev {
+    /// The index attributes along with their forms.
+    attrs: Box<[(u16, gimli::DwForm)]>,
+}
+
+
+/// A single name index, as contributed by one `.debug_names` header.
+#[derive(Debug)]
+struct NameTable<'dwarf> {
+    /// The DWARF format (32 or 64 bit) in use.
+    format: gimli::Format,
+    /// The `.debug_info` offsets of the compilation units covered.
+    cu_offsets: Box<[usize]>,
+    /// The number of hash buckets.
+    bucket_count: u32,
+    /// The number of names in the index.
+    name_count: u32,
+    /// The hash buckets.
+    buckets: R<'dwarf>,
+    /// The hashes of all names.
+    hashes: R<'dwarf>,
+    /// The `.debug_str` offsets of all names.
+    str_offsets: R<'dwarf>,
+    /// The entry pool offsets of the entries of all names.
+    entry_offsets: R<'dwarf>,
+    /// The abbreviations in use, keyed by their code.
+    abbrevs: HashMap<u64, Abbrev>,
+    /// The entry pool.
+    entry_pool: R<'dwarf>,
+}
+
+impl<'dwarf> NameTable<'dwarf> {
+    /// Parse a name index from `reader`, advancing it past the index.
+    fn parse(reader: &mut R<'dwarf>) -> gimli::Result<Self> {
+        let (len, format) = reader.read_initial_length()?;
+        let mut reader = reader.split(len)?;
+
+        let version = reader.read_u16()?;
+        if version != 5 {
+            return Err(gimli::Error::UnknownVersion(u64::from(version)))
+        }
+        let _padding = reader.read_u16()?;
+        let cu_count = reader.read_u32()?;
+        let local_tu_count = reader.read_u32()?;
+        let foreign_tu_count = reader.read_u32()?;
+        let bucket_count = reader.read_u32()?;
+        let name_count = reader.read_u32()?;
+        let abbrev_table_size = reader.read_u32()?;
+        let augmentation_size = reader.read_u32()?;
+        let () = reader.skip(augmentation_size as usize)?;
+
+        let cu_offsets = (0..cu_count)
+            .map(|_| reader.read_offset(format))
+            .collect::<gimli::Result<Box<[_]>>>()?;
+        let offset_size = usize::from(format.word_size());
+        let () = reader.skip(local_tu_count as usize * offset_size)?;
+        let () = reader.skip(foreign_tu_count as usize * 8)?;
+
+        let buckets = reader.split(bucket_count as usize * 4)?;
+        // The hashes array is only present if there are buckets.
+        let hashes = if bucket_count > 0 {
+            reader.split(name_count as usize * 4)?
+        } else {
+            reader.split(0)?
+        };
+        let str_offsets = reader.split(name_count as usize * offset_size)?;
+        let entry_offsets = reader.split(name_count as usize * offset_size)?;
+
+        let mut abbrev_table = reader.split(abbrev_table_size as usize)?;
+        let mut abbrevs = HashMap::new();
+        loop {
+            let code = abbrev_table.read_uleb128()?;
+            if code == 0 {
+                break
+            }
+            let _tag = abbrev_table.read_uleb128()?;
+            let mut attrs = Vec::new();
+            loop {
+                let idx = abbrev_table.read_uleb128_u16()?;
+                let form = abbrev_table.read_uleb128_u16()?;
+                if idx == 0 && form == 0 {
+                    break
+                }
+                let () = attrs.push((idx, gimli::DwForm(form)));
+            }
+            let abbrev = Abbrev {
+                attrs: attrs.into_boxed_slice(),
+            };
+            let _prev = abbrevs.insert(code, abbrev);
+        }
+
+        let slf = Self {
+            format,
+            cu_offsets,
+            bucket_count,
+            name_count,
+            buckets,
+            hashes,
+            str_offsets,
+            entry_offsets,
+            abbrevs,
+            entry_pool: reader,
+        };
+        Ok(slf)
+    }
+
+    /// Read the `idx`th 32 bit value from `array`.
+    fn read_u32(array: &R<'dwarf>, idx: u32) -> gimli::Result<u32> {
+        let mut reader = array.clone();
+        let () = reader.skip(idx as usize * 4)?;
+        reader.read_u32()
+    }
+
+    /// Read the `idx`th offset from `array`.
+    fn read_offset(&self, array: &R<'dwarf>, idx: u32) -> gimli::Result<usize> {
+        let mut reader = array.clone();
+        let () = reader.skip(idx as usize * usize::from(self.format.word_size()))?;
+        reader.read_offset(self.format)
+    }
+
+    /// Collect the `.debug_info` offsets of the compilation units
+    /// referenced by the entries of the name with index `idx`.
+    fn collect_units(&self, idx: u32, units: &mut HashSet<usize>) -> gimli::Result<()> {
+        let offset = self.read_offset(&self.entry_offsets, idx)?;
+        let mut entries = self.entry_pool.clone();
+        let () = entries.skip(offset)?;
+
+        loop {
+            let code = entries.read_uleb128()?;
+            if code == 0 {
+                break
+            }
+            let abbrev = self
+                .abbrevs
+                .get(&code)
+                .ok_or(gimli::Error::UnknownAbbreviation(code))?;
+
+            let mut cu_idx = None;
+            let mut is_type = false;
+            for (attr, form) in abbrev.attrs.iter() {
+                match *attr {
+                    DW_IDX_COMPILE_UNIT => {
+                        cu_idx = Some(read_form(&mut entries, *form, self.format)?);
+                    }
+                    DW_IDX_TYPE_UNIT => {
+                        let () = skip_form(&mut entries, *form, self.format)?;
+                        is_type = true;
+                    }
+                    _ => skip_form(&mut entries, *form, self.format)?,
+                }
+            }
+
+            if is_type {
+                continue
+            }
+
+            // If the index covers only a single compilation unit, the
+            // attribute may be omitted.
+            let cu_idx = match cu_idx {
+                Some(cu_idx) => cu_idx,
+                None if self.cu_offsets.len() == 1 => 0,
+                None => continue,
+            };
+            if let Some(cu_offset) = usize::try_from(cu_idx)
+                .ok()
+                .and_then(|cu_idx| self.cu_offsets.get(cu_idx))
+            {
+                let _new = units.insert(*cu_offset);
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the compilation units containing entries with the given
+    /// name.
+    fn find_units(
+        &self,
+        name: &str,
+        hash: u32,
+        debug_str: &gimli::DebugStr<R<'dwarf>>,
+        units: &mut HashSet<usize>,
+    ) -> gimli::Result<()> {
+        let is_match = |idx| -> gimli::Result<bool> {
+            let offset = self.read_offset(&self.str_offsets, idx)?;
+            let s = debug_str.get_str(gimli::DebugStrOffset(offset))?;
+            Ok(s.slice() == name.as_bytes())
+        };
+
+        if self.bucket_count == 0 {
+            // Without a hash table we have to check all names.
+            for idx in 0..self.name_count {
+                if is_match(idx)? {
+                    let () = self.collect_units(idx, units)?;
+                }
+            }
+            return Ok(())
+        }
+
+        let bucket = hash % self.bucket_count;
+        // Name indices in the bucket array are one-based, with zero
+        // indicating an empty bucket.
+        let start = Self::read_u32(&self.buckets, bucket)?;
+        if start == 0 {
+            return Ok(())
+        }
+
+        for idx in start - 1..self.name_count {
+            let name_hash = Self::read_u32(&self.hashes, idx)?;
+            if name_hash % self.bucket_count != bucket {
+                break
+            }
+            if name_hash == hash && is_match(idx)? {
+                let () = self.collect_units(idx, units)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// The compilation units that may contain a given name, as determined
+/// with the help of a [`NameIndex`].
+#[derive(Debug)]
+pub(super) struct UnitCandidates<'idx> {
+    /// The `.debug_info` offsets of all units covered by the index.
+    indexed: &'idx HashSet<usize>,
+    /// The `.debug_info` offsets of units containing the name.
+    matches: HashSet<usize>,
+}
+
+impl UnitCandidates<'_> {
+    /// Check whether the unit at the provided `.debug_info` offset may
+    /// contain the name.
+    ///
+    /// Units not covered by the index are always considered
+    /// candidates.
+    pub(super) fn contains(&self, offset: usize) -> bool {
+        self.matches.contains(&offset) || !self.indexed.contains(&offset)
+    }
+}
+
+
+/// The contents of a `.debug_names` section.
+///
+/// A section may contain multiple indices (e.g., one per compilation
+/// unit if the linker did not merge them).
+#[derive(Debug)]
+pub(crate) struct NameIndex<'dwarf> {
+    /// All name indices contained in the section.
+    tables: Box<[NameTable<'dwarf>]>,
+    /// The `.debug_info` offsets of all units covered by any index.
+    indexed: HashSet<usize>,
+}
+
+impl<'dwarf> NameIndex<'dwarf> {
+    /// Parse the name index contained in `data`.
+    pub(super) fn parse(mut data: R<'dwarf>) -> gimli::Result<Self> {
+        let mut tables = Vec::new();
+        while !data.is_empty() {
+            let () = tables.push(NameTable::parse(&mut data)?);
+        }
+
+        let indexed = tables
+            .iter()
+            .flat_map(|table| table.cu_offsets.iter().copied())
+            .collect();
+        let slf = Self {
+            tables: tables.into_boxed_slice(),
+            indexed,
+        };
+        Ok(slf)
+    }
+
+    /// Load the name index from the `.debug_names` section of the ELF
+    /// file represented by `parser`, if present.
+    pub(crate) fn load(parser: &'dwarf ElfParser) -> Result<Option<Self>> {
+        let idx = match parser.find_section(".debug_names")? {
+            Some(idx) => idx,
+            None => return Ok(None),
+        };
+        let data = parser.section_data(idx)?;
+        let index = Self::parse(R::new(data, Default::default()))?;
+        Ok(Some(index))
+    }
+
+    /// Determine the compilation units that may contain entries with
+    /// the given name.
+    ///
+    /// `None` is returned if the name can't be looked up using the
+    /// index, in which case all units have to be considered.
+    pub(super) fn find_units(
+        &self,
+        name: &str,
+        debug_str: &gimli::DebugStr<R<'dwarf>>,
+    ) -> gimli::Result<Option<UnitCandidates<'_>>> {
+        let hash = match hash(name) {
+            Some(hash) => hash,
+            None => return Ok(None),
+        };
+
+        let mut matches = HashSet::new();
+        for table in self.tables.iter() {
+            let () = table.find_units(name, hash, debug_str, &mut matches)?;
+        }
+
+        let candidates = UnitCandidates {
+            indexed: &self.indexed,
+            matches,
+        };
+        Ok(Some(candidates))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    /// Create a `.debug_names` section covering two compilation units,
+    /// at `.debug_info` offsets `0x0` and `0x100`, and containing the
+    /// provided names (as `.debug_str` offsets), each with entries in
+    /// the given units.
+    fn debug_names(names: &[(u32, &[u8])], bucket_count: u32) -> Vec<u8> {
+        let abbrevs = [
+            // Abbreviation 1: DW_TAG_subprogram with DW_IDX_compile_unit
+            // as DW_FORM_data1 and DW_IDX_die_offset as DW_FORM_ref4.
+            1u8, 0x2e, 1, 0x0b, 3, 0x13, 0, 0, // End of abbreviations.
+            0,
+        ];
+
+        let mut names = names.to_vec();
+        let strs = ["main", "foo", "bar"];
+        let hash_of = |offset: u32| hash(strs[offset as usize / 8]).unwrap();
+        if bucket_count > 0 {
+            let () = names.sort_by_key(|(offset, _)| hash_of(*offset) % bucket_count);
+        }
+
+        let mut buckets = vec![0u32; bucket_count as usize];
+        let mut entries = Vec::new();
+        let mut entry_offsets = Vec::new();
+        for (idx, (offset, cus)) in names.iter().enumerate() {
+            if bucket_count > 0 {
+                let bucket = &mut buckets[(hash_of(*offset) % bucket_count) as usize];
+                if *bucket == 0 {
+                    *bucket = idx as u32 + 1;
+                }
+            }
+            let () = entry_offsets.push(entries.len() as u32);
+            for cu in cus.iter() {
+                let () = entries.extend_from_slice(&[1, *cu, 0x2a, 0, 0, 0]);
+            }
+            let () = entries.push(0);
+        }
+
+        let mut body = Vec::new();
+        let () = body.extend_from_slice(&5u16.to_ne_bytes());
+        let () = body.extend_from_slice(&0u16.to_ne_bytes());
+        for val in [
+            2,
+            0,
+            0,
+            bucket_count,
+            names.len() as u32,
+            abbrevs.len() as u32,
+            0,
+        ] {
+            let () = body.extend_from_slice(&u32::to_ne_bytes(val));
+        }
+        for cu_offset in [0x0u32, 0x100] {
+            let () = body.extend_from_slice(&cu_offset.to_ne_bytes());
+        }
+        for bucket in buckets {
+            let () = body.extend_from_slice(&bucket.to_ne_bytes());
+        }
+        if bucket_count > 0 {
+            for (offset, _) in &names {
+                let () = body.extend_from_slice(&hash_of(*offset).to_ne_bytes());
+            }
+        }
+        for (offset, _) in &names {
+            let () = body.extend_from_slice(&offset.to_ne_bytes());
+        }
+        for offset in entry_offsets {
+            let () = body.extend_from_slice(&offset.to_ne_bytes());
+        }
+        let () = body.extend_from_slice(&abbrevs);
+        let () = body.extend_from_slice(&entries);
+
+        let mut data = (body.len() as u32).to_ne_bytes().to_vec();
+        let () = data.extend_from_slice(&body);
+        data
+    }
+
+    /// Check that we can look up the units containing a name using a
+    /// `.debug_names` index.
+    #[test]
+    fn name_index_lookup() {
+        let debug_str = b"main\0\0\0\0foo\0\0\0\0\0bar\0";
+        let debug_str = gimli::DebugStr::from(R::new(debug_str, Default::default()));
+
+        for bucket_count in [0, 1, 3] {
+            let data = debug_names(&[(0, &[0]), (8, &[0, 1]), (16, &[1])], bucket_count);
+            let index = NameIndex::parse(R::new(&data, Default::default())).unwrap();
+
+            let units = index.find_units("main", &debug_str).unwrap().unwrap();
+            assert!(units.contains(0x0));
+            assert!(!units.contains(0x100));
+            // Units not covered by the index are always candidates.
+            assert!(units.contains(0x200));
+
+            let units = index.find_units("foo", &debug_str).unwrap().unwrap();
+            assert!(units.contains(0x0));
+            assert!(units.contains(0x100));
+
+            let units = index.find_units("bar", &debug_str).unwrap().unwrap();
+            assert!(!units.contains(0x0));
+            assert!(units.contains(0x100));
+
+            let units = index.find_units("baz", &debug_str).unwrap().unwrap();
+            assert!(!units.contains(0x0));
+            assert!(!units.contains(0x100));
+        }
+
+        let data = debug_names(&[], 0);
+        let index = NameIndex::parse(R::new(&data, Default::default())).unwrap();
+        assert!(index.find_units("mäin", &debug_str).unwrap().is_none());
+    }
+}
@@ -16,7 +16,16 @@ pub(crate) type R<'dat> = EndianSlice<'dat, Endianess>;
 
 
 pub(super) fn load_section(parser: &ElfParser, id: SectionId) -> Result<R<'_>> {
-    let result = parser.find_section(id.name())?;
+    let mut result = parser.find_section(id.name())?;
+    if result.is_none() {
+        // Fall back to the legacy GNU style compressed variant of the
+        // section, if any. Decompression happens transparently.
+        if let Some(suffix) = id.name().strip_prefix(".debug_") {
+            result = parser
+                .find_section_matching(|name| name.strip_prefix(".zdebug_") == Some(suffix))?;
+        }
+    }
+
     let data = match result {
         Some(idx) => parser.section_data(idx)?,
         // Make sure to return empty data if a section does not exist.
@@ -29,3 +38,29 @@ pub(super) fn load_section(parser: &ElfParser, id: SectionId) -> Result<R<'_>> {
     let reader = EndianSlice::new(data, gimli::BigEndian);
     Ok(reader)
 }
+
+
+/// Load a section of a split DWARF object, i.e., a `.dwo` file or
+/// `.dwp` package.
+///
+/// Sections in such objects use a `.dwo` suffix (e.g.,
+/// `.debug_info.dwo`).
+pub(super) fn load_dwo_section(parser: &ElfParser, id: SectionId) -> Result<R<'_>> {
+    let result = match id.dwo_name() {
+        Some(name) => parser.find_section(name)?,
+        None => None,
+    };
+
+    let data = match result {
+        Some(idx) => parser.section_data(idx)?,
+        // Make sure to return empty data if a section does not exist.
+        None => &[],
+    };
+    Ok(R::new(data, Endianess::default()))
+}
+
+/// Create an empty reader.
+#[inline]
+pub(super) fn empty() -> R<'static> {
+    R::new(&[], Endianess::default())
+}
@@ -8,6 +8,7 @@ use std::fmt::Result as FmtResult;
 use std::mem;
 use std::mem::swap;
 use std::ops::Deref as _;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -43,6 +44,7 @@ use super::debug_link::read_debug_link;
 use super::debug_link::DebugFileIter;
 use super::function::Function;
 use super::location::Location;
+use super::names::NameIndex;
 use super::reader;
 use super::unit::Unit;
 use super::units::Units;
@@ -84,6 +86,40 @@ impl From<Option<gimli::DwLang>> for SrcLang {
 }
 
 
+/// A row of a DWARF line number program.
+///
+/// Rows are reported as decoded, without any of the post-processing
+/// that goes into the source code information reported as part of
+/// symbolization. As such, this type is mostly useful for diagnostic
+/// purposes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineRow<'src> {
+    /// The address at which the row starts.
+    pub addr: Addr,
+    /// The directory containing the source file.
+    pub dir: &'src Path,
+    /// The source file.
+    pub file: &'src OsStr,
+    /// The line number, or zero if the row does not correspond to any
+    /// source line.
+    pub line: u32,
+    /// The column number, or zero for the "left edge" of the line.
+    pub column: u32,
+    /// Whether the row marks a recommended breakpoint location (i.e., a
+    /// statement boundary).
+    pub is_stmt: bool,
+    /// Whether the row marks the first address past the end of a
+    /// sequence of contiguous instructions.
+    ///
+    /// All other members (besides `addr`) are taken over from the
+    /// preceding row.
+    pub end_sequence: bool,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
 /// Find a debug file in a list of default directories.
 ///
 /// `linker` is the path to the file containing the debug link. This function
@@ -184,7 +220,14 @@ impl DwarfResolver {
         // much effort the linker spent on optimizing it.
         let () = dwarf.populate_abbreviations_cache(AbbreviationsCacheStrategy::Duplicates);
 
-        let units = Units::parse(dwarf)?;
+        // The name index is merely an accelerator. Failure to parse it
+        // should not prevent us from using the debug information.
+        let names = NameIndex::load(static_parser).unwrap_or_else(|err| {
+            warn!("failed to parse .debug_names section: {err:#}");
+            None
+        });
+        let mut units = Units::parse(dwarf, parser.path())?;
+        let () = units.set_name_index(names);
         let slf = Self {
             units,
             parser,
@@ -193,6 +236,48 @@ impl DwarfResolver {
         Ok(slf)
     }
 
+    /// Invoke `f` for each row of the line programs of all compilation
+    /// units, along with the address range it covers.
+    #[cfg(feature = "breakpad")]
+    pub(crate) fn for_each_line(
+        &self,
+        f: &mut dyn FnMut(Range<Addr>, &Path, &OsStr, u32),
+    ) -> Result<()> {
+        let () = self.units.for_each_line(f)?;
+        Ok(())
+    }
+
+    /// Eagerly parse all data required for lookups with the given
+    /// options.
+    pub(crate) fn warmup(&self, opts: &FindSymOpts) -> Result<()> {
+        let parser = self.linkee_parser.as_ref().unwrap_or(&self.parser);
+        let () = parser.warmup()?;
+        let () = self.units.warmup(opts)?;
+        Ok(())
+    }
+
+    /// Retrieve the line number program rows covering the function
+    /// containing `addr`.
+    ///
+    /// If no function is found, an empty `Vec` is returned. If the
+    /// function's size is unknown, only rows starting at `addr` are
+    /// reported.
+    pub(crate) fn line_rows(&self, addr: Addr) -> Result<Vec<LineRow<'_>>> {
+        let range = match self.find_sym(addr, &FindSymOpts::Basic)? {
+            Ok(sym) => {
+                let end = sym
+                    .size
+                    .map(|size| sym.addr.saturating_add(size as Addr))
+                    .unwrap_or(addr);
+                sym.addr..end.max(addr.saturating_add(1))
+            }
+            Err(_reason) => return Ok(Vec::new()),
+        };
+
+        let rows = self.units.line_rows(range)?;
+        Ok(rows)
+    }
+
     /// Open a binary to load and parse .debug_line for later uses.
     ///
     /// `filename` is the name of an ELF binary/or shared object that
@@ -226,6 +311,7 @@ impl Symbolize for DwarfResolver {
                 addr: fn_addr,
                 size,
                 lang: unit.language().into(),
+                section: self.parser.find_section_name_by_addr(addr)?,
                 code_info: None,
                 inlined: Box::new([]),
             }
@@ -249,6 +335,27 @@ impl Symbolize for DwarfResolver {
 
         Ok(Ok(sym))
     }
+
+    fn fill_code_info<'slf>(
+        &'slf self,
+        sym: &mut ResolvedSym<'slf>,
+        addr: Addr,
+        opts: &FindSymOpts,
+    ) -> Result<()> {
+        if !opts.code_info() {
+            return Ok(())
+        }
+
+        // We still need the function to find inlined functions, but we
+        // skip the retrieval of basic symbol data.
+        let data = if opts.inlined_fns() {
+            self.units.find_function(addr)?
+        } else {
+            None
+        };
+        let () = self.units.fill_code_info(sym, addr, opts, data)?;
+        Ok(())
+    }
 }
 
 impl Inspect for DwarfResolver {
@@ -353,6 +460,7 @@ impl<'dwarf> Units<'dwarf> {
             file,
             line,
             column,
+            discriminator,
         } = direct_location;
 
         let mut direct_code_info = CodeInfo {
@@ -360,21 +468,28 @@ impl<'dwarf> Units<'dwarf> {
             file: Cow::Borrowed(file),
             line,
             column: column.map(|col| col.try_into().unwrap_or(u16::MAX)),
+            discriminator,
             _non_exhaustive: (),
         };
 
         let inlined = if opts.inlined_fns() {
             if let Some((function, unit)) = data {
-                if let Some(inline_stack) = self.find_inlined_functions(addr, function, unit)? {
+                if let Some(inline_stack) =
+                    self.find_inlined_functions(addr, function, unit, opts.max_inline_depth())?
+                {
+                    // Because source code locations are moved up by one
+                    // frame below, the last inlined function reported
+                    // always carries the location of `addr` itself.
                     let mut inlined = Vec::<InlinedFn>::with_capacity(inline_stack.len());
                     for result in inline_stack {
-                        let (name, location) = result?;
+                        let (name, location, inlined_addr) = result?;
                         let mut code_info = location.map(|location| {
                             let Location {
                                 dir,
                                 file,
                                 line,
                                 column,
+                                discriminator,
                             } = location;
 
                             CodeInfo {
@@ -382,6 +497,7 @@ impl<'dwarf> Units<'dwarf> {
                                 file: Cow::Borrowed(file),
                                 line,
                                 column: column.map(|col| col.try_into().unwrap_or(u16::MAX)),
+                                discriminator,
                                 _non_exhaustive: (),
                             }
                         });
@@ -398,6 +514,8 @@ impl<'dwarf> Units<'dwarf> {
 
                         let inlined_fn = InlinedFn {
                             name: Cow::Borrowed(name),
+                            addr: Some(inlined_addr),
+                            offset: Some((addr - inlined_addr) as usize),
                             code_info,
                             _non_exhaustive: (),
                         };
@@ -25,8 +25,10 @@
 // > IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // > DEALINGS IN THE SOFTWARE.
 
+use crate::log::warn;
 use crate::once::OnceCell;
 
+use super::dwo::DwoUnit;
 use super::function::Function;
 use super::function::Functions;
 use super::lines::Lines;
@@ -49,6 +51,9 @@ pub(super) struct Unit<'dwarf> {
     lang: Option<gimli::DwLang>,
     lines: OnceCell<Lines<'dwarf>>,
     funcs: OnceCell<Functions<'dwarf>>,
+    /// The split unit corresponding to this unit, if it is a skeleton
+    /// unit and the split unit could be found.
+    dwo: OnceCell<Option<DwoUnit<'dwarf>>>,
 }
 
 impl<'dwarf> Unit<'dwarf> {
@@ -64,6 +69,50 @@ impl<'dwarf> Unit<'dwarf> {
             lang,
             lines,
             funcs: OnceCell::new(),
+            dwo: OnceCell::new(),
+        }
+    }
+
+    /// Retrieve a [`gimli::UnitRef`] for the unit containing the
+    /// debugging information entries of this unit.
+    ///
+    /// For skeleton units that is the corresponding split unit, loaded
+    /// on first use. For all other units (and skeleton units whose split
+    /// unit could not be loaded) it is the unit itself.
+    ///
+    /// Note that line information always resides with the unit itself.
+    pub(super) fn die_unit_ref<'unit>(
+        &'unit self,
+        units: &'unit Units<'dwarf>,
+    ) -> gimli::UnitRef<'unit, R<'dwarf>> {
+        let dwo_id = match self.dw_unit.dwo_id {
+            Some(dwo_id) => dwo_id,
+            None => return units.unit_ref(&self.dw_unit),
+        };
+
+        let dwo = self.dwo.get_or_init(|| {
+            match units.load_dwo_unit(&self.dw_unit, dwo_id) {
+                Ok(Some(dwo)) => Some(dwo),
+                Ok(None) => {
+                    warn!(
+                        "split DWARF unit {:#x} not found; debug information will be incomplete",
+                        dwo_id.0
+                    );
+                    None
+                }
+                Err(err) => {
+                    warn!(
+                        "failed to load split DWARF unit {:#x}: {err:#}; debug information will be incomplete",
+                        dwo_id.0
+                    );
+                    None
+                }
+            }
+        });
+
+        match dwo {
+            Some(dwo) => dwo.unit_ref(),
+            None => units.unit_ref(&self.dw_unit),
         }
     }
 
@@ -73,7 +122,7 @@ impl<'dwarf> Unit<'dwarf> {
         &'unit self,
         units: &Units<'dwarf>,
     ) -> Result<&'unit Functions<'dwarf>, gimli::Error> {
-        let unit = units.unit_ref(&self.dw_unit);
+        let unit = self.die_unit_ref(units);
         let functions = self.parse_functions_dwarf_and_unit(unit, units)?;
         Ok(functions)
     }
@@ -85,7 +134,7 @@ impl<'dwarf> Unit<'dwarf> {
         units: &Units<'dwarf>,
     ) -> Result<&'unit Functions<'dwarf>, gimli::Error> {
         self.funcs.get_or_try_init(|| {
-            let unit = units.unit_ref(&self.dw_unit);
+            let unit = self.die_unit_ref(units);
             let funcs = Functions::parse(unit, units)?;
             let () = funcs.parse_inlined_functions(unit, units)?;
             Ok(funcs)
@@ -135,7 +184,7 @@ impl<'dwarf> Unit<'dwarf> {
         probe: u64,
         units: &Units<'dwarf>,
     ) -> Result<Option<&Function<'dwarf>>, gimli::Error> {
-        let unit = units.unit_ref(&self.dw_unit);
+        let unit = self.die_unit_ref(units);
         let functions = self.parse_functions_dwarf_and_unit(unit, units)?;
         let function = match functions.find_address(probe) {
             Some(address) => {
@@ -153,7 +202,7 @@ impl<'dwarf> Unit<'dwarf> {
         name: &str,
         units: &Units<'dwarf>,
     ) -> Result<Option<&'slf Function<'dwarf>>, gimli::Error> {
-        let unit = units.unit_ref(&self.dw_unit);
+        let unit = self.die_unit_ref(units);
         let functions = self.parse_functions_dwarf_and_unit(unit, units)?;
         for func in functions.functions.iter() {
             let name = Some(name.as_bytes());
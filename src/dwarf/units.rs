@@ -25,16 +25,27 @@
 // > IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // > DEALINGS IN THE SOFTWARE.
 
+use std::ffi::OsStr;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::ptr;
+
 use crate::log::warn;
 use crate::once::OnceCell;
+use crate::symbolize::FindSymOpts;
 use crate::ErrorExt as _;
 use crate::Result;
 
+use super::dwo::DwoUnit;
+use super::dwo::DwpPackage;
 use super::function::Function;
 use super::lines::Lines;
 use super::location::Location;
+use super::names::NameIndex;
 use super::range::RangeAttributes;
 use super::reader::R;
+use super::resolver::LineRow;
 use super::unit::Unit;
 use super::unit::UnitRange;
 
@@ -58,10 +69,17 @@ pub(crate) struct Units<'dwarf> {
     unit_ranges: Box<[UnitRange]>,
     /// All units along with meta-data.
     units: Box<[Unit<'dwarf>]>,
+    /// The path to the file containing the DWARF data, if known. Used
+    /// for locating split DWARF data.
+    path: Option<PathBuf>,
+    /// The DWARF package belonging to the file, if any.
+    dwp: OnceCell<Option<DwpPackage<'dwarf>>>,
+    /// The `.debug_names` index of the file, if any.
+    names: Option<NameIndex<'dwarf>>,
 }
 
 impl<'dwarf> Units<'dwarf> {
-    pub(crate) fn parse(sections: gimli::Dwarf<R<'dwarf>>) -> Result<Self> {
+    pub(crate) fn parse(sections: gimli::Dwarf<R<'dwarf>>, path: Option<&Path>) -> Result<Self> {
         // Find all the references to compilation units in .debug_aranges.
         // Note that we always also iterate through all of .debug_info to
         // find compilation units, because .debug_aranges may be missing some.
@@ -227,10 +245,51 @@ impl<'dwarf> Units<'dwarf> {
             dwarf: sections,
             unit_ranges: unit_ranges.into_boxed_slice(),
             units: res_units.into_boxed_slice(),
+            path: path.map(Path::to_path_buf),
+            dwp: OnceCell::new(),
+            names: None,
         };
         Ok(slf)
     }
 
+    /// Set the `.debug_names` index to use for accelerating lookups by
+    /// name.
+    pub(crate) fn set_name_index(&mut self, names: Option<NameIndex<'dwarf>>) {
+        self.names = names;
+    }
+
+    /// Load the split unit with ID `dwo_id` corresponding to the
+    /// provided skeleton unit.
+    ///
+    /// The split unit is looked up in the file's DWARF package, if
+    /// present, and in the `.dwo` file referenced by the skeleton unit
+    /// otherwise.
+    pub(super) fn load_dwo_unit(
+        &self,
+        skeleton: &gimli::Unit<R<'dwarf>>,
+        dwo_id: gimli::DwoId,
+    ) -> Result<Option<DwoUnit<'dwarf>>> {
+        let dwp = self.dwp.get_or_init(|| {
+            let path = self.path.as_deref()?;
+            DwpPackage::load(path).unwrap_or_else(|err| {
+                warn!(
+                    "failed to load DWARF package for `{}`: {err:#}",
+                    path.display()
+                );
+                None
+            })
+        });
+
+        if let Some(dwp) = dwp {
+            if let Some(dwo) = dwp.find_unit(&self.dwarf, skeleton, dwo_id)? {
+                return Ok(Some(dwo))
+            }
+        }
+
+        let obj_dir = self.path.as_deref().and_then(Path::parent);
+        DwoUnit::load(&self.dwarf, skeleton, dwo_id, obj_dir)
+    }
+
     /// Find the unit containing the given offset, and convert the
     /// offset into a unit offset.
     pub(super) fn find_unit(
@@ -338,23 +397,34 @@ impl<'dwarf> Units<'dwarf> {
         Ok(None)
     }
 
-    /// Find the list of inlined functions that contain `probe`.
+    /// Find the list of inlined functions that contain `probe`, up to
+    /// a nesting depth of `max_depth`.
+    ///
+    /// Each function is reported along with the start address of its
+    /// address range covering `probe`.
     pub(super) fn find_inlined_functions<'slf>(
         &'slf self,
         probe: u64,
         function: &'slf Function<'dwarf>,
         unit: &'slf Unit<'dwarf>,
+        max_depth: usize,
     ) -> Result<
         Option<
             impl ExactSizeIterator<
-                    Item = Result<(&'dwarf str, Option<Location<'slf>>), gimli::Error>,
+                    Item = Result<(&'dwarf str, Option<Location<'slf>>, u64), gimli::Error>,
                 > + 'slf,
         >,
         gimli::Error,
     > {
+        // Line information always resides with the (skeleton) unit
+        // itself, while inlined function information may be contained in
+        // a split unit.
         let unit_ref = gimli::UnitRef::new(&self.dwarf, unit.dw_unit());
-        let inlined_fns = function.parse_inlined_functions(unit_ref, self)?;
-        let iter = inlined_fns.find_inlined_functions(probe).map(move |inlined_fn| {
+        let die_unit_ref = unit.die_unit_ref(self);
+        let inlined_fns = function.parse_inlined_functions(die_unit_ref, self)?;
+        let iter = inlined_fns
+            .find_inlined_functions(probe, max_depth)
+            .map(move |(inlined_fn, addr)| {
             let name = inlined_fn
                 .name
                 .map(|name| name.to_string())
@@ -364,11 +434,22 @@ impl<'dwarf> Units<'dwarf> {
             let code_info = if let Some(call_file) = inlined_fn.call_file {
                 if let Some(lines) = unit.parse_lines(unit_ref)? {
                     if let Some((dir, file)) = lines.files.get(call_file as usize) {
+                        // A line or column of zero means "unknown", as
+                        // is the case for missing attributes.
                         let code_info = Location {
                             dir,
                             file,
-                            line: Some(inlined_fn.call_line),
-                            column: Some(inlined_fn.call_column),
+                            line: if inlined_fn.call_line != 0 {
+                                Some(inlined_fn.call_line)
+                            } else {
+                                None
+                            },
+                            column: if inlined_fn.call_column != 0 {
+                                Some(inlined_fn.call_column)
+                            } else {
+                                None
+                            },
+                            discriminator: None,
                         };
                         Some(code_info)
                     } else {
@@ -383,7 +464,7 @@ impl<'dwarf> Units<'dwarf> {
             } else {
                 None
             };
-            Ok((name, code_info))
+            Ok((name, code_info, addr))
         });
         Ok(Some(iter))
     }
@@ -399,12 +480,122 @@ impl<'dwarf> Units<'dwarf> {
         Ok(None)
     }
 
+    /// Invoke `f` for each row of the line programs of all units,
+    /// along with the address range it covers.
+    #[cfg(feature = "breakpad")]
+    pub(crate) fn for_each_line(
+        &self,
+        f: &mut dyn FnMut(Range<u64>, &Path, &OsStr, u32),
+    ) -> Result<(), gimli::Error> {
+        for unit in self.units.iter() {
+            let lines = match unit.parse_lines(self.unit_ref(unit.dw_unit()))? {
+                Some(lines) => lines,
+                None => continue,
+            };
+
+            for sequence in lines.sequences.iter() {
+                for (i, row) in sequence.rows.iter().enumerate() {
+                    let end = sequence
+                        .rows
+                        .get(i + 1)
+                        .map(|next| next.address)
+                        .unwrap_or(sequence.end);
+                    if let Some((dir, file)) = lines.files.get(row.file_index as usize) {
+                        let () = f(row.address..end, dir, file, row.line);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Retrieve the rows of the line programs of all units covering
+    /// `range`, in address order.
+    ///
+    /// Rows starting inside of `range` are reported, as are the end
+    /// markers of sequences ending inside of it.
+    pub(crate) fn line_rows(&self, range: Range<u64>) -> Result<Vec<LineRow<'_>>, gimli::Error> {
+        let mut units = Vec::<&Unit<'dwarf>>::new();
+        for (unit, _range) in self.find_units_range(range.start, range.end) {
+            if !units.iter().any(|other| ptr::eq(*other, unit)) {
+                let () = units.push(unit);
+            }
+        }
+
+        let mut rows = Vec::new();
+        for unit in units {
+            let lines = match unit.parse_lines(self.unit_ref(unit.dw_unit()))? {
+                Some(lines) => lines,
+                None => continue,
+            };
+
+            for sequence in lines.sequences.iter() {
+                if sequence.end <= range.start || sequence.start >= range.end {
+                    continue
+                }
+
+                let mut last = None;
+                for row in sequence.rows.iter() {
+                    if let Some((dir, file)) = lines.files.get(row.file_index as usize) {
+                        let line_row = LineRow {
+                            addr: row.address,
+                            dir,
+                            file,
+                            line: row.line,
+                            column: row.column,
+                            is_stmt: row.is_stmt,
+                            end_sequence: false,
+                            _non_exhaustive: (),
+                        };
+                        if range.contains(&row.address) {
+                            let () = rows.push(line_row.clone());
+                        }
+                        last = Some(line_row);
+                    }
+                }
+
+                if sequence.end > range.start && sequence.end <= range.end {
+                    if let Some(last) = last {
+                        let end = LineRow {
+                            addr: sequence.end,
+                            end_sequence: true,
+                            ..last
+                        };
+                        let () = rows.push(end);
+                    }
+                }
+            }
+        }
+
+        // At any given address, a sequence end marker precedes the
+        // start of the next sequence.
+        let () = rows.sort_by_key(|row| (row.addr, !row.end_sequence));
+        Ok(rows)
+    }
+
     pub(crate) fn find_name<'s, 'slf: 's>(
         &'slf self,
         name: &'s str,
     ) -> impl Iterator<Item = Result<&Function<'dwarf>, gimli::Error>> + 's {
+        // If available, use the name index to skip units that are known
+        // not to contain the name.
+        let candidates = self.names.as_ref().and_then(|names| {
+            names
+                .find_units(name, &self.dwarf.debug_str)
+                .unwrap_or_else(|err| {
+                    warn!("failed to look up `{name}` in .debug_names index: {err}");
+                    None
+                })
+        });
+
         self.units
             .iter()
+            .filter(move |unit| {
+                candidates
+                    .as_ref()
+                    .map(|candidates| candidates.contains(unit.offset().0))
+                    .unwrap_or(true)
+            })
             .filter_map(move |unit| unit.find_name(name, self).transpose())
     }
 
@@ -417,9 +608,20 @@ impl<'dwarf> Units<'dwarf> {
         gimli::UnitRef::new(&self.dwarf, unit)
     }
 
-    /// Initialize all function data structures. This is used for benchmarks.
-    #[cfg(test)]
-    #[cfg(feature = "nightly")]
+    /// Eagerly parse all data structures of all compilation units that
+    /// are required for lookups with the given options.
+    pub(crate) fn warmup(&self, opts: &FindSymOpts) -> Result<(), gimli::Error> {
+        let () = self.parse_functions()?;
+        if opts.code_info() {
+            let () = self.parse_lines()?;
+        }
+        if opts.inlined_fns() {
+            let () = self.parse_inlined_functions()?;
+        }
+        Ok(())
+    }
+
+    /// Initialize all function data structures.
     fn parse_functions(&self) -> Result<(), gimli::Error> {
         for unit in self.units.iter() {
             let _functions = unit.parse_functions(self)?;
@@ -427,10 +629,7 @@ impl<'dwarf> Units<'dwarf> {
         Ok(())
     }
 
-    /// Initialize all inlined function data structures. This is used for
-    /// benchmarks.
-    #[cfg(test)]
-    #[cfg(feature = "nightly")]
+    /// Initialize all inlined function data structures.
     fn parse_inlined_functions(&self) -> Result<(), gimli::Error> {
         for unit in self.units.iter() {
             let _functions = unit.parse_inlined_functions(self)?;
@@ -438,9 +637,7 @@ impl<'dwarf> Units<'dwarf> {
         Ok(())
     }
 
-    /// Initialize all line data structures. This is used for benchmarks.
-    #[cfg(test)]
-    #[cfg(feature = "nightly")]
+    /// Initialize all line data structures.
     fn parse_lines(&self) -> Result<(), gimli::Error> {
         for unit in self.units.iter() {
             let unit_ref = self.unit_ref(unit.dw_unit());
@@ -504,7 +701,7 @@ mod tests {
             let parser = ElfParser::open(bin_name.as_ref()).unwrap();
             let mut load_section = |section| reader::load_section(&parser, section);
             let dwarf = Dwarf::<R>::load(&mut load_section).unwrap();
-            let units = Units::parse(dwarf).unwrap();
+            let units = Units::parse(dwarf, None).unwrap();
 
             // Double check that we actually did what we set out to do
             // by checking that we can find a function that we know
@@ -545,7 +742,7 @@ mod tests {
             let parser = ElfParser::open(bin_name.as_ref()).unwrap();
             let mut load_section = |section| reader::load_section(&parser, section);
             let dwarf = Dwarf::<R>::load(&mut load_section).unwrap();
-            let units = Units::parse(dwarf).unwrap();
+            let units = Units::parse(dwarf, None).unwrap();
 
             // Bogus address typically somewhere in kernel space but
             // unlikely to be in any of our binaries.
@@ -569,7 +766,7 @@ mod tests {
 
         let () = b.iter(|| {
             let dwarf = Dwarf::<R>::load(&mut load_section).unwrap();
-            let units = Units::parse(black_box(dwarf)).unwrap();
+            let units = Units::parse(black_box(dwarf), None).unwrap();
             let _funcs = black_box(units.parse_functions().unwrap());
         });
     }
@@ -600,7 +797,7 @@ mod tests {
 
         let () = b.iter(|| {
             let dwarf = Dwarf::<R>::load(&mut load_section).unwrap();
-            let units = Units::parse(black_box(dwarf)).unwrap();
+            let units = Units::parse(black_box(dwarf), None).unwrap();
             let _lines = black_box(units.parse_inlined_functions().unwrap());
         });
     }
@@ -631,7 +828,7 @@ mod tests {
 
         let () = b.iter(|| {
             let dwarf = Dwarf::<R>::load(&mut load_section).unwrap();
-            let units = Units::parse(black_box(dwarf)).unwrap();
+            let units = Units::parse(black_box(dwarf), None).unwrap();
             let _lines = black_box(units.parse_lines().unwrap());
         });
     }
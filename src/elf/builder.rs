@@ -0,0 +1,390 @@
+//! A builder for synthetic ELF files, for testing purposes.
+
+use std::mem::size_of;
+use std::slice;
+
+use crate::symbolize::SymTable;
+use crate::util::Pod;
+use crate::Addr;
+
+use super::types::Elf64_Ehdr;
+use super::types::Elf64_Shdr;
+use super::types::Elf64_Sym;
+use super::types::Elf64_Word;
+use super::types::SHF_ALLOC;
+use super::types::SHN_UNDEF;
+use super::types::SHT_DYNSYM;
+use super::types::SHT_PROGBITS;
+use super::types::SHT_STRTAB;
+use super::types::SHT_SYMTAB;
+use super::types::STB_GLOBAL;
+use super::types::STT_FUNC;
+
+
+/// View the in-memory representation of `value` as a byte slice.
+fn as_bytes<T>(value: &T) -> &[u8]
+where
+    T: Pod,
+{
+    // SAFETY: `T` is `Pod` and so it is fine to view it as a byte
+    //         slice.
+    unsafe { slice::from_raw_parts((value as *const T).cast::<u8>(), size_of::<T>()) }
+}
+
+/// Append `name` to the string table `strs`, returning its offset.
+fn add_str(strs: &mut Vec<u8>, name: &str) -> Elf64_Word {
+    let offset = strs.len() as Elf64_Word;
+    let () = strs.extend_from_slice(name.as_bytes());
+    let () = strs.push(b'\0');
+    offset
+}
+
+/// Pad `data` with zeroes to the next multiple of eight bytes.
+fn align(data: &mut Vec<u8>) {
+    let () = data.resize((data.len() + 7) / 8 * 8, 0);
+}
+
+
+/// A section to be emitted by an [`ElfBuilder`].
+#[derive(Debug)]
+struct Section {
+    name: String,
+    shdr: Elf64_Shdr,
+    data: Vec<u8>,
+}
+
+
+/// A builder for minimal 64 bit ELF files in native byte order.
+///
+/// The resulting file contains a section header string table, all
+/// sections added explicitly (in order and starting at index one), and
+/// `.symtab` & `.dynsym` sections (along with their string tables) if
+/// symbols were added to them. No program headers are emitted.
+#[derive(Debug, Default)]
+pub(crate) struct ElfBuilder {
+    /// All explicitly added sections.
+    sections: Vec<Section>,
+    /// Symbols of the `.symtab` section, if any, along with their
+    /// names.
+    symtab: Option<Vec<(String, Elf64_Sym)>>,
+    /// Symbols of the `.dynsym` section, if any, along with their
+    /// names.
+    dynsym: Option<Vec<(String, Elf64_Sym)>>,
+}
+
+impl ElfBuilder {
+    /// Create a new builder for an ELF file without any sections.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a section with the given name, type, load address, and
+    /// contents.
+    ///
+    /// The section is marked as `SHF_ALLOC`.
+    pub(crate) fn section(
+        mut self,
+        name: &str,
+        sh_type: Elf64_Word,
+        addr: Addr,
+        data: &[u8],
+    ) -> Self {
+        let shdr = Elf64_Shdr {
+            sh_name: 0,
+            sh_type,
+            sh_flags: SHF_ALLOC,
+            sh_addr: addr,
+            sh_offset: 0,
+            sh_size: data.len() as _,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 1,
+            sh_entsize: 0,
+        };
+        let section = Section {
+            name: name.to_string(),
+            shdr,
+            data: data.to_vec(),
+        };
+        let () = self.sections.push(section);
+        self
+    }
+
+    /// Add a `.text` section of `len` zero bytes loaded at `addr`.
+    pub(crate) fn text(self, addr: Addr, len: usize) -> Self {
+        self.section(".text", SHT_PROGBITS, addr, &vec![0; len])
+    }
+
+    /// Add a symbol to the provided symbol table.
+    ///
+    /// The symbol's `st_name` member is ignored and set to refer to
+    /// `name`. Adding a symbol to a table causes said table to be
+    /// emitted, even if it is otherwise empty (see
+    /// [`ElfBuilder::empty_table`]).
+    pub(crate) fn sym(mut self, table: SymTable, name: &str, sym: Elf64_Sym) -> Self {
+        let syms = match table {
+            SymTable::SymTab => self.symtab.get_or_insert_with(Vec::new),
+            SymTable::DynSym => self.dynsym.get_or_insert_with(Vec::new),
+        };
+        let () = syms.push((name.to_string(), sym));
+        self
+    }
+
+    /// Add a global function symbol to the provided symbol table.
+    ///
+    /// The symbol is associated with the first section covering `addr`
+    /// or is undefined if there is none.
+    pub(crate) fn func(self, table: SymTable, name: &str, addr: Addr, size: u64) -> Self {
+        let shndx = self
+            .sections
+            .iter()
+            .position(|section| {
+                let shdr = &section.shdr;
+                (shdr.sh_addr..shdr.sh_addr + shdr.sh_size).contains(&addr)
+            })
+            .map(|idx| (idx + 1) as u16)
+            .unwrap_or(SHN_UNDEF);
+
+        let sym = Elf64_Sym {
+            st_name: 0,
+            st_info: (STB_GLOBAL << 4) | STT_FUNC,
+            st_other: 0,
+            st_shndx: shndx,
+            st_value: addr,
+            st_size: size,
+        };
+        self.sym(table, name, sym)
+    }
+
+    /// Emit the provided symbol table even if no symbols are added to
+    /// it.
+    pub(crate) fn empty_table(mut self, table: SymTable) -> Self {
+        let _syms = match table {
+            SymTable::SymTab => self.symtab.get_or_insert_with(Vec::new),
+            SymTable::DynSym => self.dynsym.get_or_insert_with(Vec::new),
+        };
+        self
+    }
+
+    /// Assemble the ELF file.
+    pub(crate) fn build(self) -> Vec<u8> {
+        let Self {
+            mut sections,
+            symtab,
+            dynsym,
+        } = self;
+
+        for (table, syms) in [(".symtab", symtab), (".dynsym", dynsym)] {
+            let syms = if let Some(syms) = syms {
+                syms
+            } else {
+                continue
+            };
+
+            let (sh_type, strtab) = if table == ".symtab" {
+                (SHT_SYMTAB, ".strtab")
+            } else {
+                (SHT_DYNSYM, ".dynstr")
+            };
+
+            // The first symbol table entry is reserved.
+            let mut data = as_bytes(&Elf64_Sym {
+                st_name: 0,
+                st_info: 0,
+                st_other: 0,
+                st_shndx: 0,
+                st_value: 0,
+                st_size: 0,
+            })
+            .to_vec();
+            let mut strs = vec![b'\0'];
+            for (name, mut sym) in syms {
+                sym.st_name = add_str(&mut strs, &name);
+                let () = data.extend_from_slice(as_bytes(&sym));
+            }
+
+            // The string table is placed right after the symbol table.
+            let strtab_idx = sections.len() + 2;
+            let shdr = Elf64_Shdr {
+                sh_name: 0,
+                sh_type,
+                sh_flags: 0,
+                sh_addr: 0,
+                sh_offset: 0,
+                sh_size: data.len() as _,
+                sh_link: strtab_idx as _,
+                sh_info: 1,
+                sh_addralign: 8,
+                sh_entsize: size_of::<Elf64_Sym>() as _,
+            };
+            let () = sections.push(Section {
+                name: table.to_string(),
+                shdr,
+                data,
+            });
+
+            let shdr = Elf64_Shdr {
+                sh_name: 0,
+                sh_type: SHT_STRTAB,
+                sh_flags: 0,
+                sh_addr: 0,
+                sh_offset: 0,
+                sh_size: strs.len() as _,
+                sh_link: 0,
+                sh_info: 0,
+                sh_addralign: 1,
+                sh_entsize: 0,
+            };
+            let () = sections.push(Section {
+                name: strtab.to_string(),
+                shdr,
+                data: strs,
+            });
+        }
+
+        let mut shstrs = vec![b'\0'];
+        for section in &mut sections {
+            section.shdr.sh_name = add_str(&mut shstrs, &section.name);
+        }
+        let shstrtab_name = add_str(&mut shstrs, ".shstrtab");
+        let shdr = Elf64_Shdr {
+            sh_name: shstrtab_name,
+            sh_type: SHT_STRTAB,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset: 0,
+            sh_size: shstrs.len() as _,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 1,
+            sh_entsize: 0,
+        };
+        let () = sections.push(Section {
+            name: ".shstrtab".to_string(),
+            shdr,
+            data: shstrs,
+        });
+
+        let mut elf = vec![0; size_of::<Elf64_Ehdr>()];
+        for section in &mut sections {
+            let () = align(&mut elf);
+            section.shdr.sh_offset = elf.len() as _;
+            let () = elf.extend_from_slice(&section.data);
+        }
+        let () = align(&mut elf);
+
+        let shoff = elf.len();
+        let null = Elf64_Shdr {
+            sh_name: 0,
+            sh_type: 0,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset: 0,
+            sh_size: 0,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 0,
+            sh_entsize: 0,
+        };
+        let () = elf.extend_from_slice(as_bytes(&null));
+        for section in &sections {
+            let () = elf.extend_from_slice(as_bytes(&section.shdr));
+        }
+
+        let ehdr = Elf64_Ehdr {
+            e_ident: [
+                127,
+                69,
+                76,
+                70,
+                // ELFCLASS64
+                2,
+                if cfg!(target_endian = "little") { 1 } else { 2 },
+                1,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+            ],
+            // ET_DYN
+            e_type: 3,
+            // EM_X86_64
+            e_machine: 62,
+            e_version: 1,
+            e_entry: 0,
+            e_phoff: 0,
+            e_shoff: shoff as _,
+            e_flags: 0,
+            e_ehsize: size_of::<Elf64_Ehdr>() as _,
+            e_phentsize: 0,
+            e_phnum: 0,
+            e_shentsize: size_of::<Elf64_Shdr>() as _,
+            e_shnum: (sections.len() + 1) as _,
+            // The section header string table is always the last section.
+            e_shstrndx: sections.len() as _,
+        };
+        let () = elf[..size_of::<Elf64_Ehdr>()].copy_from_slice(as_bytes(&ehdr));
+        elf
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::elf::ElfParser;
+    use crate::symbolize::FindSymOpts;
+    use crate::symbolize::Reason;
+
+
+    /// Check that we can parse a synthetic ELF file and look up symbols
+    /// in it.
+    #[test]
+    fn synthetic_elf_parsing() {
+        let elf = ElfBuilder::new()
+            .text(0x1000, 0x100)
+            .func(SymTable::SymTab, "foo", 0x1000, 0x10)
+            .func(SymTable::SymTab, "bar", 0x1010, 0x20)
+            .func(SymTable::DynSym, "foo", 0x1000, 0x10)
+            .build();
+        let parser = ElfParser::from_bytes(&elf, None).unwrap();
+        assert_eq!(parser.find_section(".text").unwrap(), Some(1));
+        assert_eq!(parser.find_section(".symtab").unwrap(), Some(2));
+        assert_eq!(parser.find_section(".dynsym").unwrap(), Some(4));
+
+        let sym = parser
+            .find_sym(0x1018, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "bar");
+        assert_eq!(sym.addr, 0x1010);
+        assert_eq!(sym.size, Some(0x20));
+        assert_eq!(sym.section, Some(".text"));
+
+        let result = parser.find_sym(0x1030, &FindSymOpts::Basic).unwrap();
+        assert_eq!(result, Err(Reason::UnknownAddr));
+    }
+
+    /// Check that we fall back to `.dynsym` for a synthetic ELF file
+    /// with an empty `.symtab`.
+    #[test]
+    fn synthetic_elf_dynsym_fallback() {
+        let elf = ElfBuilder::new()
+            .text(0x1000, 0x100)
+            .empty_table(SymTable::SymTab)
+            .func(SymTable::DynSym, "foo", 0x1000, 0x10)
+            .build();
+        let parser = ElfParser::from_bytes(&elf, None).unwrap();
+        let sym = parser
+            .find_sym(0x1008, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "foo");
+    }
+}
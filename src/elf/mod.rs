@@ -1,3 +1,5 @@
+#[cfg(test)]
+mod builder;
 mod parser;
 #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
 mod resolver;
@@ -11,7 +13,11 @@ pub(crate) mod types;
 //       of concerns that is not a workable location.
 pub(crate) static DEFAULT_DEBUG_DIRS: &[&str] = &["/usr/lib/debug", "/lib/debug/"];
 
+#[cfg(test)]
+pub(crate) use builder::ElfBuilder;
 pub(crate) use parser::ElfParser;
 pub(crate) use resolver::ElfResolverData;
 
+pub use resolver::ElfReloc;
 pub use resolver::ElfResolver;
+pub use resolver::ElfSym;
@@ -1,45 +1,124 @@
 use std::borrow::Cow;
+use std::ffi::CStr;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::fs::File;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::iter;
 use std::mem;
 use std::ops::Deref as _;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 
 use crate::insert_map::InsertMap;
 use crate::inspect::FindAddrOpts;
 use crate::inspect::SymInfo;
+use crate::log;
 use crate::mmap::Mmap;
 use crate::once::OnceCell;
 use crate::symbolize::FindSymOpts;
 use crate::symbolize::Reason;
 use crate::symbolize::ResolvedSym;
 use crate::symbolize::SrcLang;
+use crate::symbolize::SymSource;
+use crate::symbolize::SymStats;
+use crate::symbolize::SymTable;
 use crate::util::find_match_or_lower_bound_by_key;
+use crate::util::Pod;
 use crate::util::ReadRaw as _;
 use crate::Addr;
 use crate::Error;
 use crate::ErrorExt as _;
+use crate::ErrorKind;
 use crate::IntoError as _;
 use crate::Result;
 use crate::SymType;
 
+use super::resolver::ElfSym;
+use super::types::ByteSwap;
+use super::types::Elf32_Chdr;
+use super::types::Elf32_Ehdr;
+use super::types::Elf32_Phdr;
+use super::types::Elf32_Rel;
+use super::types::Elf32_Rela;
+use super::types::Elf32_Shdr;
+use super::types::Elf32_Sym;
 use super::types::Elf64_Chdr;
 use super::types::Elf64_Ehdr;
+use super::types::Elf64_Nhdr;
 use super::types::Elf64_Phdr;
+use super::types::Elf64_Rel;
+use super::types::Elf64_Rela;
 use super::types::Elf64_Shdr;
 use super::types::Elf64_Sym;
+use super::types::Elf64_Word;
+use super::types::EI_CLASS;
+use super::types::EI_DATA;
+use super::types::ELFCLASS32;
+use super::types::ELFCLASS64;
 use super::types::ELFCOMPRESS_ZLIB;
 use super::types::ELFCOMPRESS_ZSTD;
+use super::types::ELFDATA2LSB;
+use super::types::ELFDATA2MSB;
+use super::types::EM_AARCH64;
+use super::types::EM_X86_64;
+use super::types::ET_REL;
 use super::types::PN_XNUM;
 use super::types::PT_LOAD;
+use super::types::SHF_ALLOC;
 use super::types::SHF_COMPRESSED;
+use super::types::SHN_ABS;
+use super::types::SHN_COMMON;
 use super::types::SHN_LORESERVE;
 use super::types::SHN_UNDEF;
 use super::types::SHN_XINDEX;
 use super::types::SHT_NOBITS;
+use super::types::SHT_REL;
+use super::types::SHT_RELA;
+use super::types::SHT_SYMTAB_SHNDX;
+use super::types::STB_GLOBAL;
+use super::types::STB_LOCAL;
+use super::types::STB_WEAK;
+use super::types::STT_NOTYPE;
+use super::types::STV_DEFAULT;
+
+
+/// Iterate over the indices (in address order) of all symbols starting
+/// inside of `range`.
+///
+/// If `spanning` is `true`, the symbol covering `range.start` (as
+/// reported by [`find_sym`]) is included as well, even if it starts
+/// before the range.
+fn syms_in_range<'tab>(
+    symtab: &'tab SymbolTableCache<'_>,
+    range: Range<Addr>,
+    spanning: bool,
+) -> impl Iterator<Item = usize> + 'tab {
+    let value = |idx: &usize| symtab.table[*idx].st_value as Addr;
+    let start = symtab.syms.partition_point(|idx| value(idx) < range.start);
+    let end = symtab.syms.partition_point(|idx| value(idx) < range.end);
+
+    let first = if spanning && !range.is_empty() {
+        covering_syms(symtab, range.start, SymType::Undefined)
+            .next()
+            .filter(|idx| *idx < start)
+    } else {
+        None
+    };
+
+    first
+        .into_iter()
+        .chain(start..end.max(start))
+        .filter(move |idx| {
+            // SANITY: All indices are valid for `symtab`.
+            let sym = symtab.get(*idx).unwrap();
+            !matches!(sym.st_shndx, SHN_UNDEF | SHN_ABS | SHN_COMMON)
+        })
+}
 
 
 fn symbol_name<'mmap>(strtab: &'mmap [u8], sym: &Elf64_Sym) -> Result<&'mmap str> {
@@ -56,50 +135,174 @@ fn symbol_name<'mmap>(strtab: &'mmap [u8], sym: &Elf64_Sym) -> Result<&'mmap str
 }
 
 fn find_sym<'mmap>(
-    symtab: &[&Elf64_Sym],
-    strtab: &'mmap [u8],
+    symtab: &SymbolTableCache<'mmap>,
     addr: Addr,
     type_: SymType,
 ) -> Result<Option<ResolvedSym<'mmap>>> {
-    match find_match_or_lower_bound_by_key(symtab, addr, |sym| sym.st_value as Addr) {
-        None => Ok(None),
-        Some(idx) => {
-            for sym in symtab[idx..].iter() {
-                if sym.st_value as Addr > addr {
-                    // Once we are seeing start addresses past the provided
-                    // address, we can no longer be dealing with a match and
-                    // stop the search.
-                    break
-                }
+    let result = find_sym_with_section(symtab, addr, type_)?;
+    Ok(result.map(|(sym, _shndx)| sym))
+}
 
-                // In ELF, a symbol size of 0 indicates "no size or an unknown
-                // size" (see elf(5)). We take our changes and report these on a
-                // best-effort basis.
-                if sym.matches(type_)
-                    && sym.st_shndx != SHN_UNDEF
-                    && (sym.st_size == 0 || addr < sym.st_value + sym.st_size)
-                {
-                    let sym = ResolvedSym {
-                        name: symbol_name(strtab, sym)?,
-                        addr: sym.st_value as Addr,
-                        size: if sym.st_size == 0 {
-                            None
-                        } else {
-                            Some(usize::try_from(sym.st_size).unwrap_or(usize::MAX))
-                        },
-                        // ELF does not carry any source code language
-                        // information.
-                        lang: SrcLang::Unknown,
-                        // ELF doesn't carry source code location
-                        // information.
-                        code_info: None,
-                        inlined: Box::new([]),
-                    };
-                    return Ok(Some(sym))
-                }
-            }
+/// Check whether `sym` is a compiler generated local symbol, i.e., a
+/// local one that is either unnamed or an assembler local label (such
+/// as `.L*` or `LBB*`).
+fn is_compiler_local(strtab: &[u8], sym: &Elf64_Sym) -> bool {
+    if sym.binding() != STB_LOCAL {
+        return false
+    }
+    if sym.st_name == 0 {
+        return true
+    }
+
+    match symbol_name(strtab, sym) {
+        Ok(name) => name.is_empty() || name.starts_with(".L") || name.starts_with("LBB"),
+        // We don't know what the symbol is. Keep it around and let
+        // the lookup logic deal with it.
+        Err(_err) => false,
+    }
+}
+
+/// Check whether `sym` is a label, i.e., a named `STT_NOTYPE` symbol
+/// defined in a section, as commonly emitted for labels in
+/// hand-written assembly.
+fn is_label(strtab: &[u8], sym: &Elf64_Sym) -> bool {
+    if sym.type_() != STT_NOTYPE
+        || sym.st_name == 0
+        || matches!(sym.st_shndx, SHN_UNDEF | SHN_ABS | SHN_COMMON)
+    {
+        return false
+    }
+
+    match symbol_name(strtab, sym) {
+        // Mapping symbols (`$x`, `$d`, ...) as used on ARM and RISC-V
+        // mark transitions between code and data and do not
+        // constitute labels.
+        Ok(name) => !name.is_empty() && !name.starts_with('$'),
+        Err(_err) => false,
+    }
+}
+
+/// Iterate over the indices (in address order) of all symbols of type
+/// `type_` covering `addr`.
+///
+/// Only symbols starting at the highest address not exceeding `addr`
+/// are considered.
+fn covering_syms<'tab>(
+    symtab: &'tab SymbolTableCache<'_>,
+    addr: Addr,
+    type_: SymType,
+) -> impl Iterator<Item = usize> + 'tab {
+    let start = find_match_or_lower_bound_by_key(&symtab.syms, addr, |idx| {
+        symtab.table[*idx].st_value as Addr
+    });
+
+    start.into_iter().flat_map(move |start| {
+        symtab
+            .iter()
+            .enumerate()
+            .skip(start)
+            // Once we are seeing start addresses past the provided
+            // address, we can no longer be dealing with a match and
+            // stop the search.
+            .take_while(move |(_idx, sym)| sym.st_value as Addr <= addr)
+            // In ELF, a symbol size of 0 indicates "no size or an unknown
+            // size" (see elf(5)). We take our changes and report these on a
+            // best-effort basis.
+            // Undefined symbols as well as absolute and common ones
+            // don't have an address inside the object and are
+            // never reported.
+            .filter(move |(_idx, sym)| {
+                // Compiler generated local labels are untyped. They are
+                // only part of the table if their reporting was
+                // requested, in which case they are treated as
+                // functions.
+                (sym.matches(type_) || (type_ != SymType::Variable && sym.type_() == STT_NOTYPE))
+                    && !matches!(sym.st_shndx, SHN_UNDEF | SHN_ABS | SHN_COMMON)
+                    && (sym.st_size == 0 || addr - sym.st_value < sym.st_size)
+            })
+            .map(|(idx, _sym)| idx)
+    })
+}
+
+/// Create a `ResolvedSym` for the symbol at index `idx` (in address
+/// order), along with the index of the section it is associated with
+/// (if any).
+fn resolve_sym<'mmap>(
+    symtab: &SymbolTableCache<'mmap>,
+    idx: usize,
+) -> Result<(ResolvedSym<'mmap>, Option<usize>)> {
+    let sym = symtab
+        .get(idx)
+        .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
+    let resolved = ResolvedSym {
+        name: symtab.name(idx)?,
+        addr: sym.st_value as Addr,
+        size: if sym.st_size == 0 {
+            None
+        } else {
+            Some(usize::try_from(sym.st_size).unwrap_or(usize::MAX))
+        },
+        // ELF does not carry any source code language
+        // information.
+        lang: SrcLang::Unknown,
+        section: None,
+        // ELF doesn't carry source code location
+        // information.
+        code_info: None,
+        inlined: Box::new([]),
+    };
+    let shndx = symtab.section_index(idx)?;
+    Ok((resolved, shndx))
+}
+
+/// Find the symbol covering `addr`, along with the index of the
+/// section it is associated with (if any).
+fn find_sym_with_section<'mmap>(
+    symtab: &SymbolTableCache<'mmap>,
+    addr: Addr,
+    type_: SymType,
+) -> Result<Option<(ResolvedSym<'mmap>, Option<usize>)>> {
+    covering_syms(symtab, addr, type_)
+        .next()
+        .map(|idx| resolve_sym(symtab, idx))
+        .transpose()
+}
+
+/// Rank a symbol by binding, preferring global ones over weak ones
+/// over local ones.
+fn binding_rank(sym: &Elf64_Sym) -> u8 {
+    match sym.binding() {
+        STB_GLOBAL => 0,
+        STB_WEAK => 1,
+        STB_LOCAL => 2,
+        _ => 3,
+    }
+}
+
+/// Check whether `symtab` contains a symbol equivalent to `sym`, i.e.,
+/// one of the same type covering its address.
+fn contains_sym(symtab: &SymbolTableCache<'_>, sym: &Elf64_Sym) -> bool {
+    // Untyped compiler generated local labels may be present if their
+    // reporting was requested. They have no type to match against.
+    let type_ = match SymType::try_from(sym) {
+        Ok(type_) => type_,
+        Err(()) => return false,
+    };
+    let result = find_sym(symtab, sym.st_value, type_);
+    matches!(result, Ok(Some(_)))
+}
+
+/// Convert an "invalid data" error encountered while parsing the
+/// symbol table in `section` into `None`, so that other parts of the
+/// file can still be used.
+fn ignore_corrupt_symtab<T>(result: Result<T>, section: &str) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if err.kind() == ErrorKind::InvalidData => {
+            log::debug!("ignoring corrupt {section} symbol table: {err:#}");
             Ok(None)
         }
+        Err(err) => Err(err),
     }
 }
 
@@ -136,11 +339,131 @@ fn decompress_zstd(_data: &[u8]) -> Result<Vec<u8>> {
     ))
 }
 
+/// Decompress the contents of a legacy GNU style compressed section
+/// (`.zdebug_*`).
+///
+/// Such sections start with the magic `ZLIB`, followed by the size of
+/// the uncompressed data as a 64 bit big endian value, followed by the
+/// zlib compressed data.
+fn decompress_zdebug(mut data: &[u8]) -> Result<Vec<u8>> {
+    let magic = data
+        .read_array::<4>()
+        .ok_or_invalid_data(|| "failed to read .zdebug section magic")?;
+    if &magic != b"ZLIB" {
+        return Err(Error::with_invalid_data(
+            ".zdebug section does not start with expected magic",
+        ))
+    }
+    let size = data
+        .read_array::<8>()
+        .map(u64::from_be_bytes)
+        .ok_or_invalid_data(|| "failed to read .zdebug section size")?;
+
+    let decompressed = decompress_zlib(data)?;
+    if decompressed.len() as u64 != size {
+        return Err(Error::with_invalid_data(format!(
+            "decompressed .zdebug section data does not have expected length ({} vs. {size})",
+            decompressed.len()
+        )))
+    }
+    Ok(decompressed)
+}
+
 
+/// The encoding of the data structures of an ELF file.
 #[derive(Clone, Copy, Debug)]
+struct Encoding {
+    /// Whether the file is a 32 bit one, in which case data structures
+    /// are widened to their 64 bit counterpart upon reading.
+    is_32bit: bool,
+    /// Whether the file's byte order differs from that of the host, in
+    /// which case data structures are byte swapped upon reading.
+    swap: bool,
+}
+
+impl Encoding {
+    /// Determine the encoding from the given `e_ident` bytes.
+    fn from_ident(e_ident: &[u8]) -> Self {
+        let is_32bit = e_ident.get(EI_CLASS) == Some(&ELFCLASS32);
+        let is_big_endian = e_ident.get(EI_DATA) == Some(&ELFDATA2MSB);
+        Self {
+            is_32bit,
+            swap: is_big_endian != cfg!(target_endian = "big"),
+        }
+    }
+}
+
+
+/// Read a `T` from `data`, honoring the provided encoding.
+///
+/// If the encoding is 32 bit, the object is read as its 32 bit
+/// counterpart, `T32`, and widened.
+fn read_struct<'data, T, T32>(data: &mut &'data [u8], encoding: Encoding) -> Option<Cow<'data, T>>
+where
+    T: ByteSwap + Clone + Pod + for<'t32> From<&'t32 T32>,
+    T32: ByteSwap + Clone + Pod,
+{
+    let Encoding { is_32bit, swap } = encoding;
+    match (is_32bit, swap) {
+        (false, false) => data.read_pod_cow::<T>(),
+        (false, true) => {
+            let t = data.read_pod::<T>()?;
+            Some(Cow::Owned(t.swap_bytes()))
+        }
+        (true, false) => {
+            let t32 = data.read_pod::<T32>()?;
+            Some(Cow::Owned(T::from(&t32)))
+        }
+        (true, true) => {
+            let t32 = data.read_pod::<T32>()?;
+            Some(Cow::Owned(T::from(&t32.swap_bytes())))
+        }
+    }
+}
+
+/// Read `count` objects of type `T` from `data`, honoring the provided
+/// encoding.
+///
+/// If the encoding is 32 bit, the objects are read as their 32 bit
+/// counterpart, `T32`, and widened.
+fn read_struct_slice<'data, T, T32>(
+    data: &mut &'data [u8],
+    count: usize,
+    encoding: Encoding,
+) -> Option<Cow<'data, [T]>>
+where
+    T: ByteSwap + Clone + Pod + for<'t32> From<&'t32 T32>,
+    T32: ByteSwap + Clone + Pod,
+{
+    let Encoding { is_32bit, swap } = encoding;
+    match (is_32bit, swap) {
+        (false, false) => data.read_pod_slice_cow::<T>(count),
+        (false, true) => {
+            let ts = data.read_pod_slice_cow::<T>(count)?;
+            Some(Cow::Owned(ts.iter().map(T::swap_bytes).collect()))
+        }
+        (true, false) => {
+            let t32s = data.read_pod_slice_cow::<T32>(count)?;
+            Some(Cow::Owned(t32s.iter().map(T::from).collect()))
+        }
+        (true, true) => {
+            let t32s = data.read_pod_slice_cow::<T32>(count)?;
+            Some(Cow::Owned(
+                t32s.iter().map(|t32| T::from(&t32.swap_bytes())).collect(),
+            ))
+        }
+    }
+}
+
+
+#[derive(Clone, Debug)]
 struct EhdrExt<'mmap> {
     /// The ELF header.
-    ehdr: &'mmap Elf64_Ehdr,
+    ///
+    /// For 32 bit ELF files the header is widened to its 64 bit
+    /// counterpart. For files with a byte order different from the
+    /// host's, it is byte swapped.
+    ehdr: Cow<'mmap, Elf64_Ehdr>,
     /// Override of `ehdr.e_shnum`, handling of which is special-cased by
     /// the ELF standard.
     shnum: usize,
@@ -149,49 +472,283 @@ struct EhdrExt<'mmap> {
     phnum: usize,
 }
 
+impl EhdrExt<'_> {
+    /// Retrieve the encoding of the ELF file's data structures.
+    #[inline]
+    fn encoding(&self) -> Encoding {
+        Encoding::from_ident(&self.ehdr.e_ident)
+    }
+}
+
 
 #[derive(Debug)]
 struct SymbolTableCache<'mmap> {
-    /// The cached symbols (in address order).
-    syms: Box<[&'mmap Elf64_Sym]>,
+    /// The symbol table, as present in the file.
+    ///
+    /// For 32 bit ELF files all symbols are widened to their 64 bit
+    /// counterpart.
+    table: Cow<'mmap, [Elf64_Sym]>,
+    /// Indices into `table` of all supported symbols (in address
+    /// order).
+    syms: Box<[usize]>,
     /// The string table.
     strs: &'mmap [u8],
+    /// Extended section indices of symbols (indexed like `table`), as
+    /// present in a `SHT_SYMTAB_SHNDX` section, if any.
+    shndx: Option<Cow<'mmap, [Elf64_Word]>>,
+    /// The cached symbol names (indexed like `syms`).
+    names: OnceCell<Box<[&'mmap str]>>,
     /// The cached name to symbol index table (in dictionary order).
     str2sym: OnceCell<Box<[(&'mmap str, usize)]>>,
+    /// Whether compiler generated local symbols have been removed.
+    no_compiler_local: bool,
+    /// The cached indices into `table` of all labels (in address
+    /// order).
+    labels: OnceCell<Box<[usize]>>,
 }
 
 impl<'mmap> SymbolTableCache<'mmap> {
-    fn new(syms: Vec<&'mmap Elf64_Sym>, strs: &'mmap [u8]) -> Self {
+    fn new(table: Cow<'mmap, [Elf64_Sym]>, strs: &'mmap [u8]) -> Self {
+        let mut syms = table
+            .iter()
+            .enumerate()
+            // Filter out any symbols that we do not support. Compiler
+            // generated local labels (e.g., `.L*`) are kept, so that
+            // they can be reported if requested.
+            .filter(|(_idx, sym)| {
+                sym.matches(SymType::Undefined)
+                    || (is_label(strs, sym) && is_compiler_local(strs, sym))
+            })
+            .map(|(idx, _sym)| idx)
+            .collect::<Vec<_>>();
+
+        // Order symbols by address and those with equal address descending by
+        // size. Among symbols that still compare equal, prefer the ones
+        // with "stronger" binding, those with default visibility (e.g.,
+        // `memcpy` over a hidden `__GI_memcpy` alias), and those that
+        // have a name. Remaining ties are broken by name, so that the
+        // order does not depend on that of the symbol table itself.
+        let () = syms.sort_by(|idx1, idx2| {
+            let sym1 = &table[*idx1];
+            let sym2 = &table[*idx2];
+            sym1.st_value
+                .cmp(&sym2.st_value)
+                .then_with(|| sym1.st_size.cmp(&sym2.st_size).reverse())
+                .then_with(|| binding_rank(sym1).cmp(&binding_rank(sym2)))
+                .then_with(|| {
+                    (sym1.visibility() != STV_DEFAULT).cmp(&(sym2.visibility() != STV_DEFAULT))
+                })
+                .then_with(|| (sym1.st_name == 0).cmp(&(sym2.st_name == 0)))
+                .then_with(|| {
+                    symbol_name(strs, sym1)
+                        .ok()
+                        .cmp(&symbol_name(strs, sym2).ok())
+                })
+        });
+
         Self {
+            table,
             syms: syms.into_boxed_slice(),
             strs,
+            shndx: None,
+            names: OnceCell::new(),
             str2sym: OnceCell::new(),
+            no_compiler_local: false,
+            labels: OnceCell::new(),
+        }
+    }
+
+    /// Retrieve the symbol at index `idx` (in address order).
+    #[inline]
+    fn get(&self, idx: usize) -> Option<&Elf64_Sym> {
+        let idx = self.syms.get(idx)?;
+        // SANITY: All indices in `syms` are valid for `table`.
+        Some(&self.table[*idx])
+    }
+
+    /// Retrieve the index of the section that the symbol at index `idx`
+    /// (in address order) is associated with, resolving extended
+    /// section indices as necessary.
+    ///
+    /// `None` is returned for symbols referencing a reserved section
+    /// index, such as `SHN_ABS`.
+    fn section_index(&self, idx: usize) -> Result<Option<usize>> {
+        let table_idx = *self
+            .syms
+            .get(idx)
+            .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
+        self.raw_section_index(table_idx)
+    }
+
+    /// Retrieve the index of the section that the symbol at index
+    /// `table_idx` of `table` (i.e., in file order) is associated with.
+    ///
+    /// `None` is returned for symbols referencing a reserved section
+    /// index, such as `SHN_ABS`.
+    fn raw_section_index(&self, table_idx: usize) -> Result<Option<usize>> {
+        let sym = self
+            .table
+            .get(table_idx)
+            .ok_or_invalid_input(|| format!("symbol table index ({table_idx}) out of bounds"))?;
+        match sym.st_shndx {
+            SHN_XINDEX => {
+                let shndx = self
+                    .shndx
+                    .as_deref()
+                    .and_then(|shndx| shndx.get(table_idx))
+                    .ok_or_invalid_data(|| {
+                        format!(
+                            "extended section index of symbol at {:#x} not found",
+                            sym.st_value
+                        )
+                    })?;
+                Ok(Some(*shndx as usize))
+            }
+            shndx if shndx >= SHN_LORESERVE => Ok(None),
+            shndx => Ok(Some(usize::from(shndx))),
+        }
+    }
+
+    /// Remove all compiler generated local symbols (see
+    /// [`is_compiler_local`]) from the set of symbols reported.
+    fn remove_compiler_local_syms(&mut self) {
+        let syms = self
+            .syms
+            .iter()
+            .copied()
+            .filter(|idx| !is_compiler_local(self.strs, &self.table[*idx]))
+            .collect();
+        self.syms = syms;
+        self.no_compiler_local = true;
+    }
+
+    /// Retrieve the indices into `table` of all labels (see
+    /// [`is_label`]), in address order.
+    fn labels(&self) -> &[usize] {
+        self.labels.get_or_init(|| {
+            let mut labels = self
+                .table
+                .iter()
+                .enumerate()
+                .filter(|(_idx, sym)| {
+                    is_label(self.strs, sym)
+                        && !(self.no_compiler_local && is_compiler_local(self.strs, sym))
+                })
+                .map(|(idx, _sym)| idx)
+                .collect::<Vec<_>>();
+            // Break ties by name, so that the label reported does not
+            // depend on the order of the symbol table.
+            let () = labels.sort_by(|idx1, idx2| {
+                let sym1 = &self.table[*idx1];
+                let sym2 = &self.table[*idx2];
+                sym1.st_value.cmp(&sym2.st_value).then_with(|| {
+                    symbol_name(self.strs, sym1)
+                        .ok()
+                        .cmp(&symbol_name(self.strs, sym2).ok())
+                })
+            });
+            labels.into_boxed_slice()
+        })
+    }
+
+    /// Find the label closest to `addr` that starts inside the
+    /// `start..=addr` range and is not called `exclude`.
+    fn find_label(&self, start: Addr, addr: Addr, exclude: &str) -> Result<Option<&'mmap str>> {
+        let labels = self.labels();
+        let end = labels.partition_point(|idx| self.table[*idx].st_value <= addr);
+        for idx in labels[..end].iter().rev() {
+            let sym = &self.table[*idx];
+            if sym.st_value < start {
+                break
+            }
+            let name = symbol_name(self.strs, sym)?;
+            if name != exclude {
+                return Ok(Some(name))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Iterate over all symbols (in address order).
+    #[inline]
+    fn iter(&self) -> impl Iterator<Item = &Elf64_Sym> {
+        self.syms.iter().map(|idx| &self.table[*idx])
+    }
+
+    /// Retrieve the names of all symbols (in address order).
+    ///
+    /// Names are looked up in the string table once and cached
+    /// afterwards, making subsequent retrieval a constant time
+    /// operation.
+    fn ensure_names(&self) -> Result<&[&'mmap str]> {
+        let names = self
+            .names
+            .get_or_try_init(|| {
+                self.iter()
+                    .map(|sym| symbol_name(self.strs, sym))
+                    .collect::<Result<Box<[_]>>>()
+            })?
+            .deref();
+
+        Ok(names)
+    }
+
+    /// Retrieve the name of the symbol at index `idx` (in address
+    /// order).
+    ///
+    /// If the name cache has not been populated yet, the name is looked
+    /// up in the string table directly, as populating the cache may not
+    /// pay off for a single lookup.
+    fn name(&self, idx: usize) -> Result<&'mmap str> {
+        if let Some(names) = self.names.get() {
+            names
+                .get(idx)
+                .copied()
+                .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))
+        } else {
+            let sym = self
+                .get(idx)
+                .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
+            symbol_name(self.strs, sym)
         }
     }
 
+    /// Iterate over all symbols (in address order), along with their
+    /// names.
+    ///
+    /// Names are borrowed from the string table and cached, so that
+    /// repeated iteration does not have to scan the string table again.
+    fn iter_named(&self) -> Result<impl Iterator<Item = (&'mmap str, &Elf64_Sym)> + '_> {
+        let names = self.ensure_names()?;
+        Ok(names.iter().copied().zip(self.iter()))
+    }
+
+    #[cfg(test)]
+    #[inline]
+    fn len(&self) -> usize {
+        self.syms.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.syms.is_empty()
+    }
+
     fn create_str2sym<F>(&self, mut filter: F) -> Result<Vec<(&'mmap str, usize)>>
     where
         F: FnMut(&Elf64_Sym) -> bool,
     {
         let mut str2sym = self
-            .syms
-            .iter()
-            .filter(|sym| filter(sym))
+            .iter_named()?
+            // Make sure to enumerate before filtering, as indices need
+            // to be valid for `Self::get`.
             .enumerate()
-            .map(|(i, sym)| {
-                let name = self
-                    .strs
-                    .get(sym.st_name as usize..)
-                    .ok_or_invalid_input(|| "string table index out of bounds")?
-                    .read_cstr()
-                    .ok_or_invalid_input(|| "no valid string found in string table")?
-                    .to_str()
-                    .map_err(Error::with_invalid_data)
-                    .context("invalid symbol name")?;
-                Ok((name, i))
-            })
-            .collect::<Result<Vec<_>>>()?;
+            .filter(|(_i, (_name, sym))| filter(sym))
+            .map(|(i, (name, _sym))| (name, i))
+            .collect::<Vec<_>>();
 
+        // Note that the sort is stable, meaning that symbols of equal
+        // name remain ordered by address.
         let () = str2sym.sort_by_key(|&(name, _i)| name);
         Ok(str2sym)
     }
@@ -214,20 +771,40 @@ impl<'mmap> SymbolTableCache<'mmap> {
 }
 
 
+/// A symbol synthesized for a PLT stub.
+#[derive(Debug)]
+struct PltSym {
+    /// The address of the PLT entry.
+    addr: Addr,
+    /// The name of the section containing the PLT entry.
+    section: &'static str,
+    /// The size of the PLT entry.
+    size: usize,
+    /// The symbol name, in the form `<target>@plt`.
+    name: Box<str>,
+}
+
+
 struct Cache<'mmap> {
     /// A slice of the raw ELF data that we are about to parse.
     elf_data: &'mmap [u8],
     /// The cached ELF header.
     ehdr: OnceCell<EhdrExt<'mmap>>,
     /// The cached ELF section headers.
-    shdrs: OnceCell<&'mmap [Elf64_Shdr]>,
+    shdrs: OnceCell<Cow<'mmap, [Elf64_Shdr]>>,
     shstrtab: OnceCell<&'mmap [u8]>,
     /// The cached ELF program headers.
-    phdrs: OnceCell<&'mmap [Elf64_Phdr]>,
+    phdrs: OnceCell<Cow<'mmap, [Elf64_Phdr]>>,
     /// The cached symbol table.
     symtab: OnceCell<SymbolTableCache<'mmap>>,
     /// The cached dynamic symbol table.
     dynsym: OnceCell<SymbolTableCache<'mmap>>,
+    /// The cached symbols synthesized for PLT entries (in address
+    /// order).
+    plt: OnceCell<Box<[PltSym]>>,
+    /// Whether to keep compiler generated local symbols in symbol
+    /// tables.
+    compiler_local_syms: bool,
 }
 
 impl<'mmap> Cache<'mmap> {
@@ -241,24 +818,36 @@ impl<'mmap> Cache<'mmap> {
             phdrs: OnceCell::new(),
             symtab: OnceCell::new(),
             dynsym: OnceCell::new(),
+            plt: OnceCell::new(),
+            compiler_local_syms: false,
         }
     }
 
     /// Retrieve the raw section data for the ELF section at index
     /// `idx`, along with it's section header.
-    fn section_data_raw(&self, idx: usize) -> Result<(&'mmap Elf64_Shdr, &'mmap [u8])> {
+    fn section_data_raw(&self, idx: usize) -> Result<(&Elf64_Shdr, &'mmap [u8])> {
         let shdrs = self.ensure_shdrs()?;
         let shdr = shdrs
             .get(idx)
             .ok_or_invalid_input(|| format!("ELF section index ({idx}) out of bounds"))?;
 
         if shdr.sh_type != SHT_NOBITS {
-            let data = self
-                .elf_data
-                .get(shdr.sh_offset as usize..)
-                .ok_or_invalid_data(|| "failed to read section data: invalid offset")?
-                .read_slice(shdr.sh_size as usize)
-                .ok_or_invalid_data(|| "failed to read section data: invalid size")?;
+            let data = usize::try_from(shdr.sh_offset)
+                .ok()
+                .and_then(|offset| self.elf_data.get(offset..))
+                .ok_or_invalid_data(|| {
+                    format!(
+                        "failed to read section {idx} data: offset {:#x} is out of bounds",
+                        shdr.sh_offset
+                    )
+                })?
+                .read_slice(usize::try_from(shdr.sh_size).unwrap_or(usize::MAX))
+                .ok_or_invalid_data(|| {
+                    format!(
+                        "failed to read section {idx} data: size {:#x} is out of bounds",
+                        shdr.sh_size
+                    )
+                })?;
             Ok((shdr, data))
         } else {
             Ok((shdr, &[]))
@@ -277,20 +866,21 @@ impl<'mmap> Cache<'mmap> {
     /// of certain member variables to reference data from this header,
     /// which otherwise is zeroed out.
     #[inline]
-    fn read_first_shdr(&self, ehdr: &Elf64_Ehdr) -> Result<&'mmap Elf64_Shdr> {
-        let shdr = self
+    fn read_first_shdr(&self, ehdr: &Elf64_Ehdr) -> Result<Cow<'mmap, Elf64_Shdr>> {
+        let mut data = self
             .elf_data
             .get(ehdr.e_shoff as usize..)
-            .ok_or_invalid_data(|| "Elf64_Ehdr::e_shoff is invalid")?
-            .read_pod_ref::<Elf64_Shdr>()
+            .ok_or_invalid_data(|| "Elf64_Ehdr::e_shoff is invalid")?;
+        let encoding = Encoding::from_ident(&ehdr.e_ident);
+        let shdr = read_struct::<Elf64_Shdr, Elf32_Shdr>(&mut data, encoding)
             .ok_or_invalid_data(|| "failed to read Elf64_Shdr")?;
         Ok(shdr)
     }
 
     fn parse_ehdr(&self) -> Result<EhdrExt<'mmap>> {
         let mut elf_data = self.elf_data;
-        let ehdr = elf_data
-            .read_pod_ref::<Elf64_Ehdr>()
+        let encoding = Encoding::from_ident(elf_data);
+        let ehdr = read_struct::<Elf64_Ehdr, Elf32_Ehdr>(&mut elf_data, encoding)
             .ok_or_invalid_data(|| "failed to read Elf64_Ehdr")?;
         if !(ehdr.e_ident[0] == 0x7f
             && ehdr.e_ident[1] == b'E'
@@ -303,12 +893,26 @@ impl<'mmap> Cache<'mmap> {
             )))
         }
 
+        let class = ehdr.e_ident[EI_CLASS];
+        if class != ELFCLASS32 && class != ELFCLASS64 {
+            return Err(Error::with_invalid_data(format!(
+                "encountered unsupported ELF class: {class}"
+            )))
+        }
+
+        let data = ehdr.e_ident[EI_DATA];
+        if data != ELFDATA2LSB && data != ELFDATA2MSB {
+            return Err(Error::with_invalid_data(format!(
+                "encountered unsupported ELF data encoding: {data}"
+            )))
+        }
+
         // "If the number of entries in the section header table is larger than
         // or equal to SHN_LORESERVE, e_shnum holds the value zero and the real
         // number of entries in the section header table is held in the sh_size
         // member of the initial entry in section header table."
         let shnum = if ehdr.e_shnum == 0 {
-            let shdr = self.read_first_shdr(ehdr)?;
+            let shdr = self.read_first_shdr(&ehdr)?;
             usize::try_from(shdr.sh_size).ok().ok_or_invalid_data(|| {
                 format!(
                     "ELF file contains unsupported number of sections ({})",
@@ -325,7 +929,7 @@ impl<'mmap> Cache<'mmap> {
         // program header table is held in the sh_info member of the
         // initial entry in section header table."
         let phnum = if ehdr.e_phnum == PN_XNUM {
-            let shdr = self.read_first_shdr(ehdr)?;
+            let shdr = self.read_first_shdr(&ehdr)?;
             usize::try_from(shdr.sh_info).ok().ok_or_invalid_data(|| {
                 format!(
                     "ELF file contains unsupported number of program headers ({})",
@@ -344,34 +948,50 @@ impl<'mmap> Cache<'mmap> {
         self.ehdr.get_or_try_init(|| self.parse_ehdr())
     }
 
-    fn parse_shdrs(&self) -> Result<&'mmap [Elf64_Shdr]> {
+    fn parse_shdrs(&self) -> Result<Cow<'mmap, [Elf64_Shdr]>> {
         let ehdr = self.ensure_ehdr()?;
-        let shdrs = self
+        let mut data = self
             .elf_data
             .get(ehdr.ehdr.e_shoff as usize..)
-            .ok_or_invalid_data(|| "Elf64_Ehdr::e_shoff is invalid")?
-            .read_pod_slice_ref::<Elf64_Shdr>(ehdr.shnum)
-            .ok_or_invalid_data(|| "failed to read Elf64_Shdr")?;
+            .ok_or_invalid_data(|| "Elf64_Ehdr::e_shoff is invalid")?;
+        let mut shdrs =
+            read_struct_slice::<Elf64_Shdr, Elf32_Shdr>(&mut data, ehdr.shnum, ehdr.encoding())
+                .ok_or_invalid_data(|| "failed to read Elf64_Shdr")?;
+
+        // Sections of relocatable objects have not been assigned
+        // addresses yet. We lay them out at their file offsets, which
+        // allows for using file offsets as addresses and makes symbols
+        // of different sections distinguishable.
+        if ehdr.ehdr.e_type == ET_REL {
+            let () = shdrs
+                .to_mut()
+                .iter_mut()
+                .filter(|shdr| shdr.sh_flags & SHF_ALLOC != 0)
+                .for_each(|shdr| shdr.sh_addr = shdr.sh_offset);
+        }
         Ok(shdrs)
     }
 
-    fn ensure_shdrs(&self) -> Result<&'mmap [Elf64_Shdr]> {
-        self.shdrs.get_or_try_init(|| self.parse_shdrs()).copied()
+    fn ensure_shdrs(&self) -> Result<&[Elf64_Shdr]> {
+        let shdrs = self.shdrs.get_or_try_init(|| self.parse_shdrs())?;
+        Ok(shdrs.deref())
     }
 
-    fn parse_phdrs(&self) -> Result<&'mmap [Elf64_Phdr]> {
+    fn parse_phdrs(&self) -> Result<Cow<'mmap, [Elf64_Phdr]>> {
         let ehdr = self.ensure_ehdr()?;
-        let phdrs = self
+        let mut data = self
             .elf_data
             .get(ehdr.ehdr.e_phoff as usize..)
-            .ok_or_invalid_data(|| "Elf64_Ehdr::e_phoff is invalid")?
-            .read_pod_slice_ref::<Elf64_Phdr>(ehdr.phnum)
-            .ok_or_invalid_data(|| "failed to read Elf64_Phdr")?;
+            .ok_or_invalid_data(|| "Elf64_Ehdr::e_phoff is invalid")?;
+        let phdrs =
+            read_struct_slice::<Elf64_Phdr, Elf32_Phdr>(&mut data, ehdr.phnum, ehdr.encoding())
+                .ok_or_invalid_data(|| "failed to read Elf64_Phdr")?;
         Ok(phdrs)
     }
 
-    fn ensure_phdrs(&self) -> Result<&'mmap [Elf64_Phdr]> {
-        self.phdrs.get_or_try_init(|| self.parse_phdrs()).copied()
+    fn ensure_phdrs(&self) -> Result<&[Elf64_Phdr]> {
+        let phdrs = self.phdrs.get_or_try_init(|| self.parse_phdrs())?;
+        Ok(phdrs.deref())
     }
 
     fn shstrndx(&self, ehdr: &Elf64_Ehdr) -> Result<usize> {
@@ -395,7 +1015,7 @@ impl<'mmap> Cache<'mmap> {
 
     fn parse_shstrtab(&self) -> Result<&'mmap [u8]> {
         let ehdr = self.ensure_ehdr()?;
-        let shstrndx = self.shstrndx(ehdr.ehdr)?;
+        let shstrndx = self.shstrndx(&ehdr.ehdr)?;
         let shstrtab = self.section_data(shstrndx)?;
         Ok(shstrtab)
     }
@@ -406,19 +1026,31 @@ impl<'mmap> Cache<'mmap> {
             .copied()
     }
 
-    /// Get the name of the section at a given index.
-    fn section_name(&self, idx: usize) -> Result<&'mmap str> {
+    /// Retrieve the raw name of the section at index `idx`.
+    fn section_name_raw(&self, idx: usize) -> Result<&'mmap CStr> {
         let shdrs = self.ensure_shdrs()?;
         let shstrtab = self.ensure_shstrtab()?;
 
         let sect = shdrs
             .get(idx)
-            .ok_or_invalid_input(|| "ELF section index out of bounds")?;
+            .ok_or_invalid_input(|| format!("ELF section index ({idx}) out of bounds"))?;
         let name = shstrtab
             .get(sect.sh_name as usize..)
-            .ok_or_invalid_input(|| "string table index out of bounds")?
+            .ok_or_invalid_data(|| {
+                format!(
+                    "name of section {idx} at string table index {} out of bounds",
+                    sect.sh_name
+                )
+            })?
             .read_cstr()
-            .ok_or_invalid_input(|| "no valid string found in string table")?
+            .ok_or_invalid_data(|| format!("name of section {idx} is not NUL terminated"))?;
+        Ok(name)
+    }
+
+    /// Get the name of the section at a given index.
+    fn section_name(&self, idx: usize) -> Result<&'mmap str> {
+        let name = self
+            .section_name_raw(idx)?
             .to_str()
             .map_err(Error::with_invalid_data)
             .context("invalid section name")?;
@@ -426,8 +1058,8 @@ impl<'mmap> Cache<'mmap> {
     }
 
     #[cfg(test)]
-    fn symbol(&self, idx: usize) -> Result<&'mmap Elf64_Sym> {
-        let symtab = self.ensure_symtab()?;
+    fn symbol(&self, idx: usize) -> Result<&Elf64_Sym> {
+        let symtab = self.ensure_symtab_cache()?;
         let symbol = symtab
             .get(idx)
             .ok_or_invalid_input(|| format!("ELF symbol index ({idx}) out of bounds"))?;
@@ -439,130 +1071,367 @@ impl<'mmap> Cache<'mmap> {
     ///
     /// This function return the index of the section if found.
     fn find_section(&self, name: &str) -> Result<Option<usize>> {
+        self.find_section_matching(|section| section == name)
+    }
+
+    /// Find the first section whose name satisfies the provided
+    /// predicate.
+    ///
+    /// This function return the index of the section if found.
+    fn find_section_matching(&self, mut pred: impl FnMut(&str) -> bool) -> Result<Option<usize>> {
         let ehdr = self.ensure_ehdr()?;
         for i in 1..ehdr.shnum {
-            if self.section_name(i)? == name {
-                return Ok(Some(i))
+            // Sections whose names are not valid UTF-8 can't match
+            // what we are looking for, so just skip them.
+            if let Ok(name) = self.section_name_raw(i)?.to_str() {
+                if pred(name) {
+                    return Ok(Some(i))
+                }
             }
         }
         Ok(None)
     }
 
-    fn parse_syms(&self, section: &str) -> Result<Vec<&'mmap Elf64_Sym>> {
+    /// Find all sections of the given type (e.g., `SHT_NOTE`).
+    ///
+    /// This function returns an iterator over the indices of matching
+    /// sections, in the order in which they appear in the file.
+    fn find_sections_by_type(
+        &self,
+        sh_type: Elf64_Word,
+    ) -> Result<impl Iterator<Item = usize> + '_> {
+        let shdrs = self.ensure_shdrs()?;
+        let iter = shdrs
+            .iter()
+            .enumerate()
+            // The first section header is reserved and never refers to
+            // an actual section.
+            .skip(1)
+            .filter(move |(_idx, shdr)| shdr.sh_type == sh_type)
+            .map(|(idx, _shdr)| idx);
+        Ok(iter)
+    }
+
+    /// Parse the symbol table contained in the section with the given
+    /// name, along with the string table it references.
+    fn parse_symtab(&self, section: &str) -> Result<SymbolTableCache<'mmap>> {
         let idx = if let Some(idx) = self.find_section(section)? {
             idx
         } else {
             // The symbol table does not exists. Fake an empty one.
-            return Ok(Vec::new())
+            return Ok(SymbolTableCache::new(Cow::Borrowed(&[]), &[]))
+        };
+        let (shdr, mut syms) = self.section_data_raw(idx)?;
+        // The symbol table's `sh_link` member references the section
+        // holding the associated string table.
+        let strs = self.section_data(shdr.sh_link as usize)?;
+
+        let ehdr = self.ensure_ehdr()?;
+        let encoding = ehdr.encoding();
+        let sym_size = if encoding.is_32bit {
+            mem::size_of::<Elf32_Sym>()
+        } else {
+            mem::size_of::<Elf64_Sym>()
         };
-        let mut syms = self.section_data(idx)?;
 
-        if syms.len() % mem::size_of::<Elf64_Sym>() != 0 {
+        if syms.len() % sym_size != 0 {
             return Err(Error::with_invalid_data(
                 "size of symbol table section is invalid",
             ))
         }
 
-        let count = syms.len() / mem::size_of::<Elf64_Sym>();
+        let count = syms.len() / sym_size;
         // Short-circuit if there are no symbols. The data may not actually be
         // properly aligned in this case either, so don't attempt to even read.
         if count == 0 {
-            return Ok(Vec::new())
+            return Ok(SymbolTableCache::new(Cow::Borrowed(&[]), strs))
+        }
+        let mut syms = read_struct_slice::<Elf64_Sym, Elf32_Sym>(&mut syms, count, encoding)
+            .ok_or_invalid_data(|| format!("failed to read {section} symbol table contents"))?;
+        let shndx = self.parse_symtab_shndx(idx)?;
+
+        // Symbol values of relocatable objects are relative to their
+        // section. Make them relative to the section's (synthetic)
+        // address, in line with the section layout we use.
+        if ehdr.ehdr.e_type == ET_REL {
+            let shdrs = self.ensure_shdrs()?;
+            for (i, sym) in syms.to_mut().iter_mut().enumerate() {
+                let sect_idx = match sym.st_shndx {
+                    SHN_UNDEF => continue,
+                    SHN_XINDEX => match shndx.as_deref().and_then(|shndx| shndx.get(i)) {
+                        Some(idx) => *idx as usize,
+                        None => continue,
+                    },
+                    shndx if shndx >= SHN_LORESERVE => continue,
+                    shndx => usize::from(shndx),
+                };
+                if let Some(shdr) = shdrs.get(sect_idx) {
+                    sym.st_value = sym.st_value.wrapping_add(shdr.sh_addr);
+                }
+            }
         }
-        let mut syms = syms
-            .read_pod_slice_ref::<Elf64_Sym>(count)
-            .ok_or_invalid_data(|| format!("failed to read {section} symbol table contents"))?
-            .iter()
-            // Filter out any symbols that we do not support.
-            .filter(|sym| sym.matches(SymType::Undefined))
-            .collect::<Vec<&Elf64_Sym>>();
-        // Order symbols by address and those with equal address descending by
-        // size.
-        let () = syms.sort_by(|sym1, sym2| {
-            sym1.st_value
-                .cmp(&sym2.st_value)
-                .then_with(|| sym1.st_size.cmp(&sym2.st_size).reverse())
-        });
 
-        Ok(syms)
+        let mut cache = SymbolTableCache::new(syms, strs);
+        cache.shndx = shndx;
+        if !self.compiler_local_syms {
+            let () = cache.remove_compiler_local_syms();
+        }
+        Ok(cache)
+    }
+
+    /// Parse the extended section indices associated with the symbol
+    /// table in section `symtab_idx`, if any.
+    ///
+    /// Objects with more than `SHN_LORESERVE` sections store the section
+    /// indices of affected symbols in a `SHT_SYMTAB_SHNDX` section,
+    /// marking the symbols themselves with `SHN_XINDEX`.
+    fn parse_symtab_shndx(&self, symtab_idx: usize) -> Result<Option<Cow<'mmap, [Elf64_Word]>>> {
+        let shdrs = self.ensure_shdrs()?;
+        let idx = self
+            .find_sections_by_type(SHT_SYMTAB_SHNDX)?
+            .find(|idx| shdrs[*idx].sh_link as usize == symtab_idx);
+        let idx = if let Some(idx) = idx {
+            idx
+        } else {
+            return Ok(None)
+        };
+
+        let (_shdr, mut data) = self.section_data_raw(idx)?;
+        let count = data.len() / mem::size_of::<Elf64_Word>();
+        let shndx = data
+            .read_pod_slice_cow::<Elf64_Word>(count)
+            .ok_or_invalid_data(|| "failed to read extended section index table")?;
+        let shndx = if self.ensure_ehdr()?.encoding().swap {
+            Cow::Owned(shndx.iter().map(|idx| idx.swap_bytes()).collect())
+        } else {
+            shndx
+        };
+        Ok(Some(shndx))
     }
 
     fn ensure_symtab_cache(&self) -> Result<&SymbolTableCache<'mmap>> {
-        self.symtab.get_or_try_init(|| {
-            let syms = self.parse_syms(".symtab")?;
-            let strtab = self.parse_strs(".strtab")?;
-            let cache = SymbolTableCache::new(syms, strtab);
-            Ok(cache)
-        })
+        self.symtab.get_or_try_init(|| self.parse_symtab(".symtab"))
     }
 
     fn ensure_dynsym_cache(&self) -> Result<&SymbolTableCache<'mmap>> {
-        self.dynsym.get_or_try_init(|| {
-            // TODO: We really should check the `.dynamic` section for
-            //       information on what symbol and string tables to
-            //       use instead of hard coding names here.
-            let syms = self.parse_syms(".dynsym")?;
-            let dynstr = self.parse_strs(".dynstr")?;
-            let cache = SymbolTableCache::new(syms, dynstr);
-            Ok(cache)
-        })
+        // Stripped objects frequently lack `.symtab`, but they still
+        // carry `.dynsym` for the dynamic linker's benefit, which we use
+        // as a fallback.
+        // TODO: We really should check the `.dynamic` section for
+        //       information on what symbol table to use instead of hard
+        //       coding the name here.
+        self.dynsym.get_or_try_init(|| self.parse_symtab(".dynsym"))
     }
 
-    fn ensure_symtab(&self) -> Result<&[&'mmap Elf64_Sym]> {
-        let symtab = self.ensure_symtab_cache()?;
-        Ok(&symtab.syms)
-    }
+    /// Parse the relocations contained in the `SHT_REL` or `SHT_RELA`
+    /// section at index `idx`.
+    ///
+    /// Relocations without an explicit addend are reported with an
+    /// addend of zero.
+    fn parse_relocations(&self, idx: usize) -> Result<Cow<'mmap, [Elf64_Rela]>> {
+        let encoding = self.ensure_ehdr()?.encoding();
+        let (shdr, mut data) = self.section_data_raw(idx)?;
+        let size = match (shdr.sh_type, encoding.is_32bit) {
+            (SHT_RELA, false) => mem::size_of::<Elf64_Rela>(),
+            (SHT_RELA, true) => mem::size_of::<Elf32_Rela>(),
+            (SHT_REL, false) => mem::size_of::<Elf64_Rel>(),
+            (SHT_REL, true) => mem::size_of::<Elf32_Rel>(),
+            (type_, _) => {
+                return Err(Error::with_invalid_input(format!(
+                    "section {idx} is not a relocation section (type {type_})"
+                )))
+            }
+        };
 
-    fn ensure_dynsym(&self) -> Result<&[&'mmap Elf64_Sym]> {
-        let dynsym = self.ensure_dynsym_cache()?;
-        Ok(&dynsym.syms)
-    }
+        if data.len() % size != 0 {
+            return Err(Error::with_invalid_data(format!(
+                "size of relocation section {idx} is invalid"
+            )))
+        }
+        let count = data.len() / size;
+        // Short-circuit if there are no relocations, as the data may
+        // not be properly aligned.
+        if count == 0 {
+            return Ok(Cow::Borrowed(&[]))
+        }
 
-    fn parse_strs(&self, section: &str) -> Result<&'mmap [u8]> {
-        let strs = if let Some(idx) = self.find_section(section)? {
-            self.section_data(idx)?
+        let relas = if shdr.sh_type == SHT_RELA {
+            read_struct_slice::<Elf64_Rela, Elf32_Rela>(&mut data, count, encoding)
+                .ok_or_invalid_data(|| format!("failed to read relocation section {idx}"))?
         } else {
-            &[]
+            let rels = read_struct_slice::<Elf64_Rel, Elf32_Rel>(&mut data, count, encoding)
+                .ok_or_invalid_data(|| format!("failed to read relocation section {idx}"))?;
+            Cow::Owned(rels.iter().map(Elf64_Rela::from).collect())
         };
-        Ok(strs)
-    }
-
-    fn ensure_str2symtab(&self) -> Result<&[(&'mmap str, usize)]> {
-        let symtab = self.ensure_symtab_cache()?;
-        let str2sym = symtab.ensure_str2sym(|_sym| true)?;
-        Ok(str2sym)
+        Ok(relas)
     }
 
-    fn ensure_str2dynsym(&self) -> Result<&[(&'mmap str, usize)]> {
-        let symtab = self.ensure_symtab_cache()?;
-        let dynsym = self.ensure_dynsym_cache()?;
-        let str2sym = dynsym.ensure_str2sym(|sym| {
-            // We filter out all the symbols that already exist in symtab,
-            // to prevent any duplicates from showing up.
-            let result = find_sym(
-                &symtab.syms,
-                symtab.strs,
-                sym.st_value,
-                // SANITY: We filter out all unsupported symbol types,
-                //         so this conversion should always succeed.
-                SymType::try_from(sym).unwrap(),
-            );
-            !matches!(result, Ok(Some(_)))
-        })?;
-        Ok(str2sym)
+    /// Retrieve all relocations applying to the section at index
+    /// `idx`.
+    fn relocations(&self, idx: usize) -> Result<Vec<Elf64_Rela>> {
+        let shdrs = self.ensure_shdrs()?;
+        let mut relas = Vec::new();
+        for (rel_idx, shdr) in shdrs.iter().enumerate() {
+            if matches!(shdr.sh_type, SHT_REL | SHT_RELA) && shdr.sh_info as usize == idx {
+                let () = relas.extend_from_slice(&self.parse_relocations(rel_idx)?);
+            }
+        }
+        Ok(relas)
     }
-}
 
-impl Debug for Cache<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "Cache")
-    }
-}
+    /// Synthesize symbols for PLT entries, by correlating them with the
+    /// relocations in `.rela.plt`.
+    fn parse_plt(&self) -> Result<Vec<PltSym>> {
+        let ehdr = self.ensure_ehdr()?;
+        // We only support PLT layouts of 64 bit architectures, with
+        // native byte order.
+        let encoding = ehdr.encoding();
+        if encoding.is_32bit || encoding.swap {
+            return Ok(Vec::new())
+        }
 
+        // The size of the header entry at the start of `.plt`, which
+        // does not correspond to any relocation.
+        let plt_hdr_size = match ehdr.ehdr.e_machine {
+            EM_X86_64 => 16,
+            EM_AARCH64 => 32,
+            // We don't know the PLT layout of other architectures.
+            _ => return Ok(Vec::new()),
+        };
 
-/// A parser for ELF64 files.
-#[derive(Debug)]
-pub(crate) struct ElfParser {
+        let rela_idx = if let Some(idx) = self.find_section(".rela.plt")? {
+            idx
+        } else {
+            return Ok(Vec::new())
+        };
+
+        // If present, `.plt.sec` contains the actual stubs being called
+        // and it has no header entry. `.plt` then only contains the
+        // stubs used for lazy binding, but they still map one-to-one
+        // to relocations and are worth reporting.
+        let mut plt_sects = Vec::with_capacity(2);
+        if let Some(idx) = self.find_section(".plt.sec")? {
+            let () = plt_sects.push((idx, 0, ".plt.sec"));
+        }
+        if let Some(idx) = self.find_section(".plt")? {
+            let () = plt_sects.push((idx, plt_hdr_size, ".plt"));
+        }
+        if plt_sects.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let relas = self.parse_relocations(rela_idx)?;
+        if relas.is_empty() {
+            return Ok(Vec::new())
+        }
+        let (rela_shdr, _data) = self.section_data_raw(rela_idx)?;
+
+        // The relocations reference symbols by their index in the
+        // (unsorted) symbol table linked to by the section.
+        let (syms_shdr, mut syms) = self.section_data_raw(rela_shdr.sh_link as usize)?;
+        let count = syms.len() / mem::size_of::<Elf64_Sym>();
+        let syms = syms
+            .read_pod_slice_cow::<Elf64_Sym>(count)
+            .ok_or_invalid_data(|| "failed to read .rela.plt symbol table contents")?;
+        let strs = self.section_data(syms_shdr.sh_link as usize)?;
+
+        let shdrs = self.ensure_shdrs()?;
+        let mut plt = Vec::with_capacity(relas.len() * plt_sects.len());
+
+        for (plt_idx, plt_hdr_size, plt_name) in plt_sects {
+            // SANITY: `find_section` only reports valid indices.
+            let plt_shdr = &shdrs[plt_idx];
+            let entsize = if plt_shdr.sh_entsize != 0 {
+                plt_shdr.sh_entsize
+            } else {
+                16
+            };
+            let plt_end = plt_shdr.sh_addr.saturating_add(plt_shdr.sh_size);
+
+            for (i, rela) in relas.iter().enumerate() {
+                let addr = (i as u64)
+                    .checked_mul(entsize)
+                    .and_then(|offset| offset.checked_add(plt_hdr_size))
+                    .and_then(|offset| offset.checked_add(plt_shdr.sh_addr));
+                let addr = match addr {
+                    Some(addr) if addr.saturating_add(entsize) <= plt_end => addr,
+                    _ => break,
+                };
+
+                // Relocations without a symbol (e.g., for IFUNCs) do not
+                // provide us with a name to use.
+                let sym_idx = rela.sym_idx() as usize;
+                if sym_idx == 0 {
+                    continue
+                }
+
+                let sym = syms.get(sym_idx).ok_or_invalid_data(|| {
+                    format!(".rela.plt symbol index ({sym_idx}) out of bounds")
+                })?;
+                let name = symbol_name(strs, sym)?;
+                let sym = PltSym {
+                    addr,
+                    section: plt_name,
+                    size: entsize as usize,
+                    name: format!("{name}@plt").into_boxed_str(),
+                };
+                let () = plt.push(sym);
+            }
+        }
+
+        // Lookup relies on entries being sorted by address.
+        let () = plt.sort_by_key(|sym| sym.addr);
+        Ok(plt)
+    }
+
+    /// Retrieve the synthesized PLT symbols.
+    ///
+    /// PLT symbols are a nicety. A failure to parse them is logged and
+    /// treated as if no PLT entries existed, so that lookups of regular
+    /// symbols are unaffected.
+    fn ensure_plt(&self) -> &[PltSym] {
+        self.plt
+            .get_or_init(|| match self.parse_plt() {
+                Ok(plt) => plt.into_boxed_slice(),
+                Err(err) => {
+                    log::debug!("ignoring corrupt PLT: {err:#}");
+                    Box::default()
+                }
+            })
+            .deref()
+    }
+
+    fn ensure_str2symtab(&self) -> Result<&[(&'mmap str, usize)]> {
+        let symtab = self.ensure_symtab_cache()?;
+        let str2sym = symtab.ensure_str2sym(|_sym| true)?;
+        Ok(str2sym)
+    }
+
+    fn ensure_str2dynsym(&self) -> Result<&[(&'mmap str, usize)]> {
+        let symtab = self.ensure_symtab_cache()?;
+        let dynsym = self.ensure_dynsym_cache()?;
+        // We filter out all the symbols that already exist in symtab,
+        // to prevent any duplicates from showing up.
+        let str2sym = dynsym.ensure_str2sym(|sym| !contains_sym(symtab, sym))?;
+        Ok(str2sym)
+    }
+}
+
+impl Debug for Cache<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Cache")
+    }
+}
+
+
+/// A parser for ELF files.
+///
+/// Data are parsed lazily and cached on first use, relying on single
+/// threaded interior mutability and reference counting. As such, the
+/// parser is neither `Send` nor `Sync`; parallel workloads should use a
+/// separate instance per thread.
+#[derive(Debug)]
+pub(crate) struct ElfParser {
     /// A cache for relevant parts of the ELF file.
     // SAFETY: We must not hand out references with a 'static lifetime to
     //         this member. Rather, they should never outlive `self`.
@@ -579,6 +1448,8 @@ pub(crate) struct ElfParser {
     _mmap: Mmap,
     /// The path to the ELF file being worked on, if available.
     path: Option<PathBuf>,
+    /// The symbol table(s) to consult for symbolization.
+    sym_source: SymSource,
 }
 
 impl ElfParser {
@@ -604,10 +1475,50 @@ impl ElfParser {
             decompressed: InsertMap::new(),
             cache: Cache::new(elf_data),
             path,
+            sym_source: SymSource::default(),
         };
         parser
     }
 
+    /// Set the symbol table(s) to consult for symbolization.
+    pub(crate) fn set_sym_source(&mut self, sym_source: SymSource) {
+        self.sym_source = sym_source;
+    }
+
+    /// Set whether to keep compiler generated local symbols (unnamed
+    /// ones and assembler local labels) in symbol tables.
+    ///
+    /// This setting only has an effect if symbol tables have not been
+    /// parsed yet.
+    pub(crate) fn set_compiler_local_syms(&mut self, enable: bool) {
+        self.cache.compiler_local_syms = enable;
+    }
+
+    /// Create an `ElfParser` operating on a copy of the provided
+    /// in-memory ELF data.
+    pub(crate) fn from_bytes(data: &[u8], path: Option<PathBuf>) -> Result<Self> {
+        let mmap = Mmap::from_bytes(data).context("failed to create memory mapping")?;
+        Ok(Self::from_mmap(mmap, path))
+    }
+
+    /// Create an `ElfParser` from ELF data provided by a reader.
+    ///
+    /// The reader is rewound and its entire contents are copied into
+    /// memory.
+    pub(crate) fn from_reader<R>(mut reader: R, path: Option<PathBuf>) -> Result<Self>
+    where
+        R: Read + Seek,
+    {
+        let _pos = reader
+            .seek(SeekFrom::Start(0))
+            .context("failed to seek to start of ELF data")?;
+        let mut data = Vec::new();
+        let _cnt = reader
+            .read_to_end(&mut data)
+            .context("failed to read ELF data")?;
+        Self::from_bytes(&data, path)
+    }
+
     /// Create an `ElfParser` for a path.
     pub(crate) fn open(path: &Path) -> Result<ElfParser> {
         let file =
@@ -627,8 +1538,8 @@ impl ElfParser {
             let data = self.decompressed.get_or_try_insert(idx, || {
                 // Compression header is contained in the actual section
                 // data.
-                let chdr = data
-                    .read_pod::<Elf64_Chdr>()
+                let encoding = self.cache.ensure_ehdr()?.encoding();
+                let chdr = read_struct::<Elf64_Chdr, Elf32_Chdr>(&mut data, encoding)
                     .ok_or_invalid_data(|| "failed to read Elf64_Chdr")?;
 
                 let decompressed = match chdr.ch_type {
@@ -647,6 +1558,12 @@ impl ElfParser {
                 Ok(decompressed)
             })?;
             Ok(data.as_slice())
+        } else if data.starts_with(b"ZLIB") && self.cache.section_name(idx)?.starts_with(".zdebug")
+        {
+            let data = self
+                .decompressed
+                .get_or_try_insert(idx, || decompress_zdebug(data))?;
+            Ok(data.as_slice())
         } else {
             Ok(data)
         }
@@ -660,6 +1577,72 @@ impl ElfParser {
         Ok(index)
     }
 
+    /// Find the first section whose name satisfies the provided
+    /// predicate.
+    ///
+    /// This function return the index of the section if found. It can
+    /// be used to look up any of a set of related sections (e.g.,
+    /// `.debug_info` and `.zdebug_info`) in a single pass, while
+    /// leaving the matching policy to the caller.
+    pub(crate) fn find_section_matching(
+        &self,
+        pred: impl FnMut(&str) -> bool,
+    ) -> Result<Option<usize>> {
+        let index = self.cache.find_section_matching(pred)?;
+        Ok(index)
+    }
+
+    /// Retrieve all relocations applying to the section at index `idx`.
+    ///
+    /// Relocations of `SHT_REL` sections are reported with an addend of
+    /// zero.
+    pub(crate) fn relocations(&self, idx: usize) -> Result<Vec<Elf64_Rela>> {
+        self.cache.relocations(idx)
+    }
+
+    /// Check whether the ELF file is a relocatable object (`ET_REL`),
+    /// such as a `.o` file or a kernel module.
+    ///
+    /// The sections of relocatable objects are laid out at their file
+    /// offsets, meaning that file offsets double as addresses.
+    pub(crate) fn is_relocatable(&self) -> Result<bool> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_type == ET_REL)
+    }
+
+    /// Retrieve the name of the allocated section containing the
+    /// virtual address `addr`, if any.
+    pub(crate) fn find_section_name_by_addr(&self, addr: Addr) -> Result<Option<&str>> {
+        let shdrs = self.cache.ensure_shdrs()?;
+        let idx = shdrs.iter().enumerate().skip(1).find_map(|(idx, shdr)| {
+            (shdr.sh_flags & SHF_ALLOC != 0
+                && addr >= shdr.sh_addr
+                && addr - shdr.sh_addr < shdr.sh_size)
+                .then_some(idx)
+        });
+        let name = idx.map(|idx| self.cache.section_name(idx)).transpose()?;
+        Ok(name)
+    }
+
+    /// Find the first section of the given type (e.g., `SHT_NOTE`).
+    ///
+    /// This function return the index of the section if found.
+    pub(crate) fn find_section_by_type(&self, sh_type: Elf64_Word) -> Result<Option<usize>> {
+        let index = self.cache.find_sections_by_type(sh_type)?.next();
+        Ok(index)
+    }
+
+    /// Find all sections of the given type (e.g., `SHT_NOTE`).
+    ///
+    /// This function returns an iterator over the indices of matching
+    /// sections, in the order in which they appear in the file.
+    pub(crate) fn find_sections_by_type(
+        &self,
+        sh_type: Elf64_Word,
+    ) -> Result<impl Iterator<Item = usize> + '_> {
+        self.cache.find_sections_by_type(sh_type)
+    }
+
     pub(crate) fn find_sym(
         &self,
         addr: Addr,
@@ -668,59 +1651,302 @@ impl ElfParser {
         // ELF doesn't carry any source code or inlining information.
         let _opts = opts;
 
-        let symtab_cache = self.cache.ensure_symtab_cache()?;
-        if let Some(sym) = find_sym(
-            &symtab_cache.syms,
-            symtab_cache.strs,
-            addr,
-            SymType::Undefined,
-        )? {
-            return Ok(Ok(sym))
+        // PLT entries generally don't have symbols associated with
+        // them, meaning that a lookup of an address inside a PLT would
+        // report whatever symbol precedes it. Hence, check for a PLT
+        // match first.
+        let plt = self.cache.ensure_plt();
+        let idx = plt.partition_point(|sym| sym.addr <= addr);
+        if let Some(sym) = idx.checked_sub(1).and_then(|idx| plt.get(idx)) {
+            if addr - sym.addr < sym.size as Addr {
+                let sym = ResolvedSym {
+                    name: &sym.name,
+                    addr: sym.addr,
+                    size: Some(sym.size),
+                    lang: SrcLang::Unknown,
+                    section: Some(sym.section),
+                    code_info: None,
+                    inlined: Box::new([]),
+                };
+                return Ok(Ok(sym))
+            }
         }
 
-        let dynsym_cache = self.cache.ensure_dynsym_cache()?;
-        if let Some(sym) = find_sym(
-            &dynsym_cache.syms,
-            dynsym_cache.strs,
-            addr,
-            SymType::Undefined,
-        )? {
-            return Ok(Ok(sym))
+        let (symtab_cache, dynsym_cache) = self.sym_tables()?;
+        for cache in [symtab_cache, dynsym_cache].into_iter().flatten().flatten() {
+            if let Some((mut sym, shndx)) = find_sym_with_section(cache, addr, SymType::Undefined)?
+            {
+                sym.section = shndx.map(|idx| self.cache.section_name(idx)).transpose()?;
+                return Ok(Ok(sym))
+            }
         }
 
         // At this point we haven't found a symbol for the given
         // address. The emptiness of `dynsym` has no bearing on the
         // reason we report -- for all intents and purposes it is either
         // required or not at all necessary.
-        let reason = if symtab_cache.syms.is_empty() {
-            Reason::MissingSyms
-        } else {
-            Reason::UnknownAddr
+        let reason = match (symtab_cache, dynsym_cache) {
+            // Only a single symbol table was consulted.
+            (Some(cache), None) | (None, Some(cache)) => match cache {
+                None => Reason::CorruptSymtab,
+                Some(cache) if cache.is_empty() => Reason::MissingSyms,
+                Some(_) => Reason::UnknownAddr,
+            },
+            (Some(None), _) => Reason::CorruptSymtab,
+            (Some(Some(symtab_cache)), Some(None)) if symtab_cache.is_empty() => {
+                Reason::CorruptSymtab
+            }
+            (Some(Some(symtab_cache)), _) if symtab_cache.is_empty() => Reason::MissingSyms,
+            (Some(Some(_)), _) | (None, None) => Reason::UnknownAddr,
         };
         Ok(Err(reason))
     }
 
-    /// Calculate the file offset of the given symbol.
+    /// Eagerly parse all data required for symbol lookups, i.e., the
+    /// PLT and the symbol tables in use.
+    pub(crate) fn warmup(&self) -> Result<()> {
+        let _plt = self.cache.ensure_plt();
+        let _tables = self.sym_tables()?;
+        Ok(())
+    }
+
+    /// Retrieve the symbol at index `idx` of the provided symbol table,
+    /// as referenced by relocations, for example.
+    pub(crate) fn sym_by_index(&self, table: SymTable, idx: usize) -> Result<ElfSym<'_>> {
+        let cache = match table {
+            SymTable::SymTab => self.cache.ensure_symtab_cache()?,
+            SymTable::DynSym => self.cache.ensure_dynsym_cache()?,
+        };
+        let sym = cache
+            .table
+            .get(idx)
+            .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
+        let sym = ElfSym {
+            name: symbol_name(cache.strs, sym)?,
+            addr: sym.st_value,
+            size: sym.st_size,
+            type_: sym.type_(),
+            binding: sym.binding(),
+            section: cache.raw_section_index(idx)?,
+            _non_exhaustive: (),
+        };
+        Ok(sym)
+    }
+
+    /// Find the label (a named `STT_NOTYPE` symbol) closest to `addr`
+    /// inside the symbol `sym` that was previously reported for it.
+    ///
+    /// Labels at the very address of the symbol that carry its name are
+    /// not considered, as they are no more specific than the symbol
+    /// itself.
+    pub(crate) fn find_label(&self, addr: Addr, sym: &ResolvedSym<'_>) -> Result<Option<&str>> {
+        let (symtab_cache, dynsym_cache) = self.sym_tables()?;
+        for cache in [symtab_cache, dynsym_cache].into_iter().flatten().flatten() {
+            if let Some(label) = cache.find_label(sym.addr, addr, sym.name)? {
+                return Ok(Some(label))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Retrieve the `.symtab` and `.dynsym` symbol tables to consult for
+    /// symbolization, as per the configured [`SymSource`].
+    ///
+    /// The outer `Option` is `None` if the respective table is not to
+    /// be consulted. The inner one is `None` if the table is corrupt.
+    /// A corrupt symbol table should not prevent us from consulting the
+    /// other one, so callers should only report it if they come up
+    /// empty.
+    #[allow(clippy::type_complexity)]
+    fn sym_tables(
+        &self,
+    ) -> Result<(
+        Option<Option<&SymbolTableCache<'_>>>,
+        Option<Option<&SymbolTableCache<'_>>>,
+    )> {
+        let symtab = if self.sym_source != SymSource::DynSymOnly {
+            Some(ignore_corrupt_symtab(
+                self.cache.ensure_symtab_cache(),
+                ".symtab",
+            )?)
+        } else {
+            None
+        };
+        let dynsym = if self.sym_source != SymSource::SymTab {
+            Some(ignore_corrupt_symtab(
+                self.cache.ensure_dynsym_cache(),
+                ".dynsym",
+            )?)
+        } else {
+            None
+        };
+        Ok((symtab, dynsym))
+    }
+
+    /// Gather statistics about the symbol table used for
+    /// symbolization.
+    pub(crate) fn sym_stats(&self) -> Result<SymStats> {
+        let (symtab_cache, dynsym_cache) = self.sym_tables()?;
+        let used = [
+            (symtab_cache, SymTable::SymTab),
+            (dynsym_cache, SymTable::DynSym),
+        ]
+        .into_iter()
+        .find_map(|(cache, table)| match cache {
+            Some(Some(cache)) if !cache.is_empty() => Some((table, cache)),
+            _ => None,
+        });
+
+        let (table, syms) = match used {
+            // The first entry of a symbol table is reserved and does
+            // not represent an actual symbol.
+            Some((table, cache)) => (Some(table), cache.table.get(1..).unwrap_or_default()),
+            None => (None, [].as_slice()),
+        };
+
+        let stats = SymStats {
+            table,
+            total: syms.len(),
+            funcs: syms
+                .iter()
+                .filter(|sym| sym.matches(SymType::Function))
+                .count(),
+            undefined: syms.iter().filter(|sym| sym.st_shndx == SHN_UNDEF).count(),
+            stripped: self.find_section(".symtab")?.is_none(),
+            _non_exhaustive: (),
+        };
+        Ok(stats)
+    }
+
+    /// Retrieve all symbols starting inside of `range`, in address
+    /// order.
+    ///
+    /// If `spanning` is `true`, the symbol covering `range.start` is
+    /// reported first, even if it starts before the range. Only the
+    /// first non-empty symbol table used for symbolization is
+    /// consulted.
+    pub(crate) fn syms_in_range(
+        &self,
+        range: Range<Addr>,
+        spanning: bool,
+    ) -> Result<Vec<ElfSym<'_>>> {
+        let (symtab_cache, dynsym_cache) = self.sym_tables()?;
+        let cache = [symtab_cache, dynsym_cache]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .find(|cache| !cache.is_empty());
+
+        let cache = match cache {
+            Some(cache) => cache,
+            None => return Ok(Vec::new()),
+        };
+
+        let names = cache.ensure_names()?;
+        syms_in_range(cache, range, spanning)
+            .map(|idx| {
+                // SANITY: `syms_in_range` only reports valid indices.
+                let sym = cache.get(idx).unwrap();
+                let sym = ElfSym {
+                    name: names[idx],
+                    addr: sym.st_value,
+                    size: sym.st_size,
+                    type_: sym.type_(),
+                    binding: sym.binding(),
+                    section: cache.raw_section_index(cache.syms[idx])?,
+                    _non_exhaustive: (),
+                };
+                Ok(sym)
+            })
+            .collect()
+    }
+
+    /// Find all symbols covering `addr` that start at the same address
+    /// as the one [`find_sym`][Self::find_sym] reports.
+    ///
+    /// The first symbol reported is the one that
+    /// [`find_sym`][Self::find_sym] reports. Remaining symbols are
+    /// ordered by binding strength and then by name.
+    pub(crate) fn find_syms(
+        &self,
+        addr: Addr,
+        opts: &FindSymOpts,
+    ) -> Result<Result<Vec<ResolvedSym<'_>>, Reason>> {
+        let sym = match self.find_sym(addr, opts)? {
+            Ok(sym) => sym,
+            Err(reason) => return Ok(Err(reason)),
+        };
+
+        let (symtab, dynsym) = self.sym_tables()?;
+
+        let mut others = Vec::<(u8, ResolvedSym<'_>)>::new();
+        for symtab in [symtab, dynsym].into_iter().flatten().flatten() {
+            for idx in covering_syms(symtab, addr, SymType::Undefined) {
+                let (mut other, shndx) = resolve_sym(symtab, idx)?;
+                // Skip the symbol we already found as well as symbols
+                // present in both `.symtab` and `.dynsym`.
+                let is_dup =
+                    |sym: &ResolvedSym<'_>| sym.name == other.name && sym.addr == other.addr;
+                if is_dup(&sym) || others.iter().any(|(_rank, sym)| is_dup(sym)) {
+                    continue
+                }
+
+                other.section = shndx.map(|idx| self.cache.section_name(idx)).transpose()?;
+                // SANITY: `covering_syms` only reports valid indices.
+                let rank = binding_rank(symtab.get(idx).unwrap());
+                let () = others.push((rank, other));
+            }
+        }
+
+        let () = others.sort_by(|(rank1, sym1), (rank2, sym2)| {
+            rank1.cmp(rank2).then_with(|| sym1.name.cmp(sym2.name))
+        });
+        let syms = iter::once(sym)
+            .chain(others.into_iter().map(|(_rank, sym)| sym))
+            .collect();
+        Ok(Ok(syms))
+    }
+
+    /// Calculate the file offset of the symbol at index `idx` (in
+    /// address order) of the provided symbol table.
     ///
     /// # Notes
     /// It is the caller's responsibility to ensure that the symbol's section
     /// index is not `SHN_UNDEF`.
-    fn file_offset(&self, shdrs: &[Elf64_Shdr], sym: &Elf64_Sym) -> Result<Option<u64>> {
+    fn file_offset(
+        &self,
+        shdrs: &[Elf64_Shdr],
+        symtab: &SymbolTableCache<'_>,
+        idx: usize,
+    ) -> Result<Option<u64>> {
+        let sym = symtab
+            .get(idx)
+            .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
         debug_assert_ne!(sym.st_shndx, SHN_UNDEF);
 
-        if sym.st_shndx >= SHN_LORESERVE {
+        let shndx = if let Some(shndx) = symtab.section_index(idx)? {
+            shndx
+        } else {
             return Ok(None)
-        }
+        };
 
-        let section = shdrs
-            .get(usize::from(sym.st_shndx))
-            .ok_or_invalid_input(|| {
+        let section = shdrs.get(shndx).ok_or_invalid_data(|| {
+            format!(
+                "ELF section index ({shndx}) of symbol at {:#x} out of bounds",
+                sym.st_value
+            )
+        })?;
+        let offset = sym
+            .st_value
+            .checked_sub(section.sh_addr)
+            .and_then(|offset| offset.checked_add(section.sh_offset))
+            .ok_or_invalid_data(|| {
                 format!(
-                    "ELF section index ({}) of symbol at {:#x} out of bounds",
-                    sym.st_shndx, sym.st_value
+                    "symbol at {:#x} lies outside of its section ({shndx})",
+                    sym.st_value
                 )
             })?;
-        Ok(Some(sym.st_value - section.sh_addr + section.sh_offset))
+        Ok(Some(offset))
     }
 
     fn find_addr_impl<'slf>(
@@ -728,7 +1954,7 @@ impl ElfParser {
         name: &str,
         opts: &FindAddrOpts,
         shdrs: &'slf [Elf64_Shdr],
-        syms: &[&'slf Elf64_Sym],
+        symtab: &SymbolTableCache<'_>,
         str2sym: &'slf [(&'slf str, usize)],
     ) -> Result<Vec<SymInfo<'slf>>> {
         let r = find_match_or_lower_bound_by_key(str2sym, name, |&(name, _i)| name);
@@ -739,27 +1965,33 @@ impl ElfParser {
                     if *name_visit != name {
                         break
                     }
-                    let sym_ref = &syms.get(*sym_i).ok_or_invalid_input(|| {
+                    let sym_ref = symtab.get(*sym_i).ok_or_invalid_input(|| {
                         format!("symbol table index ({sym_i}) out of bounds")
                     })?;
+                    // Compiler generated local labels are untyped and
+                    // never reported here.
+                    let sym_type = match SymType::try_from(sym_ref) {
+                        Ok(sym_type) => sym_type,
+                        Err(()) => continue,
+                    };
                     if sym_ref.st_shndx != SHN_UNDEF {
                         found.push(SymInfo {
                             name: Cow::Borrowed(name_visit),
                             addr: sym_ref.st_value as Addr,
                             size: sym_ref.st_size as usize,
-                            // SANITY: We filter out all unsupported symbol
-                            //         types, so this conversion should always
-                            //         succeed.
-                            sym_type: SymType::try_from(**sym_ref).unwrap(),
+                            sym_type,
                             file_offset: opts
                                 .offset_in_file
-                                .then(|| self.file_offset(shdrs, sym_ref))
+                                .then(|| self.file_offset(shdrs, symtab, *sym_i))
                                 .transpose()?
                                 .flatten(),
                             obj_file_name: self.path().map(Cow::Borrowed),
                         });
                     }
                 }
+                // If there are multiple matches, report functions
+                // before other symbol types.
+                let () = found.sort_by_key(|sym| sym.sym_type != SymType::Function);
                 Ok(found)
             }
             None => Ok(vec![]),
@@ -772,36 +2004,34 @@ impl ElfParser {
         opts: &FindAddrOpts,
     ) -> Result<Vec<SymInfo<'slf>>> {
         let shdrs = self.cache.ensure_shdrs()?;
-        let symtab = self.cache.ensure_symtab()?;
+        let symtab = self.cache.ensure_symtab_cache()?;
         let str2symtab = self.cache.ensure_str2symtab()?;
         let syms = self.find_addr_impl(name, opts, shdrs, symtab, str2symtab)?;
         if !syms.is_empty() {
             return Ok(syms)
         }
 
-        let dynsym = self.cache.ensure_dynsym()?;
+        let dynsym = self.cache.ensure_dynsym_cache()?;
         let str2dynsym = self.cache.ensure_str2dynsym()?;
         let syms = self.find_addr_impl(name, opts, shdrs, dynsym, str2dynsym)?;
         Ok(syms)
     }
 
-    fn for_each_sym_impl<F>(
+    fn for_each_sym_impl<P, F>(
         &self,
         opts: &FindAddrOpts,
-        syms: &[&Elf64_Sym],
-        str2sym: &[(&str, usize)],
+        symtab: &SymbolTableCache<'_>,
+        mut filter: P,
         mut f: F,
     ) -> Result<()>
     where
+        P: FnMut(&Elf64_Sym) -> bool,
         F: FnMut(&SymInfo<'_>),
     {
         let shdrs = self.cache.ensure_shdrs()?;
 
-        for (name, idx) in str2sym {
-            let sym = &syms
-                .get(*idx)
-                .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
-            if sym.matches(opts.sym_type) && sym.st_shndx != SHN_UNDEF {
+        for (idx, (name, sym)) in symtab.iter_named()?.enumerate() {
+            if sym.matches(opts.sym_type) && sym.st_shndx != SHN_UNDEF && filter(sym) {
                 let sym_info = SymInfo {
                     name: Cow::Borrowed(name),
                     addr: sym.st_value as Addr,
@@ -809,10 +2039,10 @@ impl ElfParser {
                     // SANITY: We filter out all unsupported symbol
                     //         types, so this conversion should always
                     //         succeed.
-                    sym_type: SymType::try_from(**sym).unwrap(),
+                    sym_type: SymType::try_from(sym).unwrap(),
                     file_offset: opts
                         .offset_in_file
-                        .then(|| self.file_offset(shdrs, sym))
+                        .then(|| self.file_offset(shdrs, symtab, idx))
                         .transpose()?
                         .flatten(),
                     obj_file_name: None,
@@ -831,13 +2061,13 @@ impl ElfParser {
         opts: &FindAddrOpts,
         mut f: &mut dyn FnMut(&SymInfo<'_>),
     ) -> Result<()> {
-        let symtab = self.cache.ensure_symtab()?;
-        let str2symtab = self.cache.ensure_str2symtab()?;
-        let () = self.for_each_sym_impl(opts, symtab, str2symtab, &mut f)?;
+        let symtab = self.cache.ensure_symtab_cache()?;
+        let () = self.for_each_sym_impl(opts, symtab, |_sym| true, &mut f)?;
 
-        let dynsym = self.cache.ensure_dynsym()?;
-        let str2dynsym = self.cache.ensure_str2dynsym()?;
-        let () = self.for_each_sym_impl(opts, dynsym, str2dynsym, &mut f)?;
+        let dynsym = self.cache.ensure_dynsym_cache()?;
+        // Symbols already reported as part of `.symtab` are skipped, to
+        // prevent any duplicates from showing up.
+        let () = self.for_each_sym_impl(opts, dynsym, |sym| !contains_sym(symtab, sym), &mut f)?;
 
         Ok(())
     }
@@ -849,8 +2079,8 @@ impl ElfParser {
         let phdrs = self.program_headers()?;
         let offset = phdrs.iter().find_map(|phdr| {
             if phdr.p_type == PT_LOAD {
-                if (phdr.p_vaddr..phdr.p_vaddr + phdr.p_memsz).contains(&addr) {
-                    return Some(addr - phdr.p_vaddr + phdr.p_offset)
+                if (phdr.p_vaddr..phdr.p_vaddr.saturating_add(phdr.p_memsz)).contains(&addr) {
+                    return Some((addr - phdr.p_vaddr).wrapping_add(phdr.p_offset))
                 }
             }
             None
@@ -866,6 +2096,51 @@ impl ElfParser {
         Ok(name)
     }
 
+    /// Retrieve the file data covered by the segment described by
+    /// `phdr`.
+    pub(crate) fn segment_data(&self, phdr: &Elf64_Phdr) -> Result<&[u8]> {
+        let data = usize::try_from(phdr.p_offset)
+            .ok()
+            .and_then(|offset| self.cache.elf_data.get(offset..))
+            .ok_or_invalid_data(|| {
+                format!(
+                    "failed to read segment data: offset {:#x} is out of bounds",
+                    phdr.p_offset
+                )
+            })?
+            .read_slice(usize::try_from(phdr.p_filesz).unwrap_or(usize::MAX))
+            .ok_or_invalid_data(|| {
+                format!(
+                    "failed to read segment data: size {:#x} is out of bounds",
+                    phdr.p_filesz
+                )
+            })?;
+        Ok(data)
+    }
+
+    /// Check whether the ELF file's byte order differs from that of the
+    /// host.
+    pub(crate) fn is_byte_swapped(&self) -> Result<bool> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.encoding().swap)
+    }
+
+    /// Read an ELF note header from `data`, honoring the file's byte
+    /// order.
+    pub(crate) fn read_nhdr(&self, data: &mut &[u8]) -> Result<Option<Elf64_Nhdr>> {
+        let swap = self.is_byte_swapped()?;
+        let nhdr = data
+            .read_pod::<Elf64_Nhdr>()
+            .map(|nhdr| if swap { nhdr.swap_bytes() } else { nhdr });
+        Ok(nhdr)
+    }
+
+    /// Retrieve the ELF file's machine type (`e_machine`).
+    pub(crate) fn machine(&self) -> Result<u16> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_machine)
+    }
+
     pub(crate) fn section_headers(&self) -> Result<&[Elf64_Shdr]> {
         let phdrs = self.cache.ensure_shdrs()?;
         Ok(phdrs)
@@ -877,12 +2152,33 @@ impl ElfParser {
     }
 
     /// Translate a file offset into a virtual offset.
+    ///
+    /// Only data actually backed by the file are considered. That is,
+    /// offsets that would map into the zero-initialized tail of a
+    /// segment (as is commonly used for `.bss`) are not translated.
     pub(crate) fn file_offset_to_virt_offset(&self, offset: u64) -> Result<Option<Addr>> {
+        // Relocatable objects don't have program headers. Map the
+        // offset via the section that contains it instead.
+        if self.is_relocatable()? {
+            let shdrs = self.section_headers()?;
+            let addr = shdrs.iter().find_map(|shdr| {
+                if shdr.sh_flags & SHF_ALLOC != 0 && shdr.sh_type != SHT_NOBITS {
+                    if (shdr.sh_offset..shdr.sh_offset.saturating_add(shdr.sh_size))
+                        .contains(&offset)
+                    {
+                        return Some((offset - shdr.sh_offset).wrapping_add(shdr.sh_addr) as Addr)
+                    }
+                }
+                None
+            });
+            return Ok(addr)
+        }
+
         let phdrs = self.program_headers()?;
         let addr = phdrs.iter().find_map(|phdr| {
             if phdr.p_type == PT_LOAD {
-                if (phdr.p_offset..phdr.p_offset + phdr.p_memsz).contains(&offset) {
-                    return Some((offset - phdr.p_offset + phdr.p_vaddr) as Addr)
+                if (phdr.p_offset..phdr.p_offset.saturating_add(phdr.p_filesz)).contains(&offset) {
+                    return Some((offset - phdr.p_offset).wrapping_add(phdr.p_vaddr) as Addr)
                 }
             }
             None
@@ -893,13 +2189,15 @@ impl ElfParser {
 
     #[cfg(test)]
     fn pick_symtab_addr(&self) -> (&str, Addr, usize) {
-        let symtab = self.cache.ensure_symtab().unwrap();
+        let symtab = self.cache.ensure_symtab_cache().unwrap();
 
         let mut idx = symtab.len() / 2;
-        while !symtab[idx].matches(SymType::Function) || symtab[idx].st_shndx == SHN_UNDEF {
+        while !symtab.get(idx).unwrap().matches(SymType::Function)
+            || symtab.get(idx).unwrap().st_shndx == SHN_UNDEF
+        {
             idx += 1;
         }
-        let sym = &symtab[idx];
+        let sym = symtab.get(idx).unwrap();
         let addr = sym.st_value;
         let size = sym.st_size;
 
@@ -924,9 +2222,13 @@ mod tests {
     use super::*;
 
     use super::super::types::SHN_LORESERVE;
+    use super::super::types::SHT_NOTE;
+    use super::super::types::SHT_PROGBITS;
+    use super::super::types::STT_FUNC;
 
     use std::env;
     use std::env::current_exe;
+    use std::fs::read as read_file;
     use std::io::Seek as _;
     use std::io::Write as _;
     use std::mem::size_of;
@@ -936,6 +2238,9 @@ mod tests {
 
     use test_log::test;
 
+    use crate::elf::ElfBuilder;
+    use crate::normalize::buildid::read_build_id;
+
 
     /// Exercise the `Debug` representation of various types.
     #[test]
@@ -957,7 +2262,7 @@ mod tests {
             e_shstrndx: 29,
         };
         let ehdr = EhdrExt {
-            ehdr: &ehdr,
+            ehdr: Cow::Borrowed(&ehdr),
             shnum: 42,
             phnum: 0,
         };
@@ -1096,7 +2401,7 @@ mod tests {
 
         let parser = ElfParser::open_file(file.as_file(), file.path()).unwrap();
         let ehdr = parser.cache.ensure_ehdr().unwrap();
-        let shstrndx = parser.cache.shstrndx(ehdr.ehdr).unwrap();
+        let shstrndx = parser.cache.shstrndx(&ehdr.ehdr).unwrap();
         assert_eq!(shstrndx, SHSTRNDX.into());
     }
 
@@ -1128,6 +2433,60 @@ mod tests {
         assert_eq!(sym.size, Some(size));
     }
 
+    /// Check that we can look up sections using a custom predicate.
+    #[test]
+    fn section_lookup_by_predicate() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-no-debug.bin");
+
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let symtab = parser.find_section(".symtab").unwrap();
+        assert!(symtab.is_some());
+        assert_eq!(
+            parser
+                .find_section_matching(|name| name.eq_ignore_ascii_case(".SYMTAB"))
+                .unwrap(),
+            symtab
+        );
+
+        let idx = parser.find_section_matching(|_name| false).unwrap();
+        assert_eq!(idx, None);
+    }
+
+    /// Check that we can look up sections by type.
+    #[test]
+    fn section_lookup_by_type() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-no-debug.bin");
+
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let shdrs = parser.section_headers().unwrap();
+        let notes = parser
+            .find_sections_by_type(SHT_NOTE)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert!(!notes.is_empty());
+        assert!(notes.iter().all(|idx| shdrs[*idx].sh_type == SHT_NOTE));
+        assert_eq!(
+            parser.find_section_by_type(SHT_NOTE).unwrap(),
+            notes.first().copied()
+        );
+
+        let symtab = parser.find_section(".symtab").unwrap();
+        assert_eq!(
+            parser
+                .find_section_by_type(shdrs[symtab.unwrap()].sh_type)
+                .unwrap(),
+            symtab
+        );
+
+        // `SHT_NULL` is only used by the reserved first section header,
+        // which should never be reported.
+        assert_eq!(parser.find_section_by_type(0).unwrap(), None);
+    }
+
     #[test]
     fn elf64_lookup_symbol_random() {
         let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
@@ -1195,13 +2554,34 @@ mod tests {
         assert_ne!(syms[0].addr, syms[1].addr);
     }
 
+    /// Check that we report the section that a symbol resides in.
+    #[test]
+    fn symbol_section() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs-no-dwarf.bin");
+
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let sym = parser
+            .find_sym(0x2000100, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert_eq!(sym.section, Some(".text"));
+
+        let name = parser.find_section_name_by_addr(0x4001100).unwrap();
+        assert_eq!(name, Some(".data"));
+        let name = parser.find_section_name_by_addr(0x1).unwrap();
+        assert_eq!(name, None);
+    }
+
     /// Make sure that we do not report a symbol if there is no conceivable
     /// match.
     #[test]
     fn lookup_symbol_without_match() {
         let strtab = b"\x00_glapi_tls_Context\x00_glapi_get_dispatch_table_size\x00";
         let symtab = [
-            &Elf64_Sym {
+            Elf64_Sym {
                 st_name: 0,
                 st_info: 0,
                 st_other: 0,
@@ -1209,7 +2589,7 @@ mod tests {
                 st_value: 0,
                 st_size: 0,
             },
-            &Elf64_Sym {
+            Elf64_Sym {
                 st_name: 0x1,
                 // Note: the type is *not* `STT_FUNC`.
                 st_info: 0x16,
@@ -1218,7 +2598,7 @@ mod tests {
                 st_value: 0x8,
                 st_size: 0x8,
             },
-            &Elf64_Sym {
+            Elf64_Sym {
                 st_name: 0x21,
                 st_info: 0x12,
                 st_other: 0x0,
@@ -1228,7 +2608,8 @@ mod tests {
             },
         ];
 
-        let result = find_sym(&symtab, strtab, 0x10d20, SymType::Function).unwrap();
+        let symtab = SymbolTableCache::new(Cow::Borrowed(&symtab), strtab);
+        let result = find_sym(&symtab, 0x10d20, SymType::Function).unwrap();
         assert_eq!(result, None);
     }
 
@@ -1236,9 +2617,10 @@ mod tests {
     /// reported, if it is the only conceivable match.
     #[test]
     fn lookup_symbol_with_unknown_size() {
-        fn test(symtab: &[&Elf64_Sym]) {
+        fn test(symtab: &[Elf64_Sym]) {
             let strtab = b"\x00__libc_init_first\x00versionsort64\x00";
-            let sym = find_sym(symtab, strtab, 0x29d00, SymType::Function)
+            let symtab = SymbolTableCache::new(Cow::Borrowed(symtab), strtab);
+            let sym = find_sym(&symtab, 0x29d00, SymType::Function)
                 .unwrap()
                 .unwrap();
             assert_eq!(sym.name, "__libc_init_first");
@@ -1248,7 +2630,7 @@ mod tests {
             // Because the symbol has a size of 0 and is the only conceivable
             // match, we report it on the basis that ELF reserves these for "no
             // size or an unknown size" cases.
-            let sym = find_sym(symtab, strtab, 0x29d90, SymType::Function)
+            let sym = find_sym(&symtab, 0x29d90, SymType::Function)
                 .unwrap()
                 .unwrap();
             assert_eq!(sym.name, "__libc_init_first");
@@ -1258,12 +2640,12 @@ mod tests {
             // Note that despite of the first symbol (the invalid one; present
             // by default and reserved by ELF), is not being reported here
             // because it has an `st_shndx` value of `SHN_UNDEF`.
-            let result = find_sym(symtab, strtab, 0x1, SymType::Function).unwrap();
+            let result = find_sym(&symtab, 0x1, SymType::Function).unwrap();
             assert_eq!(result, None);
         }
 
         let symtab = [
-            &Elf64_Sym {
+            Elf64_Sym {
                 st_name: 0,
                 st_info: 0,
                 st_other: 0,
@@ -1271,7 +2653,7 @@ mod tests {
                 st_value: 0,
                 st_size: 0,
             },
-            &Elf64_Sym {
+            Elf64_Sym {
                 st_name: 0x1,
                 st_info: 0x12,
                 st_other: 0x0,
@@ -1279,7 +2661,7 @@ mod tests {
                 st_value: 0x29d00,
                 st_size: 0x0,
             },
-            &Elf64_Sym {
+            Elf64_Sym {
                 st_name: 0xdeadbeef,
                 st_info: 0x12,
                 st_other: 0x0,
@@ -1314,7 +2696,7 @@ mod tests {
             e_shstrndx: 1,
         };
         let ehdr = EhdrExt {
-            ehdr: &ehdr,
+            ehdr: Cow::Borrowed(&ehdr),
             shnum: 3,
             phnum: 0,
         };
@@ -1367,16 +2749,1060 @@ mod tests {
         let cache = Cache {
             elf_data: aligned_data,
             ehdr: OnceCell::from(ehdr),
-            shdrs: OnceCell::from(shdrs.as_slice()),
+            shdrs: OnceCell::from(Cow::Borrowed(shdrs.as_slice())),
             shstrtab: OnceCell::from(b".shstrtab\x00.symtab\x00".as_slice()),
             phdrs: OnceCell::new(),
             symtab: OnceCell::new(),
             dynsym: OnceCell::new(),
+            plt: OnceCell::new(),
+            compiler_local_syms: false,
         };
 
         assert_eq!(cache.find_section(".symtab").unwrap(), Some(2));
 
-        let symtab = cache.ensure_symtab().unwrap();
+        let symtab = cache.ensure_symtab_cache().unwrap();
         assert!(symtab.is_empty());
     }
+
+    /// Create a minimal 32 bit ELF file containing a single function
+    /// symbol and check that we can parse it, optionally with all data
+    /// stored in the non-native byte order.
+    fn test_elf32_parsing(swap: bool) {
+        #[repr(C)]
+        struct Elf {
+            ehdr: Elf32_Ehdr,
+            shdrs: [Elf32_Shdr; 4],
+            syms: [Elf32_Sym; 2],
+            strs: [u8; 8],
+            shstrs: [u8; 28],
+        }
+
+        let shdrs_off = size_of::<Elf32_Ehdr>();
+        let syms_off = shdrs_off + size_of::<[Elf32_Shdr; 4]>();
+        let strs_off = syms_off + size_of::<[Elf32_Sym; 2]>();
+        let shstrs_off = strs_off + 8;
+
+        let elf = Elf {
+            ehdr: Elf32_Ehdr {
+                e_ident: [127, 69, 76, 70, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                e_type: 3,
+                e_machine: 3,
+                e_version: 1,
+                e_entry: 0,
+                e_phoff: 0,
+                e_shoff: shdrs_off as _,
+                e_flags: 0,
+                e_ehsize: size_of::<Elf32_Ehdr>() as _,
+                e_phentsize: size_of::<Elf32_Phdr>() as _,
+                e_phnum: 0,
+                e_shentsize: size_of::<Elf32_Shdr>() as _,
+                e_shnum: 4,
+                e_shstrndx: 1,
+            },
+            shdrs: [
+                Elf32_Shdr {
+                    sh_name: 0,
+                    sh_type: 0,
+                    sh_flags: 0,
+                    sh_addr: 0,
+                    sh_offset: 0,
+                    sh_size: 0,
+                    sh_link: 0,
+                    sh_info: 0,
+                    sh_addralign: 0,
+                    sh_entsize: 0,
+                },
+                Elf32_Shdr {
+                    sh_name: 1,
+                    sh_type: 3,
+                    sh_flags: 0,
+                    sh_addr: 0,
+                    sh_offset: shstrs_off as _,
+                    sh_size: 28,
+                    sh_link: 0,
+                    sh_info: 0,
+                    sh_addralign: 1,
+                    sh_entsize: 0,
+                },
+                Elf32_Shdr {
+                    sh_name: 11,
+                    sh_type: 2,
+                    sh_flags: 0,
+                    sh_addr: 0,
+                    sh_offset: syms_off as _,
+                    sh_size: size_of::<[Elf32_Sym; 2]>() as _,
+                    sh_link: 3,
+                    sh_info: 1,
+                    sh_addralign: 4,
+                    sh_entsize: size_of::<Elf32_Sym>() as _,
+                },
+                Elf32_Shdr {
+                    sh_name: 19,
+                    sh_type: 3,
+                    sh_flags: 0,
+                    sh_addr: 0,
+                    sh_offset: strs_off as _,
+                    sh_size: 8,
+                    sh_link: 0,
+                    sh_info: 0,
+                    sh_addralign: 1,
+                    sh_entsize: 0,
+                },
+            ],
+            syms: [
+                Elf32_Sym {
+                    st_name: 0,
+                    st_value: 0,
+                    st_size: 0,
+                    st_info: 0,
+                    st_other: 0,
+                    st_shndx: 0,
+                },
+                Elf32_Sym {
+                    st_name: 1,
+                    st_value: 0x1000,
+                    st_size: 0x10,
+                    // STB_GLOBAL + STT_FUNC
+                    st_info: 0x12,
+                    st_other: 0,
+                    st_shndx: 2,
+                },
+            ],
+            strs: *b"\x00foo\x00\x00\x00\x00",
+            shstrs: *b"\x00.shstrtab\x00.symtab\x00.strtab\x00\x00",
+        };
+
+        let elf = if swap {
+            let mut ehdr = elf.ehdr.swap_bytes();
+            ehdr.e_ident[EI_DATA] = if cfg!(target_endian = "little") {
+                ELFDATA2MSB
+            } else {
+                ELFDATA2LSB
+            };
+
+            Elf {
+                ehdr,
+                shdrs: elf.shdrs.map(|shdr| shdr.swap_bytes()),
+                syms: elf.syms.map(|sym| sym.swap_bytes()),
+                ..elf
+            }
+        } else {
+            elf
+        };
+
+        let dump =
+            unsafe { slice::from_raw_parts((&elf as *const Elf).cast::<u8>(), size_of::<Elf>()) };
+        let parser = ElfParser::from_bytes(dump, None).unwrap();
+        assert_eq!(parser.find_section(".symtab").unwrap(), Some(2));
+
+        let sym = parser
+            .find_sym(0x1008, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "foo");
+        assert_eq!(sym.addr, 0x1000);
+        assert_eq!(sym.size, Some(0x10));
+
+        // Addresses past the end of the symbol should not be attributed
+        // to it.
+        let result = parser.find_sym(0x1010, &FindSymOpts::Basic).unwrap();
+        assert_eq!(result, Err(Reason::UnknownAddr));
+
+        let syms = parser.find_addr("foo", &FindAddrOpts::default()).unwrap();
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].addr, 0x1000);
+    }
+
+    /// Check that we can parse 32 bit ELF files.
+    #[test]
+    fn elf32_parsing() {
+        test_elf32_parsing(false)
+    }
+
+    /// Check that we can parse ELF files using the non-native byte
+    /// order.
+    #[test]
+    fn elf_byte_swapped_parsing() {
+        test_elf32_parsing(true)
+    }
+
+    /// Check that we report errors instead of panicking when working
+    /// with truncated ELF files.
+    #[test]
+    fn truncated_file_handling() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let data = read_file(&path).unwrap();
+
+        for len in [1, 32, 64, data.len() / 2, data.len() - 1] {
+            let parser = ElfParser::from_bytes(&data[..len], None).unwrap();
+            let result = parser.find_sym(0x2000100, &FindSymOpts::Basic);
+            assert!(result.is_err(), "{len}: {result:?}");
+            let result = parser.find_addr("factorial", &FindAddrOpts::default());
+            assert!(result.is_err(), "{len}: {result:?}");
+            let result = parser.find_section(".text");
+            assert!(result.is_err(), "{len}: {result:?}");
+        }
+    }
+
+    /// Check that we report errors instead of panicking when working
+    /// with deliberately corrupted ELF files.
+    #[test]
+    fn corrupt_file_handling() {
+        fn exercise(data: &[u8]) {
+            let parser = ElfParser::from_bytes(data, None).unwrap();
+            for addr in [0, 0x2000100, 0x2000110, Addr::MAX] {
+                let _result = parser.find_sym(addr, &FindSymOpts::Basic);
+                let _result = parser.find_section_name_by_addr(addr);
+                let _result = parser.find_file_offset(addr);
+            }
+
+            let opts = FindAddrOpts {
+                offset_in_file: true,
+                sym_type: SymType::Undefined,
+            };
+            let _result = parser.find_addr("factorial", &opts);
+            let _result = parser.for_each(&opts, &mut |_sym| ());
+            let _result = parser.file_offset_to_virt_offset(0x100);
+            let _result = read_build_id(&parser);
+
+            if let Ok(shdrs) = parser.section_headers() {
+                for idx in 0..shdrs.len() {
+                    let _result = parser.section_data(idx);
+                    let _result = parser.cache.section_name(idx);
+                }
+            }
+        }
+
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs-no-dwarf.bin");
+        let data = read_file(&path).unwrap();
+        let () = exercise(&data);
+
+        let parser = ElfParser::from_bytes(&data, None).unwrap();
+        let shoff = parser.cache.ensure_ehdr().unwrap().ehdr.e_shoff as usize;
+        let shnum = parser.section_headers().unwrap().len();
+
+        // A corpus of corruptions, each in the form of an offset into
+        // the file along with the bytes to write there.
+        let mut corpus = vec![
+            // Invalid magic.
+            (0, vec![0]),
+            // Invalid `EI_CLASS`.
+            (4, vec![0x7]),
+            // Invalid `EI_DATA`.
+            (5, vec![0x7]),
+            // Out-of-bounds `e_phoff`.
+            (32, u64::MAX.to_ne_bytes().to_vec()),
+            // Out-of-bounds `e_shoff`.
+            (40, u64::MAX.to_ne_bytes().to_vec()),
+            // `e_phnum` of `PN_XNUM`.
+            (56, u16::MAX.to_ne_bytes().to_vec()),
+            // Excessive `e_shnum`.
+            (60, u16::MAX.to_ne_bytes().to_vec()),
+            // `e_shnum` of zero.
+            (60, 0u16.to_ne_bytes().to_vec()),
+            // Out-of-bounds `e_shstrndx`.
+            (62, 0xfffeu16.to_ne_bytes().to_vec()),
+            // `e_shstrndx` of `SHN_XINDEX`.
+            (62, u16::MAX.to_ne_bytes().to_vec()),
+        ];
+
+        for idx in 0..shnum {
+            let shdr = shoff + idx * size_of::<Elf64_Shdr>();
+            // `sh_name`, `sh_type`, `sh_addr`, `sh_offset`, `sh_size`,
+            // `sh_link`, and `sh_entsize`, respectively.
+            let () = corpus.extend([
+                (shdr, u32::MAX.to_ne_bytes().to_vec()),
+                (shdr + 4, u32::MAX.to_ne_bytes().to_vec()),
+                (shdr + 16, u64::MAX.to_ne_bytes().to_vec()),
+                (shdr + 24, u64::MAX.to_ne_bytes().to_vec()),
+                (shdr + 32, u64::MAX.to_ne_bytes().to_vec()),
+                (shdr + 40, u32::MAX.to_ne_bytes().to_vec()),
+                (shdr + 56, u64::MAX.to_ne_bytes().to_vec()),
+            ]);
+        }
+
+        for (offset, bytes) in corpus {
+            let mut data = data.clone();
+            let () = data[offset..offset + bytes.len()].copy_from_slice(&bytes);
+            let () = exercise(&data);
+        }
+    }
+
+    /// Check that a corrupt symbol table is reported as such instead of
+    /// failing symbolization altogether.
+    #[test]
+    fn corrupt_symtab_handling() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs-no-dwarf.bin");
+        let mut data = read_file(&path).unwrap();
+
+        let parser = ElfParser::from_bytes(&data, None).unwrap();
+        let idx = parser.find_section(".symtab").unwrap().unwrap();
+        let shoff = parser.cache.ensure_ehdr().unwrap().ehdr.e_shoff as usize;
+        // Make the size of the symbol table no longer be a multiple of
+        // the symbol size. `sh_size` is located at offset 32 of
+        // `Elf64_Shdr`.
+        let size_off = shoff + idx * size_of::<Elf64_Shdr>() + 32;
+        let () = data[size_off] ^= 1;
+
+        let parser = ElfParser::from_bytes(&data, None).unwrap();
+        let result = parser
+            .find_sym(0x2000100, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(result, Reason::CorruptSymtab);
+    }
+
+    /// Check that name based lookups of a filtered symbol table map
+    /// to the correct symbols and that we can iterate over all named
+    /// symbols.
+    #[test]
+    fn filtered_str2sym_lookup() {
+        let syms = vec![
+            Elf64_Sym {
+                st_name: 1,
+                // STB_GLOBAL + STT_FUNC
+                st_info: 0x12,
+                st_other: 0,
+                st_shndx: 1,
+                st_value: 0x10,
+                st_size: 0x10,
+            },
+            Elf64_Sym {
+                st_name: 3,
+                st_info: 0x12,
+                st_other: 0,
+                st_shndx: 1,
+                st_value: 0x20,
+                st_size: 0x10,
+            },
+        ];
+        let cache = SymbolTableCache::new(Cow::Owned(syms), b"\x00a\x00b\x00");
+        let names = cache
+            .iter_named()
+            .unwrap()
+            .map(|(name, _sym)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["a", "b"]);
+        // Name lookups should be served from the now populated cache.
+        assert!(cache.names.get().is_some());
+        assert_eq!(cache.name(1).unwrap(), "b");
+        assert!(cache.name(2).is_err());
+
+        let str2sym = cache.ensure_str2sym(|sym| sym.st_value != 0x10).unwrap();
+        assert_eq!(str2sym.len(), 1);
+
+        let (name, idx) = str2sym[0];
+        assert_eq!(name, "b");
+        assert_eq!(cache.get(idx).unwrap().st_value, 0x20);
+    }
+
+    /// Check that we prefer global symbols over weak and local ones
+    /// when multiple symbols share an address.
+    #[test]
+    fn symbol_binding_preference() {
+        let sym = |st_name, binding: u8| Elf64_Sym {
+            st_name,
+            // STT_FUNC
+            st_info: (binding << 4) | 2,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: 0x1000,
+            st_size: 0x10,
+        };
+
+        let strs = b"\x00local\x00weak\x00global\x00";
+        let permutations = [
+            [sym(1, STB_LOCAL), sym(7, STB_WEAK), sym(12, STB_GLOBAL)],
+            [sym(12, STB_GLOBAL), sym(7, STB_WEAK), sym(1, STB_LOCAL)],
+            [sym(7, STB_WEAK), sym(1, STB_LOCAL), sym(12, STB_GLOBAL)],
+        ];
+
+        for syms in permutations {
+            let cache = SymbolTableCache::new(Cow::Owned(syms.to_vec()), strs);
+            let sym = find_sym(&cache, 0x1008, SymType::Function)
+                .unwrap()
+                .unwrap();
+            assert_eq!(sym.name, "global");
+        }
+
+        let syms = vec![sym(1, STB_LOCAL), sym(7, STB_WEAK)];
+        let cache = SymbolTableCache::new(Cow::Owned(syms), strs);
+        let weak = find_sym(&cache, 0x1000, SymType::Function)
+            .unwrap()
+            .unwrap();
+        assert_eq!(weak.name, "weak");
+
+        // Symbols with default visibility should be preferred over
+        // hidden ones.
+        let hidden = Elf64_Sym {
+            // STV_HIDDEN
+            st_other: 0x2,
+            ..sym(12, STB_GLOBAL)
+        };
+        let syms = vec![hidden, sym(1, STB_GLOBAL)];
+        let cache = SymbolTableCache::new(Cow::Owned(syms), strs);
+        let sym = find_sym(&cache, 0x1000, SymType::Function)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "local");
+    }
+
+    /// Check that the symbol reported for an address with multiple
+    /// aliases does not depend on the symbol table order.
+    #[test]
+    fn alias_lookup_order_independence() {
+        let sym = |st_name| Elf64_Sym {
+            st_name,
+            // STB_GLOBAL + STT_FUNC
+            st_info: (STB_GLOBAL << 4) | 2,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: 0x1000,
+            st_size: 0x10,
+        };
+
+        let strs = b"\x00beta\x00alpha\x00";
+        for syms in [vec![sym(1), sym(6)], vec![sym(6), sym(1)]] {
+            let cache = SymbolTableCache::new(Cow::Owned(syms), strs);
+            let sym = find_sym(&cache, 0x1004, SymType::Function)
+                .unwrap()
+                .unwrap();
+            assert_eq!(sym.name, "alpha");
+        }
+    }
+
+    /// Check that we can find all symbols covering an address.
+    #[test]
+    fn covering_sym_lookup() {
+        let sym = |st_name, binding: u8, st_value, st_size| Elf64_Sym {
+            st_name,
+            // STT_FUNC
+            st_info: (binding << 4) | 2,
+            st_other: 0,
+            st_shndx: 1,
+            st_value,
+            st_size,
+        };
+
+        let strs = b"\x00local\x00weak\x00global\x00";
+        let syms = vec![
+            sym(1, STB_LOCAL, 0x1000, 0x10),
+            sym(7, STB_WEAK, 0x1000, 0x20),
+            sym(12, STB_GLOBAL, 0x1000, 0x8),
+        ];
+        let cache = SymbolTableCache::new(Cow::Owned(syms), strs);
+
+        let names = |addr| {
+            let mut names = covering_syms(&cache, addr, SymType::Function)
+                .map(|idx| cache.name(idx).unwrap())
+                .collect::<Vec<_>>();
+            let () = names.sort();
+            names
+        };
+
+        assert_eq!(names(0x1004), vec!["global", "local", "weak"]);
+        assert_eq!(names(0x1008), vec!["local", "weak"]);
+        assert_eq!(names(0x1018), vec!["weak"]);
+        assert_eq!(names(0x1020), Vec::<&str>::new());
+        assert_eq!(names(0xfff), Vec::<&str>::new());
+    }
+
+    /// Check that we can find all symbols starting inside an address
+    /// range.
+    #[test]
+    fn range_sym_lookup() {
+        let sym = |st_name, st_shndx, st_value, st_size| Elf64_Sym {
+            st_name,
+            // STB_GLOBAL + STT_FUNC
+            st_info: (STB_GLOBAL << 4) | 2,
+            st_other: 0,
+            st_shndx,
+            st_value,
+            st_size,
+        };
+
+        let strs = b"\x00a\x00b\x00c\x00d\x00undef\x00";
+        let syms = vec![
+            sym(1, 1, 0x1000, 0x20),
+            sym(3, 1, 0x1020, 0x10),
+            sym(5, 1, 0x1030, 0x10),
+            sym(7, 1, 0x1040, 0x10),
+            sym(9, SHN_UNDEF, 0x1024, 0),
+        ];
+        let cache = SymbolTableCache::new(Cow::Owned(syms), strs);
+
+        let names = |range, spanning| {
+            syms_in_range(&cache, range, spanning)
+                .map(|idx| cache.name(idx).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(0x1020..0x1040, false), vec!["b", "c"]);
+        assert_eq!(names(0x1010..0x1040, false), vec!["b", "c"]);
+        assert_eq!(names(0x1010..0x1040, true), vec!["a", "b", "c"]);
+        assert_eq!(names(0x1020..0x1040, true), vec!["b", "c"]);
+        assert_eq!(names(0x1000..0x2000, false), vec!["a", "b", "c", "d"]);
+        assert_eq!(names(0x1010..0x1010, true), Vec::<&str>::new());
+        assert_eq!(names(0x2000..0x3000, true), Vec::<&str>::new());
+    }
+
+    /// Check that we can look up symbols in an address range of an ELF
+    /// file.
+    #[test]
+    fn elf_range_sym_lookup() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let parser = ElfParser::open(&path).unwrap();
+
+        let syms = parser.syms_in_range(0x2000100..0x2000101, false).unwrap();
+        assert_eq!(syms.len(), 1, "{syms:?}");
+        assert_eq!(syms[0].name, "factorial");
+        assert_eq!(syms[0].addr, 0x2000100);
+        assert_ne!(syms[0].size, 0);
+
+        // The symbol covering the start address should only be reported
+        // if asked for.
+        let syms = parser.syms_in_range(0x2000101..0x2000102, false).unwrap();
+        assert!(syms.is_empty(), "{syms:?}");
+        let syms = parser
+            .syms_in_range(0x2000101..0x2000102, true)
+            .unwrap()
+            .into_iter()
+            .map(|sym| sym.name)
+            .collect::<Vec<_>>();
+        assert_eq!(syms, vec!["factorial"]);
+    }
+
+    /// Check that we correctly resolve extended section indices of
+    /// symbols.
+    #[test]
+    fn extended_section_index_resolution() {
+        let sym = |st_name, st_shndx, st_value| Elf64_Sym {
+            st_name,
+            // STB_GLOBAL + STT_FUNC
+            st_info: 0x12,
+            st_other: 0,
+            st_shndx,
+            st_value,
+            st_size: 0x10,
+        };
+
+        let strs = b"\x00a\x00b\x00c\x00";
+        let syms = vec![
+            sym(1, SHN_XINDEX, 0x3000),
+            sym(3, 0x2, 0x1000),
+            sym(5, SHN_ABS, 0x2000),
+        ];
+        let mut cache = SymbolTableCache::new(Cow::Owned(syms), strs);
+        // Without an extended section index table present we have no way
+        // of resolving the section index.
+        let err = cache.section_index(2).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        cache.shndx = Some(Cow::Owned(vec![0x10042, 0, 0]));
+        // Symbols are in address order.
+        assert_eq!(cache.section_index(0).unwrap(), Some(0x2));
+        assert_eq!(cache.section_index(1).unwrap(), None);
+        assert_eq!(cache.section_index(2).unwrap(), Some(0x10042));
+        let err = cache.section_index(3).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    /// Check that absolute and common symbols are not reported for
+    /// address lookups.
+    #[test]
+    fn abs_common_symbol_skipping() {
+        let sym = |st_name, st_shndx, st_value, st_size| Elf64_Sym {
+            st_name,
+            // STB_GLOBAL + STT_OBJECT
+            st_info: 0x11,
+            st_other: 0,
+            st_shndx,
+            st_value,
+            st_size,
+        };
+
+        let syms = vec![
+            sym(1, SHN_ABS, 0, 0),
+            sym(5, SHN_COMMON, 0x10, 8),
+            sym(12, 1, 0x1000, 0x10),
+        ];
+        let strs = b"\x00abs\x00common\x00var\x00";
+        let cache = SymbolTableCache::new(Cow::Owned(syms), strs);
+
+        let result = find_sym(&cache, 0x4, SymType::Undefined).unwrap();
+        assert_eq!(result, None);
+        let result = find_sym(&cache, 0x14, SymType::Undefined).unwrap();
+        assert_eq!(result, None);
+
+        let sym = find_sym(&cache, 0x1008, SymType::Undefined)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "var");
+    }
+
+    /// Check that we fall back to `.dynsym` for objects stripped of
+    /// `.symtab`.
+    #[test]
+    fn dynsym_fallback() {
+        let so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so-stripped.so");
+        let parser = ElfParser::open(&so).unwrap();
+        assert_eq!(parser.find_section(".symtab").unwrap(), None);
+        assert!(parser.cache.ensure_symtab_cache().unwrap().is_empty());
+
+        let syms = parser
+            .find_addr("the_answer", &FindAddrOpts::default())
+            .unwrap();
+        assert_eq!(syms.len(), 1);
+        let addr = syms[0].addr;
+
+        let sym = parser.find_sym(addr, &FindSymOpts::Basic).unwrap().unwrap();
+        assert_eq!(sym.name, "the_answer");
+        assert_eq!(sym.addr, addr);
+    }
+
+    /// Check that we report sensible symbol table statistics.
+    #[test]
+    fn symbol_table_stats() {
+        let so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so-stripped.so");
+        let mut parser = ElfParser::open(&so).unwrap();
+        let stats = parser.sym_stats().unwrap();
+        assert_eq!(stats.table, Some(SymTable::DynSym));
+        assert!(stats.stripped);
+        assert!(stats.funcs > 0);
+        assert!(stats.total >= stats.funcs);
+
+        let () = parser.set_sym_source(SymSource::SymTab);
+        let stats = parser.sym_stats().unwrap();
+        assert_eq!(stats.table, None);
+        assert_eq!(stats.total, 0);
+
+        let exe = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs-no-dwarf.bin");
+        let parser = ElfParser::open(&exe).unwrap();
+        let stats = parser.sym_stats().unwrap();
+        assert_eq!(stats.table, Some(SymTable::SymTab));
+        assert!(!stats.stripped);
+    }
+
+    /// Check that we correctly translate file offsets into virtual
+    /// offsets.
+    #[test]
+    fn file_offset_translation() {
+        // Our test binary is pretty much guaranteed to contain a
+        // `.bss` section.
+        let exe = current_exe().unwrap();
+        let parser = ElfParser::open(&exe).unwrap();
+
+        let phdrs = parser.program_headers().unwrap();
+        let mut found_bss = false;
+        for phdr in phdrs.iter().filter(|phdr| phdr.p_type == PT_LOAD) {
+            if phdr.p_filesz > 0 {
+                let addr = parser.file_offset_to_virt_offset(phdr.p_offset).unwrap();
+                assert_eq!(addr, Some(phdr.p_vaddr));
+
+                let offset = phdr.p_offset + phdr.p_filesz - 1;
+                let addr = parser.file_offset_to_virt_offset(offset).unwrap();
+                assert_eq!(addr, Some(phdr.p_vaddr + phdr.p_filesz - 1));
+            }
+
+            // Offsets past the file backed part of a segment must not
+            // be attributed to its zero-initialized tail.
+            if phdr.p_memsz > phdr.p_filesz {
+                found_bss = true;
+                let offset = phdr.p_offset + phdr.p_filesz;
+                let addr = parser.file_offset_to_virt_offset(offset).unwrap();
+                assert_ne!(addr, Some(phdr.p_vaddr + phdr.p_filesz));
+            }
+        }
+        assert!(found_bss);
+    }
+
+    /// Check that we can decompress legacy GNU style compressed
+    /// section data.
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn zdebug_decompression() {
+        let data = [
+            0x5a, 0x4c, 0x49, 0x42, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b, 0x78, 0x9c,
+            0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0x28, 0xcf, 0x2f, 0xca, 0x49, 0x01, 0x00, 0x1a,
+            0x0b, 0x04, 0x5d,
+        ];
+        let decompressed = decompress_zdebug(&data).unwrap();
+        assert_eq!(decompressed, b"hello world");
+
+        // A size mismatch should be reported as an error.
+        let mut data = data;
+        data[11] = 0x0c;
+        let err = decompress_zdebug(&data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let err = decompress_zdebug(b"ZLIX").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that we can look up symbols in relocatable object files.
+    #[test]
+    fn relocatable_object_lookup() {
+        let obj = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-so.o");
+        let parser = ElfParser::open(&obj).unwrap();
+        assert!(parser.is_relocatable().unwrap());
+
+        // With `-ffunction-sections` each function resides in a
+        // dedicated section, with all of them starting at zero.
+        for name in ["the_answer", "the_ignored_answer"] {
+            let section = format!(".text.{name}");
+            let idx = parser.find_section(&section).unwrap().unwrap();
+            let shdr = &parser.section_headers().unwrap()[idx];
+            let addr = parser
+                .file_offset_to_virt_offset(shdr.sh_offset + 1)
+                .unwrap()
+                .unwrap();
+            let sym = parser.find_sym(addr, &FindSymOpts::Basic).unwrap().unwrap();
+            assert_eq!(sym.name, name);
+            assert_eq!(sym.section, Some(section.as_str()));
+        }
+
+        // `await_input` calls into libc, which requires relocations.
+        let idx = parser.find_section(".text.await_input").unwrap().unwrap();
+        let relas = parser.relocations(idx).unwrap();
+        assert!(!relas.is_empty());
+
+        // Sections without relocations should report none.
+        let idx = parser.find_section(".text.the_answer").unwrap().unwrap();
+        let relas = parser.relocations(idx).unwrap();
+        assert_eq!(relas, Vec::new());
+    }
+
+    /// Check that we synthesize symbols for PLT entries.
+    #[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+    #[test]
+    fn plt_symbol_synthesis() {
+        let so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = ElfParser::open(&so).unwrap();
+
+        let plt = parser.cache.ensure_plt();
+        // Lookup relies on entries being sorted by address.
+        assert!(plt.windows(2).all(|syms| syms[0].addr <= syms[1].addr));
+        // `libtest-so.so` calls into `fflush` in libc, which should be
+        // reflected by a PLT entry.
+        let fflush = plt
+            .iter()
+            .find(|sym| &*sym.name == "fflush@plt")
+            .unwrap_or_else(|| panic!("failed to find fflush PLT entry: {plt:?}"));
+
+        let sym = parser
+            .find_sym(fflush.addr + 1, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "fflush@plt");
+        assert_eq!(sym.addr, fflush.addr);
+        assert_eq!(sym.size, Some(fflush.size));
+        assert!(
+            matches!(sym.section, Some(".plt" | ".plt.sec")),
+            "{:?}",
+            sym.section
+        );
+
+        // With a separate `.plt.sec` section, the lazy binding stub in
+        // `.plt` should be reported as well.
+        if parser.find_section(".plt.sec").unwrap().is_some() {
+            let fflushs = plt
+                .iter()
+                .filter(|sym| &*sym.name == "fflush@plt")
+                .collect::<Vec<_>>();
+            assert_eq!(fflushs.len(), 2, "{fflushs:?}");
+            assert_ne!(fflushs[0].section, fflushs[1].section);
+        }
+    }
+
+    /// Check that a corrupt `.rela.plt` section does not prevent the
+    /// lookup of regular symbols.
+    #[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+    #[test]
+    fn corrupt_plt_relocations() {
+        let so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let mut data = read_file(&so).unwrap();
+
+        let (shdr_off, sym) = {
+            let parser = ElfParser::from_bytes(&data, None).unwrap();
+            let idx = parser.find_section(".rela.plt").unwrap().unwrap();
+            let ehdr = parser.cache.ensure_ehdr().unwrap();
+            // `sh_size` is located at offset 32 of an `Elf64_Shdr`.
+            let shdr_off =
+                ehdr.ehdr.e_shoff as usize + idx * usize::from(ehdr.ehdr.e_shentsize) + 32;
+            let sym = parser.pick_symtab_addr();
+            (shdr_off, (sym.0.to_string(), sym.1))
+        };
+
+        // Make the section size no longer a multiple of the relocation
+        // entry size.
+        let size = u64::from_ne_bytes(data[shdr_off..shdr_off + 8].try_into().unwrap());
+        let () = data[shdr_off..shdr_off + 8].copy_from_slice(&(size + 1).to_ne_bytes());
+
+        let parser = ElfParser::from_bytes(&data, None).unwrap();
+        assert!(parser.cache.parse_plt().is_err());
+        assert!(parser.cache.ensure_plt().is_empty());
+
+        let (name, addr) = sym;
+        let result = parser.find_sym(addr, &FindSymOpts::Basic).unwrap().unwrap();
+        assert_eq!(result.name, name);
+    }
+
+    /// Check that sections with names that are not valid UTF-8 are
+    /// skipped when searching for a section.
+    #[test]
+    fn invalid_section_name_skipping() {
+        let mut elf = ElfBuilder::new()
+            .section(".bad", SHT_PROGBITS, 0x1000, &[0; 16])
+            .text(0x2000, 0x10)
+            .build();
+        let offset = elf
+            .windows(5)
+            .position(|window| window == b".bad\0")
+            .unwrap();
+        elf[offset + 1] = 0xff;
+
+        let parser = ElfParser::from_bytes(&elf, None).unwrap();
+        assert_eq!(parser.find_section(".text").unwrap(), Some(2));
+        assert_eq!(parser.find_section(".data").unwrap(), None);
+    }
+
+    /// Check that compiler generated local symbols are only reported
+    /// if asked for.
+    #[test]
+    fn compiler_local_sym_filtering() {
+        let local = |addr, size| Elf64_Sym {
+            st_name: 0,
+            // STB_LOCAL + STT_FUNC
+            st_info: 2,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: addr,
+            st_size: size,
+        };
+        let label = |addr| Elf64_Sym {
+            st_name: 0,
+            // STB_LOCAL + STT_NOTYPE
+            st_info: 0,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: addr,
+            st_size: 0,
+        };
+        let elf = ElfBuilder::new()
+            .text(0x1000, 0x100)
+            .func(SymTable::SymTab, "foo", 0x1000, 0x100)
+            .sym(SymTable::SymTab, "", local(0x1010, 0x10))
+            .sym(SymTable::SymTab, ".LBB0_1", local(0x1040, 0x10))
+            .sym(SymTable::SymTab, ".Ltmp0", label(0x1060))
+            .sym(SymTable::SymTab, "static_fn", local(0x1080, 0x10))
+            .build();
+
+        let parser = ElfParser::from_bytes(&elf, None).unwrap();
+        for addr in [0x1014, 0x1044, 0x1064] {
+            let sym = parser.find_sym(addr, &FindSymOpts::Basic).unwrap().unwrap();
+            assert_eq!(sym.name, "foo");
+        }
+        // Regular local symbols are always reported.
+        let sym = parser
+            .find_sym(0x1084, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "static_fn");
+
+        let mut parser = ElfParser::from_bytes(&elf, None).unwrap();
+        let () = parser.set_compiler_local_syms(true);
+        let sym = parser
+            .find_sym(0x1014, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "");
+        let sym = parser
+            .find_sym(0x1044, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, ".LBB0_1");
+        // Untyped assembler local labels are reported as well.
+        let sym = parser
+            .find_sym(0x1064, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, ".Ltmp0");
+        // But they are never reported by name.
+        let syms = parser
+            .find_addr(".Ltmp0", &FindAddrOpts::default())
+            .unwrap();
+        assert!(syms.is_empty(), "{syms:?}");
+    }
+
+    /// Check that untyped compiler generated local labels in `.dynsym`
+    /// don't trip up name based lookups.
+    #[test]
+    fn compiler_local_dynsym_label() {
+        let label = |addr| Elf64_Sym {
+            st_name: 0,
+            // STB_LOCAL + STT_NOTYPE
+            st_info: 0,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: addr,
+            st_size: 0,
+        };
+        let elf = ElfBuilder::new()
+            .text(0x1000, 0x100)
+            .func(SymTable::SymTab, "foo", 0x1000, 0x100)
+            .func(SymTable::DynSym, "bar", 0x1000, 0x100)
+            .sym(SymTable::DynSym, ".Ltmp0", label(0x1060))
+            .build();
+
+        let mut parser = ElfParser::from_bytes(&elf, None).unwrap();
+        let () = parser.set_compiler_local_syms(true);
+        // A lookup of a name not present in `.symtab` makes us consult
+        // `.dynsym`. `bar` covers the same address as `foo` and is
+        // filtered as a duplicate.
+        let syms = parser.find_addr("bar", &FindAddrOpts::default()).unwrap();
+        assert!(syms.is_empty(), "{syms:?}");
+        let syms = parser
+            .find_addr(".Ltmp0", &FindAddrOpts::default())
+            .unwrap();
+        assert!(syms.is_empty(), "{syms:?}");
+    }
+
+    /// Check that we can find the label closest to an address inside a
+    /// function.
+    #[test]
+    fn label_lookup() {
+        let label = |addr| Elf64_Sym {
+            st_name: 0,
+            // STB_LOCAL + STT_NOTYPE
+            st_info: 0,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: addr,
+            st_size: 0,
+        };
+        let elf = ElfBuilder::new()
+            .text(0x1000, 0x200)
+            .func(SymTable::SymTab, "foo", 0x1000, 0x100)
+            .sym(SymTable::SymTab, "foo", label(0x1000))
+            .sym(SymTable::SymTab, "loop", label(0x1020))
+            .sym(SymTable::SymTab, "$x", label(0x1030))
+            .sym(SymTable::SymTab, "done", label(0x1040))
+            .func(SymTable::SymTab, "bar", 0x1100, 0x100)
+            .build();
+
+        let parser = ElfParser::from_bytes(&elf, None).unwrap();
+        let find_label = |addr| {
+            let sym = parser.find_sym(addr, &FindSymOpts::Basic).unwrap().unwrap();
+            assert_eq!(sym.name, "foo");
+            parser.find_label(addr, &sym).unwrap()
+        };
+
+        // A label carrying the function's name is not reported.
+        assert_eq!(find_label(0x1010), None);
+        assert_eq!(find_label(0x1020), Some("loop"));
+        // Mapping symbols are not labels.
+        assert_eq!(find_label(0x1034), Some("loop"));
+        assert_eq!(find_label(0x10ff), Some("done"));
+
+        // Labels of one function are never reported for another.
+        let sym = parser
+            .find_sym(0x1104, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "bar");
+        assert_eq!(parser.find_label(0x1104, &sym).unwrap(), None);
+    }
+
+    /// Check that the label reported for an address does not depend on
+    /// the order of labels sharing an address in the symbol table.
+    #[test]
+    fn label_lookup_order_independence() {
+        let label = |addr| Elf64_Sym {
+            st_name: 0,
+            // STB_LOCAL + STT_NOTYPE
+            st_info: 0,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: addr,
+            st_size: 0,
+        };
+
+        for names in [["alias1", "alias2"], ["alias2", "alias1"]] {
+            let elf = ElfBuilder::new()
+                .text(0x1000, 0x100)
+                .func(SymTable::SymTab, "foo", 0x1000, 0x100)
+                .sym(SymTable::SymTab, names[0], label(0x1020))
+                .sym(SymTable::SymTab, names[1], label(0x1020))
+                .build();
+
+            let parser = ElfParser::from_bytes(&elf, None).unwrap();
+            let sym = parser
+                .find_sym(0x1024, &FindSymOpts::Basic)
+                .unwrap()
+                .unwrap();
+            let label = parser.find_label(0x1024, &sym).unwrap();
+            assert_eq!(label, Some("alias2"));
+        }
+    }
+
+    /// Check that we can look up symbols by their index in the symbol
+    /// table.
+    #[test]
+    fn sym_lookup_by_index() {
+        let abs = Elf64_Sym {
+            st_name: 0,
+            // STB_LOCAL + STT_OBJECT
+            st_info: 1,
+            st_other: 0,
+            st_shndx: SHN_ABS,
+            st_value: 0x42,
+            st_size: 8,
+        };
+        let elf = ElfBuilder::new()
+            .text(0x1000, 0x100)
+            .func(SymTable::SymTab, "foo", 0x1080, 0x10)
+            .sym(SymTable::SymTab, "abs", abs)
+            .func(SymTable::DynSym, "bar", 0x1000, 0x20)
+            .build();
+        let parser = ElfParser::from_bytes(&elf, None).unwrap();
+
+        let sym = parser.sym_by_index(SymTable::SymTab, 1).unwrap();
+        assert_eq!(sym.name, "foo");
+        assert_eq!(sym.addr, 0x1080);
+        assert_eq!(sym.size, 0x10);
+        assert_eq!(sym.type_, STT_FUNC);
+        assert_eq!(sym.binding, STB_GLOBAL);
+        assert_eq!(sym.section, Some(1));
+
+        let sym = parser.sym_by_index(SymTable::SymTab, 2).unwrap();
+        assert_eq!(sym.name, "abs");
+        assert_eq!(sym.binding, STB_LOCAL);
+        assert_eq!(sym.section, None);
+
+        let sym = parser.sym_by_index(SymTable::DynSym, 1).unwrap();
+        assert_eq!(sym.name, "bar");
+
+        let err = parser.sym_by_index(SymTable::SymTab, 3).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
 }
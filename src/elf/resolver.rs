@@ -1,13 +1,24 @@
+#[cfg(feature = "breakpad")]
+use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::io::Read;
+use std::io::Seek;
+#[cfg(feature = "breakpad")]
+use std::io::Write;
 use std::ops::Deref as _;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 
+#[cfg(feature = "breakpad")]
+use crate::breakpad::write_elf;
 #[cfg(feature = "dwarf")]
 use crate::dwarf::DwarfResolver;
+#[cfg(feature = "dwarf")]
+use crate::dwarf::LineRow;
 use crate::elf::DEFAULT_DEBUG_DIRS;
 use crate::file_cache::FileCache;
 use crate::inspect::FindAddrOpts;
@@ -17,6 +28,8 @@ use crate::once::OnceCell;
 use crate::symbolize::FindSymOpts;
 use crate::symbolize::Reason;
 use crate::symbolize::ResolvedSym;
+use crate::symbolize::SymSource;
+use crate::symbolize::SymTable;
 use crate::symbolize::Symbolize;
 use crate::symbolize::TranslateFileOffset;
 use crate::Addr;
@@ -50,10 +63,15 @@ impl FileCache<ElfResolverData> {
     /// and the provided list of debug directories consulted when
     /// following debug links.
     /// If `debug_dirs` is `None` only ELF symbols will be consulted.
+    /// `sym_source` determines the ELF symbol table(s) consulted and
+    /// `compiler_local_syms` whether compiler generated local symbols
+    /// are reported.
     pub(crate) fn elf_resolver<'slf>(
         &'slf self,
         path: &Path,
         debug_dirs: Option<&[PathBuf]>,
+        sym_source: SymSource,
+        compiler_local_syms: bool,
     ) -> Result<&'slf Rc<ElfResolver>> {
         let (file, cell) = self.entry(path)?;
         let resolver = if let Some(data) = cell.get() {
@@ -82,8 +100,10 @@ impl FileCache<ElfResolverData> {
             }
             .clone()
         } else {
-            let parser = Rc::new(ElfParser::open_file(file, path)?);
-            let resolver = ElfResolver::from_parser(parser, debug_dirs)?;
+            let mut parser = ElfParser::open_file(file, path)?;
+            let () = parser.set_sym_source(sym_source);
+            let () = parser.set_compiler_local_syms(compiler_local_syms);
+            let resolver = ElfResolver::from_parser(Rc::new(parser), debug_dirs)?;
             Rc::new(resolver)
         };
 
@@ -112,6 +132,49 @@ impl FileCache<ElfResolverData> {
 }
 
 
+/// A symbol as present in an ELF symbol table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElfSym<'src> {
+    /// The name of the symbol.
+    pub name: &'src str,
+    /// The symbol's value, typically its address.
+    pub addr: Addr,
+    /// The symbol's size, or zero if it is unknown.
+    pub size: u64,
+    /// The symbol's type (one of the `STT_*` constants).
+    pub type_: u8,
+    /// The symbol's binding (one of the `STB_*` constants).
+    pub binding: u8,
+    /// The index of the section the symbol is associated with, or
+    /// `None` if it references a reserved section index (such as
+    /// `SHN_ABS`).
+    pub section: Option<usize>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// A relocation as present in an ELF relocation section.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElfReloc {
+    /// The location at which to apply the relocation, relative to the
+    /// start of the section it applies to for relocatable objects.
+    pub offset: u64,
+    /// The index of the symbol the relocation refers to, in the symbol
+    /// table associated with the relocation section.
+    pub sym_idx: usize,
+    /// The (architecture specific) type of the relocation.
+    pub type_: u32,
+    /// The constant addend used for computing the relocated value.
+    /// Relocations without an explicit addend (`SHT_REL`) report zero.
+    pub addend: i64,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
 /// The symbol resolver for a single ELF file.
 pub struct ElfResolver {
     backend: ElfBackend,
@@ -136,6 +199,29 @@ impl ElfResolver {
         )
     }
 
+    /// Create a `ElfResolver` that loads data from the provided reader.
+    ///
+    /// The reader's contents are copied into memory in their entirety,
+    /// making it possible to symbolize ELF data that is not backed by
+    /// a file on disk, such as a member of an archive. Because no path
+    /// is associated with the data, debug links are only looked up in
+    /// the default debug directories.
+    pub fn from_reader<R>(reader: R) -> Result<Self>
+    where
+        R: Read + Seek,
+    {
+        let parser = Rc::new(ElfParser::from_reader(reader, None)?);
+        Self::from_parser(
+            parser,
+            Some(
+                &DEFAULT_DEBUG_DIRS
+                    .iter()
+                    .map(PathBuf::from)
+                    .collect::<Vec<_>>(),
+            ),
+        )
+    }
+
     /// Create a new [`ElfResolver`] using `parser`.
     ///
     /// If `debug_dirs` is `Some`, interpret DWARF debug information. If it is
@@ -144,6 +230,15 @@ impl ElfResolver {
         parser: Rc<ElfParser>,
         debug_dirs: Option<&[PathBuf]>,
     ) -> Result<Self> {
+        // We don't apply relocations to DWARF data, so for relocatable
+        // objects we have to stick to ELF symbols.
+        #[cfg(feature = "dwarf")]
+        let debug_dirs = if parser.is_relocatable()? {
+            None
+        } else {
+            debug_dirs
+        };
+
         #[cfg(feature = "dwarf")]
         let backend = if let Some(debug_dirs) = debug_dirs {
             let dwarf = DwarfResolver::from_parser(parser, debug_dirs)?;
@@ -160,7 +255,8 @@ impl ElfResolver {
         Ok(resolver)
     }
 
-    fn parser(&self) -> &Rc<ElfParser> {
+    /// Retrieve the underlying [`ElfParser`].
+    pub(crate) fn parser(&self) -> &Rc<ElfParser> {
         match &self.backend {
             #[cfg(feature = "dwarf")]
             ElfBackend::Dwarf(dwarf) => dwarf.parser(),
@@ -168,10 +264,114 @@ impl ElfResolver {
         }
     }
 
+    /// Eagerly parse all data required for lookups with the given
+    /// options.
+    pub(crate) fn warmup(&self, opts: &FindSymOpts) -> Result<()> {
+        match &self.backend {
+            #[cfg(feature = "dwarf")]
+            ElfBackend::Dwarf(dwarf) => dwarf.warmup(opts),
+            ElfBackend::Elf(parser) => {
+                let _opts = opts;
+                parser.warmup()
+            }
+        }
+    }
+
     /// Retrieve the path to the ELF file represented by this resolver.
     pub(crate) fn path(&self) -> Option<&Path> {
         self.parser().path()
     }
+
+    /// Invoke `f` for each row of the DWARF line programs, along with
+    /// the address range it covers.
+    ///
+    /// Nothing is reported if DWARF information is not used.
+    #[cfg(feature = "breakpad")]
+    pub(crate) fn for_each_line(
+        &self,
+        f: &mut dyn FnMut(Range<Addr>, &Path, &OsStr, u32),
+    ) -> Result<()> {
+        match &self.backend {
+            #[cfg(feature = "dwarf")]
+            ElfBackend::Dwarf(dwarf) => dwarf.for_each_line(f),
+            ElfBackend::Elf(_parser) => {
+                let _f = f;
+                Ok(())
+            }
+        }
+    }
+
+    /// Retrieve the rows of the DWARF line number program covering the
+    /// function containing `addr`.
+    ///
+    /// This is a diagnostic API, reporting rows as decoded from the
+    /// debug information. An empty `Vec` is returned if no DWARF
+    /// information is used or no function contains `addr`.
+    #[cfg(feature = "dwarf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dwarf")))]
+    pub fn line_rows(&self, addr: Addr) -> Result<Vec<LineRow<'_>>> {
+        match &self.backend {
+            ElfBackend::Dwarf(dwarf) => dwarf.line_rows(addr),
+            ElfBackend::Elf(_parser) => Ok(Vec::new()),
+        }
+    }
+
+    /// Retrieve the symbol at index `idx` of the provided symbol table.
+    ///
+    /// Indices refer to the order in which symbols are stored in the
+    /// file, as used by relocations, for example. All symbols are
+    /// reported, irrespective of their type.
+    pub fn sym_by_index(&self, table: SymTable, idx: usize) -> Result<ElfSym<'_>> {
+        self.parser().sym_by_index(table, idx)
+    }
+
+    /// Retrieve all symbols starting inside of `range`, in address
+    /// order.
+    ///
+    /// If `spanning` is `true`, the symbol covering `range.start` is
+    /// reported first, even if it starts before the range, making it
+    /// possible to enumerate all functions overlapping with `range`.
+    /// Symbols are taken from the first non-empty symbol table used for
+    /// symbolization.
+    pub fn syms_in_range(&self, range: Range<Addr>, spanning: bool) -> Result<Vec<ElfSym<'_>>> {
+        self.parser().syms_in_range(range, spanning)
+    }
+
+    /// Retrieve all relocations applying to the section at index
+    /// `section`.
+    ///
+    /// Symbol indices reported can be resolved using
+    /// [`sym_by_index`][Self::sym_by_index].
+    pub fn relocations(&self, section: usize) -> Result<Vec<ElfReloc>> {
+        let relas = self.parser().relocations(section)?;
+        let relocs = relas
+            .iter()
+            .map(|rela| ElfReloc {
+                offset: rela.r_offset,
+                sym_idx: rela.sym_idx() as usize,
+                type_: rela.type_(),
+                addend: rela.r_addend,
+                _non_exhaustive: (),
+            })
+            .collect();
+        Ok(relocs)
+    }
+
+    /// Write the symbols of the ELF file, along with source code line
+    /// information if available, in the Breakpad symbol file format.
+    ///
+    /// Function symbols and line records use file offsets as
+    /// addresses, meaning that the output can be used for symbolizing
+    /// file offsets via the
+    /// [`Breakpad`][crate::symbolize::source::Breakpad] source.
+    #[cfg(feature = "breakpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "breakpad")))]
+    pub fn write_breakpad<W>(&self, mut writer: W) -> Result<()>
+    where
+        W: Write,
+    {
+        write_elf(self, &mut writer)
+    }
 }
 
 impl Symbolize for ElfResolver {
@@ -183,6 +383,61 @@ impl Symbolize for ElfResolver {
             ElfBackend::Elf(parser) => parser.find_sym(addr, opts),
         }
     }
+
+    fn find_syms(
+        &self,
+        addr: Addr,
+        opts: &FindSymOpts,
+    ) -> Result<Result<Vec<ResolvedSym<'_>>, Reason>> {
+        match &self.backend {
+            #[cfg(feature = "dwarf")]
+            ElfBackend::Dwarf(dwarf) => {
+                // Source code information is only available for the
+                // symbol found using DWARF. All others are looked up in
+                // the ELF symbol tables.
+                let sym = match dwarf.find_sym(addr, opts)? {
+                    Ok(sym) => sym,
+                    Err(reason) => return Ok(Err(reason)),
+                };
+                let others = dwarf
+                    .parser()
+                    .find_syms(addr, &FindSymOpts::Basic)?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|other| other.name != sym.name || other.addr != sym.addr);
+                let mut syms = vec![sym];
+                let () = syms.extend(others);
+                Ok(Ok(syms))
+            }
+            ElfBackend::Elf(parser) => parser.find_syms(addr, opts),
+        }
+    }
+
+    fn fill_code_info<'slf>(
+        &'slf self,
+        sym: &mut ResolvedSym<'slf>,
+        addr: Addr,
+        opts: &FindSymOpts,
+    ) -> Result<()> {
+        match &self.backend {
+            #[cfg(feature = "dwarf")]
+            ElfBackend::Dwarf(dwarf) => dwarf.fill_code_info(sym, addr, opts),
+            // ELF doesn't carry any source code or inlining information.
+            ElfBackend::Elf(_parser) => {
+                let (_sym, _addr, _opts) = (sym, addr, opts);
+                Ok(())
+            }
+        }
+    }
+
+    fn find_label<'slf>(
+        &'slf self,
+        addr: Addr,
+        sym: &ResolvedSym<'slf>,
+    ) -> Result<Option<&'slf str>> {
+        // Labels are only ever present in the ELF symbol tables.
+        self.parser().find_label(addr, sym)
+    }
 }
 
 impl TranslateFileOffset for ElfResolver {
@@ -230,6 +485,9 @@ impl Debug for ElfResolver {
 mod tests {
     use super::*;
 
+    use std::fs::read;
+    use std::io::Cursor;
+    use std::io::SeekFrom;
 
     /// Exercise the `Debug` representation of various types.
     #[test]
@@ -262,4 +520,121 @@ mod tests {
         assert_eq!(parser.find_file_offset(0x0).unwrap(), None);
         assert_eq!(parser.find_file_offset(0xffffffffffffffff).unwrap(), None);
     }
+
+    /// Check that we can symbolize an address using ELF data provided
+    /// by a reader.
+    #[test]
+    fn symbolize_from_reader() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let data = read(&path).unwrap();
+        let mut cursor = Cursor::new(data);
+        // Move the reader away from the start to make sure that it gets
+        // rewound.
+        let _pos = cursor.seek(SeekFrom::End(0)).unwrap();
+
+        let resolver = ElfResolver::from_reader(cursor).unwrap();
+        let sym = resolver
+            .find_sym(0x2000100, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert_eq!(sym.addr, 0x2000100);
+        assert_eq!(resolver.path(), None);
+    }
+
+    /// Check that we can retrieve the relocations of a section and
+    /// resolve the symbols they reference.
+    #[test]
+    fn section_relocations() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-so.o");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let idx = parser.find_section(".text.await_input").unwrap().unwrap();
+        let resolver = ElfResolver::from_parser(parser, None).unwrap();
+
+        let relocs = resolver.relocations(idx).unwrap();
+        let names = relocs
+            .iter()
+            .map(|reloc| {
+                resolver
+                    .sym_by_index(SymTable::SymTab, reloc.sym_idx)
+                    .unwrap()
+                    .name
+            })
+            .collect::<Vec<_>>();
+        assert!(names.contains(&"fflush"), "{names:?}");
+        assert!(names.contains(&"getc"), "{names:?}");
+    }
+
+    /// Check that we can retrieve the line number program rows of a
+    /// function.
+    #[cfg(feature = "dwarf")]
+    #[test]
+    fn function_line_rows() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let resolver = ElfResolver::from_parser(parser.clone(), Some(&[])).unwrap();
+
+        let sym = resolver
+            .find_sym(0x2000100, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+        let range = sym.addr..sym.addr + sym.size.unwrap() as Addr;
+
+        let rows = resolver.line_rows(0x2000104).unwrap();
+        assert!(!rows.is_empty());
+        assert_eq!(rows[0].addr, 0x2000100);
+        assert!(rows
+            .iter()
+            .all(|row| range.contains(&row.addr) || row.end_sequence && row.addr == range.end));
+        assert!(rows.windows(2).all(|rows| rows[0].addr <= rows[1].addr));
+        assert!(rows
+            .iter()
+            .any(|row| row.file == "test-stable-addrs.c" && row.line != 0));
+
+        // Without DWARF we don't report any rows.
+        let resolver = ElfResolver::from_parser(parser, None).unwrap();
+        assert_eq!(resolver.line_rows(0x2000104).unwrap(), Vec::new());
+    }
+
+    /// Check that we can incrementally fill in source code and inlined
+    /// function information for a previously found symbol.
+    #[cfg(feature = "dwarf")]
+    #[test]
+    fn incremental_code_info() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let resolver = ElfResolver::from_parser(parser, Some(&[])).unwrap();
+
+        let addr = 0x200020a;
+        let expected = resolver
+            .find_sym(addr, &FindSymOpts::CodeInfoAndInlined)
+            .unwrap()
+            .unwrap();
+        assert!(!expected.inlined.is_empty());
+
+        let mut sym = resolver
+            .find_sym(addr, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.code_info, None);
+
+        let () = resolver
+            .fill_code_info(&mut sym, addr, &FindSymOpts::Basic)
+            .unwrap();
+        assert_eq!(sym.code_info, None);
+
+        let () = resolver
+            .fill_code_info(&mut sym, addr, &FindSymOpts::CodeInfoAndInlined)
+            .unwrap();
+        assert_eq!(sym, expected);
+    }
 }
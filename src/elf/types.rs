@@ -3,16 +3,90 @@ use crate::SymType;
 
 const EI_NIDENT: usize = 16;
 
+pub(crate) const EI_CLASS: usize = 4;
+pub(crate) const EI_DATA: usize = 5;
+
+pub(crate) const ELFCLASS32: u8 = 1;
+pub(crate) const ELFCLASS64: u8 = 2;
+
+pub(crate) const ELFDATA2LSB: u8 = 1;
+pub(crate) const ELFDATA2MSB: u8 = 2;
+
+
+/// A trait for ELF data structures that can have the byte order of
+/// their members swapped, in order to convert between little and big
+/// endian representations.
+pub(crate) trait ByteSwap {
+    /// Create a copy of the object with the byte order of all its
+    /// members swapped.
+    fn swap_bytes(&self) -> Self;
+}
+
+
+type Elf32_Addr = u32;
+type Elf32_Half = u16;
+type Elf32_Off = u32;
+type Elf32_Sword = i32;
+type Elf32_Word = u32;
+
 type Elf64_Addr = u64;
 type Elf64_Half = u16;
 type Elf64_Off = u64;
+type Elf64_Sxword = i64;
 type Elf64_Word = u32;
 type Elf64_Xword = u64;
 
+pub(crate) const ET_REL: u16 = 1;
 pub(crate) const ET_EXEC: u16 = 2;
 pub(crate) const ET_DYN: u16 = 3;
 
-#[derive(Debug)]
+pub(crate) const EM_X86_64: u16 = 62;
+pub(crate) const EM_AARCH64: u16 = 183;
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf32_Ehdr {
+    pub e_ident: [u8; EI_NIDENT],
+    pub e_type: Elf32_Half,
+    pub e_machine: Elf32_Half,
+    pub e_version: Elf32_Word,
+    pub e_entry: Elf32_Addr,
+    pub e_phoff: Elf32_Off,
+    pub e_shoff: Elf32_Off,
+    pub e_flags: Elf32_Word,
+    pub e_ehsize: Elf32_Half,
+    pub e_phentsize: Elf32_Half,
+    pub e_phnum: Elf32_Half,
+    pub e_shentsize: Elf32_Half,
+    pub e_shnum: Elf32_Half,
+    pub e_shstrndx: Elf32_Half,
+}
+
+// SAFETY: `Elf32_Ehdr` is valid for any bit pattern.
+unsafe impl Pod for Elf32_Ehdr {}
+
+impl ByteSwap for Elf32_Ehdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            e_ident: self.e_ident,
+            e_type: self.e_type.swap_bytes(),
+            e_machine: self.e_machine.swap_bytes(),
+            e_version: self.e_version.swap_bytes(),
+            e_entry: self.e_entry.swap_bytes(),
+            e_phoff: self.e_phoff.swap_bytes(),
+            e_shoff: self.e_shoff.swap_bytes(),
+            e_flags: self.e_flags.swap_bytes(),
+            e_ehsize: self.e_ehsize.swap_bytes(),
+            e_phentsize: self.e_phentsize.swap_bytes(),
+            e_phnum: self.e_phnum.swap_bytes(),
+            e_shentsize: self.e_shentsize.swap_bytes(),
+            e_shnum: self.e_shnum.swap_bytes(),
+            e_shstrndx: self.e_shstrndx.swap_bytes(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub(crate) struct Elf64_Ehdr {
     pub e_ident: [u8; EI_NIDENT], /* ELF "magic number" */
@@ -34,9 +108,83 @@ pub(crate) struct Elf64_Ehdr {
 // SAFETY: `Elf64_Ehdr` is valid for any bit pattern.
 unsafe impl Pod for Elf64_Ehdr {}
 
+impl ByteSwap for Elf64_Ehdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            e_ident: self.e_ident,
+            e_type: self.e_type.swap_bytes(),
+            e_machine: self.e_machine.swap_bytes(),
+            e_version: self.e_version.swap_bytes(),
+            e_entry: self.e_entry.swap_bytes(),
+            e_phoff: self.e_phoff.swap_bytes(),
+            e_shoff: self.e_shoff.swap_bytes(),
+            e_flags: self.e_flags.swap_bytes(),
+            e_ehsize: self.e_ehsize.swap_bytes(),
+            e_phentsize: self.e_phentsize.swap_bytes(),
+            e_phnum: self.e_phnum.swap_bytes(),
+            e_shentsize: self.e_shentsize.swap_bytes(),
+            e_shnum: self.e_shnum.swap_bytes(),
+            e_shstrndx: self.e_shstrndx.swap_bytes(),
+        }
+    }
+}
+
+impl From<&Elf32_Ehdr> for Elf64_Ehdr {
+    fn from(other: &Elf32_Ehdr) -> Self {
+        Self {
+            e_ident: other.e_ident,
+            e_type: other.e_type,
+            e_machine: other.e_machine,
+            e_version: other.e_version,
+            e_entry: other.e_entry.into(),
+            e_phoff: other.e_phoff.into(),
+            e_shoff: other.e_shoff.into(),
+            e_flags: other.e_flags,
+            e_ehsize: other.e_ehsize,
+            e_phentsize: other.e_phentsize,
+            e_phnum: other.e_phnum,
+            e_shentsize: other.e_shentsize,
+            e_shnum: other.e_shnum,
+            e_shstrndx: other.e_shstrndx,
+        }
+    }
+}
+
 pub(crate) const PT_LOAD: u32 = 1;
+pub(crate) const PT_NOTE: u32 = 4;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf32_Phdr {
+    pub p_type: Elf32_Word,
+    pub p_offset: Elf32_Off,
+    pub p_vaddr: Elf32_Addr,
+    pub p_paddr: Elf32_Addr,
+    pub p_filesz: Elf32_Word,
+    pub p_memsz: Elf32_Word,
+    pub p_flags: Elf32_Word,
+    pub p_align: Elf32_Word,
+}
+
+// SAFETY: `Elf32_Phdr` is valid for any bit pattern.
+unsafe impl Pod for Elf32_Phdr {}
+
+impl ByteSwap for Elf32_Phdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            p_type: self.p_type.swap_bytes(),
+            p_offset: self.p_offset.swap_bytes(),
+            p_vaddr: self.p_vaddr.swap_bytes(),
+            p_paddr: self.p_paddr.swap_bytes(),
+            p_filesz: self.p_filesz.swap_bytes(),
+            p_memsz: self.p_memsz.swap_bytes(),
+            p_flags: self.p_flags.swap_bytes(),
+            p_align: self.p_align.swap_bytes(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub(crate) struct Elf64_Phdr {
     pub p_type: Elf64_Word,
@@ -52,11 +200,76 @@ pub(crate) struct Elf64_Phdr {
 // SAFETY: `Elf64_Phdr` is valid for any bit pattern.
 unsafe impl Pod for Elf64_Phdr {}
 
+impl ByteSwap for Elf64_Phdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            p_type: self.p_type.swap_bytes(),
+            p_flags: self.p_flags.swap_bytes(),
+            p_offset: self.p_offset.swap_bytes(),
+            p_vaddr: self.p_vaddr.swap_bytes(),
+            p_paddr: self.p_paddr.swap_bytes(),
+            p_filesz: self.p_filesz.swap_bytes(),
+            p_memsz: self.p_memsz.swap_bytes(),
+            p_align: self.p_align.swap_bytes(),
+        }
+    }
+}
+
+impl From<&Elf32_Phdr> for Elf64_Phdr {
+    fn from(other: &Elf32_Phdr) -> Self {
+        Self {
+            p_type: other.p_type,
+            p_flags: other.p_flags,
+            p_offset: other.p_offset.into(),
+            p_vaddr: other.p_vaddr.into(),
+            p_paddr: other.p_paddr.into(),
+            p_filesz: other.p_filesz.into(),
+            p_memsz: other.p_memsz.into(),
+            p_align: other.p_align.into(),
+        }
+    }
+}
+
 pub(crate) const PF_X: Elf64_Word = 1;
 
 pub(crate) const PN_XNUM: u16 = 0xffff;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf32_Shdr {
+    pub sh_name: Elf32_Word,
+    pub sh_type: Elf32_Word,
+    pub sh_flags: Elf32_Word,
+    pub sh_addr: Elf32_Addr,
+    pub sh_offset: Elf32_Off,
+    pub sh_size: Elf32_Word,
+    pub sh_link: Elf32_Word,
+    pub sh_info: Elf32_Word,
+    pub sh_addralign: Elf32_Word,
+    pub sh_entsize: Elf32_Word,
+}
+
+// SAFETY: `Elf32_Shdr` is valid for any bit pattern.
+unsafe impl Pod for Elf32_Shdr {}
+
+impl ByteSwap for Elf32_Shdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            sh_name: self.sh_name.swap_bytes(),
+            sh_type: self.sh_type.swap_bytes(),
+            sh_flags: self.sh_flags.swap_bytes(),
+            sh_addr: self.sh_addr.swap_bytes(),
+            sh_offset: self.sh_offset.swap_bytes(),
+            sh_size: self.sh_size.swap_bytes(),
+            sh_link: self.sh_link.swap_bytes(),
+            sh_info: self.sh_info.swap_bytes(),
+            sh_addralign: self.sh_addralign.swap_bytes(),
+            sh_entsize: self.sh_entsize.swap_bytes(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub(crate) struct Elf64_Shdr {
     pub sh_name: Elf64_Word,       /* Section name, index in string tbl */
@@ -74,19 +287,97 @@ pub(crate) struct Elf64_Shdr {
 // SAFETY: `Elf64_Shdr` is valid for any bit pattern.
 unsafe impl Pod for Elf64_Shdr {}
 
+impl ByteSwap for Elf64_Shdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            sh_name: self.sh_name.swap_bytes(),
+            sh_type: self.sh_type.swap_bytes(),
+            sh_flags: self.sh_flags.swap_bytes(),
+            sh_addr: self.sh_addr.swap_bytes(),
+            sh_offset: self.sh_offset.swap_bytes(),
+            sh_size: self.sh_size.swap_bytes(),
+            sh_link: self.sh_link.swap_bytes(),
+            sh_info: self.sh_info.swap_bytes(),
+            sh_addralign: self.sh_addralign.swap_bytes(),
+            sh_entsize: self.sh_entsize.swap_bytes(),
+        }
+    }
+}
+
+impl From<&Elf32_Shdr> for Elf64_Shdr {
+    fn from(other: &Elf32_Shdr) -> Self {
+        Self {
+            sh_name: other.sh_name,
+            sh_type: other.sh_type,
+            sh_flags: other.sh_flags.into(),
+            sh_addr: other.sh_addr.into(),
+            sh_offset: other.sh_offset.into(),
+            sh_size: other.sh_size.into(),
+            sh_link: other.sh_link,
+            sh_info: other.sh_info,
+            sh_addralign: other.sh_addralign.into(),
+            sh_entsize: other.sh_entsize.into(),
+        }
+    }
+}
+
+pub(crate) const SHF_ALLOC: u64 = 0x2;
 pub(crate) const SHF_COMPRESSED: u64 = 0x800;
 
 pub(crate) const SHN_UNDEF: u16 = 0;
 pub(crate) const SHN_LORESERVE: u16 = 0xff00;
+pub(crate) const SHN_ABS: u16 = 0xfff1;
+pub(crate) const SHN_COMMON: u16 = 0xfff2;
 pub(crate) const SHN_XINDEX: u16 = 0xffff;
 
+pub(crate) const SHT_PROGBITS: Elf64_Word = 1;
+pub(crate) const SHT_SYMTAB: Elf64_Word = 2;
+pub(crate) const SHT_STRTAB: Elf64_Word = 3;
+pub(crate) const SHT_RELA: Elf64_Word = 4;
 pub(crate) const SHT_NOTE: Elf64_Word = 7;
 pub(crate) const SHT_NOBITS: Elf64_Word = 8;
+pub(crate) const SHT_REL: Elf64_Word = 9;
+pub(crate) const SHT_DYNSYM: Elf64_Word = 11;
+pub(crate) const SHT_SYMTAB_SHNDX: Elf64_Word = 18;
+
+pub(crate) const STB_LOCAL: u8 = 0;
+pub(crate) const STB_GLOBAL: u8 = 1;
+pub(crate) const STB_WEAK: u8 = 2;
 
+pub(crate) const STV_DEFAULT: u8 = 0;
+
+pub(crate) const STT_NOTYPE: u8 = 0;
 pub(crate) const STT_OBJECT: u8 = 1;
 pub(crate) const STT_FUNC: u8 = 2;
 pub(crate) const STT_GNU_IFUNC: u8 = 10;
 
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf32_Sym {
+    pub st_name: Elf32_Word,
+    pub st_value: Elf32_Addr,
+    pub st_size: Elf32_Word,
+    pub st_info: u8,
+    pub st_other: u8,
+    pub st_shndx: Elf32_Half,
+}
+
+// SAFETY: `Elf32_Sym` is valid for any bit pattern.
+unsafe impl Pod for Elf32_Sym {}
+
+impl ByteSwap for Elf32_Sym {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            st_name: self.st_name.swap_bytes(),
+            st_value: self.st_value.swap_bytes(),
+            st_size: self.st_size.swap_bytes(),
+            st_info: self.st_info,
+            st_other: self.st_other,
+            st_shndx: self.st_shndx.swap_bytes(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub(crate) struct Elf64_Sym {
@@ -105,6 +396,20 @@ impl Elf64_Sym {
         self.st_info & 0xf
     }
 
+    /// Extract the symbols binding, typically represented by a STB_*
+    /// constant.
+    #[inline]
+    pub fn binding(&self) -> u8 {
+        self.st_info >> 4
+    }
+
+    /// Extract the symbols visibility, typically represented by a
+    /// STV_* constant.
+    #[inline]
+    pub fn visibility(&self) -> u8 {
+        self.st_other & 0x3
+    }
+
     /// Check whether the symbol's type matches that represented by the
     /// given [`SymType`].
     #[inline]
@@ -136,6 +441,166 @@ impl TryFrom<&Elf64_Sym> for SymType {
 // SAFETY: `Elf64_Sym` is valid for any bit pattern.
 unsafe impl Pod for Elf64_Sym {}
 
+impl ByteSwap for Elf64_Sym {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            st_name: self.st_name.swap_bytes(),
+            st_info: self.st_info,
+            st_other: self.st_other,
+            st_shndx: self.st_shndx.swap_bytes(),
+            st_value: self.st_value.swap_bytes(),
+            st_size: self.st_size.swap_bytes(),
+        }
+    }
+}
+
+impl From<&Elf32_Sym> for Elf64_Sym {
+    fn from(other: &Elf32_Sym) -> Self {
+        Self {
+            st_name: other.st_name,
+            st_info: other.st_info,
+            st_other: other.st_other,
+            st_shndx: other.st_shndx,
+            st_value: other.st_value.into(),
+            st_size: other.st_size.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf32_Rel {
+    pub r_offset: Elf32_Addr,
+    pub r_info: Elf32_Word,
+}
+
+// SAFETY: `Elf32_Rel` is valid for any bit pattern.
+unsafe impl Pod for Elf32_Rel {}
+
+impl ByteSwap for Elf32_Rel {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            r_offset: self.r_offset.swap_bytes(),
+            r_info: self.r_info.swap_bytes(),
+        }
+    }
+}
+
+/// Convert a 32 bit `r_info` value into its 64 bit counterpart.
+///
+/// The two use different splits between symbol index and relocation
+/// type.
+#[inline]
+fn widen_r_info(r_info: Elf32_Word) -> Elf64_Xword {
+    let sym_idx = Elf64_Xword::from(r_info >> 8);
+    let type_ = Elf64_Xword::from(r_info & 0xff);
+    (sym_idx << 32) | type_
+}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf64_Rel {
+    pub r_offset: Elf64_Addr, /* Location at which to apply the action */
+    pub r_info: Elf64_Xword,  /* Index and type of relocation */
+}
+
+// SAFETY: `Elf64_Rel` is valid for any bit pattern.
+unsafe impl Pod for Elf64_Rel {}
+
+impl ByteSwap for Elf64_Rel {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            r_offset: self.r_offset.swap_bytes(),
+            r_info: self.r_info.swap_bytes(),
+        }
+    }
+}
+
+impl From<&Elf32_Rel> for Elf64_Rel {
+    fn from(other: &Elf32_Rel) -> Self {
+        Self {
+            r_offset: other.r_offset.into(),
+            r_info: widen_r_info(other.r_info),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf32_Rela {
+    pub r_offset: Elf32_Addr,
+    pub r_info: Elf32_Word,
+    pub r_addend: Elf32_Sword,
+}
+
+// SAFETY: `Elf32_Rela` is valid for any bit pattern.
+unsafe impl Pod for Elf32_Rela {}
+
+impl ByteSwap for Elf32_Rela {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            r_offset: self.r_offset.swap_bytes(),
+            r_info: self.r_info.swap_bytes(),
+            r_addend: self.r_addend.swap_bytes(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[repr(C)]
+pub(crate) struct Elf64_Rela {
+    pub r_offset: Elf64_Addr,   /* Location at which to apply the action */
+    pub r_info: Elf64_Xword,    /* Index and type of relocation */
+    pub r_addend: Elf64_Sxword, /* Constant addend used to compute value */
+}
+
+impl Elf64_Rela {
+    /// Extract the index of the symbol the relocation refers to.
+    #[inline]
+    pub fn sym_idx(&self) -> u32 {
+        (self.r_info >> 32) as u32
+    }
+
+    /// Extract the (architecture specific) type of the relocation.
+    #[inline]
+    pub fn type_(&self) -> u32 {
+        (self.r_info & 0xffffffff) as u32
+    }
+}
+
+// SAFETY: `Elf64_Rela` is valid for any bit pattern.
+unsafe impl Pod for Elf64_Rela {}
+
+impl ByteSwap for Elf64_Rela {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            r_offset: self.r_offset.swap_bytes(),
+            r_info: self.r_info.swap_bytes(),
+            r_addend: self.r_addend.swap_bytes(),
+        }
+    }
+}
+
+impl From<&Elf32_Rela> for Elf64_Rela {
+    fn from(other: &Elf32_Rela) -> Self {
+        Self {
+            r_offset: other.r_offset.into(),
+            r_info: widen_r_info(other.r_info),
+            r_addend: other.r_addend.into(),
+        }
+    }
+}
+
+impl From<&Elf64_Rel> for Elf64_Rela {
+    fn from(other: &Elf64_Rel) -> Self {
+        Self {
+            r_offset: other.r_offset,
+            r_info: other.r_info,
+            r_addend: 0,
+        }
+    }
+}
+
 pub(crate) const NT_GNU_BUILD_ID: Elf64_Word = 3;
 
 #[derive(Debug)]
@@ -149,8 +614,40 @@ pub(crate) struct Elf64_Nhdr {
 // SAFETY: `Elf64_Nhdr` is valid for any bit pattern.
 unsafe impl Pod for Elf64_Nhdr {}
 
+impl ByteSwap for Elf64_Nhdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            n_namesz: self.n_namesz.swap_bytes(),
+            n_descsz: self.n_descsz.swap_bytes(),
+            n_type: self.n_type.swap_bytes(),
+        }
+    }
+}
+
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf32_Chdr {
+    pub ch_type: Elf32_Word,
+    pub ch_size: Elf32_Word,
+    pub ch_addralign: Elf32_Word,
+}
+
+// SAFETY: `Elf32_Chdr` is valid for any bit pattern.
+unsafe impl Pod for Elf32_Chdr {}
+
+impl ByteSwap for Elf32_Chdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            ch_type: self.ch_type.swap_bytes(),
+            ch_size: self.ch_size.swap_bytes(),
+            ch_addralign: self.ch_addralign.swap_bytes(),
+        }
+    }
+}
+
+
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub(crate) struct Elf64_Chdr {
     /// Compression format.
@@ -167,6 +664,28 @@ pub(crate) struct Elf64_Chdr {
 // SAFETY: `Elf64_Chdr` is valid for any bit pattern.
 unsafe impl Pod for Elf64_Chdr {}
 
+impl ByteSwap for Elf64_Chdr {
+    fn swap_bytes(&self) -> Self {
+        Self {
+            ch_type: self.ch_type.swap_bytes(),
+            ch_reserved: self.ch_reserved.swap_bytes(),
+            ch_size: self.ch_size.swap_bytes(),
+            ch_addralign: self.ch_addralign.swap_bytes(),
+        }
+    }
+}
+
+impl From<&Elf32_Chdr> for Elf64_Chdr {
+    fn from(other: &Elf32_Chdr) -> Self {
+        Self {
+            ch_type: other.ch_type,
+            ch_reserved: 0,
+            ch_size: other.ch_size.into(),
+            ch_addralign: other.ch_addralign.into(),
+        }
+    }
+}
+
 
 /// zlib/deflate algorithm.
 pub(crate) const ELFCOMPRESS_ZLIB: u32 = 1;
@@ -242,5 +761,77 @@ mod tests {
             st_size: 0,
         };
         assert_ne!(format!("{sym:?}"), "");
+
+        let rela = Elf64_Rela {
+            r_offset: 0,
+            r_info: 0,
+            r_addend: 0,
+        };
+        assert_ne!(format!("{rela:?}"), "");
+    }
+
+    /// Check that we correctly widen 32 bit relocations.
+    #[test]
+    fn relocation_widening() {
+        let rel = Elf32_Rel {
+            r_offset: 0x1234,
+            // Symbol 0x42, type 0x8.
+            r_info: 0x4208,
+        };
+        let rela = Elf64_Rela::from(&Elf64_Rel::from(&rel));
+        assert_eq!(rela.r_offset, 0x1234);
+        assert_eq!(rela.sym_idx(), 0x42);
+        assert_eq!(rela.type_(), 0x8);
+        assert_eq!(rela.r_addend, 0);
+
+        let rela = Elf32_Rela {
+            r_offset: 0x1234,
+            r_info: 0xabcdef01,
+            r_addend: -4,
+        };
+        let rela = Elf64_Rela::from(&rela);
+        assert_eq!(rela.sym_idx(), 0xabcdef);
+        assert_eq!(rela.type_(), 0x01);
+        assert_eq!(rela.r_addend, -4);
+    }
+
+    /// Check that we correctly extract various symbol attributes.
+    #[test]
+    fn symbol_attributes() {
+        let sym = Elf64_Sym {
+            st_name: 0,
+            // STB_WEAK + STT_FUNC
+            st_info: 0x22,
+            // STV_HIDDEN
+            st_other: 0x2,
+            st_shndx: 0,
+            st_value: 0,
+            st_size: 0,
+        };
+        assert_eq!(sym.type_(), STT_FUNC);
+        assert_eq!(sym.binding(), STB_WEAK);
+        assert_eq!(sym.visibility(), 2);
+        assert!(sym.matches(SymType::Function));
+        assert!(!sym.matches(SymType::Variable));
+    }
+
+    /// Check that indirect functions (`STT_GNU_IFUNC`) are treated as
+    /// functions.
+    #[test]
+    fn ifunc_symbol_matching() {
+        let sym = Elf64_Sym {
+            st_name: 0,
+            // STB_GLOBAL + STT_GNU_IFUNC
+            st_info: 0x1a,
+            st_other: 0,
+            st_shndx: 0,
+            st_value: 0,
+            st_size: 0,
+        };
+        assert_eq!(sym.type_(), STT_GNU_IFUNC);
+        assert!(sym.matches(SymType::Function));
+        assert!(sym.matches(SymType::Undefined));
+        assert!(!sym.matches(SymType::Variable));
+        assert_eq!(SymType::try_from(&sym), Ok(SymType::Function));
     }
 }
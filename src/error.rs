@@ -449,6 +449,14 @@ impl Error {
         Self::with_io_error(io::ErrorKind::NotFound, error)
     }
 
+    #[inline]
+    pub(crate) fn with_permission_denied<E>(error: E) -> Self
+    where
+        E: ToString,
+    {
+        Self::with_io_error(io::ErrorKind::PermissionDenied, error)
+    }
+
     #[inline]
     pub(crate) fn with_invalid_data<E>(error: E) -> Self
     where
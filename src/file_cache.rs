@@ -78,10 +78,8 @@ impl<T> Entry<T> {
 /// By default all features are enabled.
 #[derive(Clone, Debug)]
 pub(crate) struct Builder<T> {
-    /// Whether to attempt to gather source code location information.
-    ///
-    /// This setting implies usage of debug symbols and forces the corresponding
-    /// flag to `true`.
+    /// Whether or not to automatically reload files that were updated
+    /// since the last open.
     auto_reload: bool,
     /// Phantom data for our otherwise "unused" generic argument.
     _phantom: PhantomData<T>,
@@ -164,6 +162,11 @@ impl<T> FileCache<T> {
 
         Ok((&entry.file, &entry.value))
     }
+
+    /// Remove all entries from the cache.
+    pub(crate) fn clear(&mut self) {
+        let () = self.cache.clear();
+    }
 }
 
 impl<T> Default for FileCache<T> {
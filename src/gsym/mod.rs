@@ -3,5 +3,7 @@ mod linetab;
 mod parser;
 mod resolver;
 mod types;
+mod writer;
 
 pub(crate) use resolver::GsymResolver;
+pub use writer::GsymWriter;
@@ -117,6 +117,7 @@ impl<'dat> GsymResolver<'dat> {
             file: Cow::Borrowed(OsStr::new(file)),
             line,
             column: None,
+            discriminator: None,
             _non_exhaustive: (),
         };
         Ok(info)
@@ -201,6 +202,7 @@ impl Symbolize for GsymResolver<'_> {
                 addr: sym_addr,
                 size: Some(usize::try_from(info.size).unwrap_or(usize::MAX)),
                 lang,
+                section: None,
                 code_info: None,
                 inlined: Box::new([]),
             };
@@ -304,6 +306,8 @@ impl GsymResolver<'_> {
 
                     let inlined_fn = InlinedFn {
                         name: Cow::Borrowed(name),
+                        addr: None,
+                        offset: None,
                         code_info,
                         _non_exhaustive: (),
                     };
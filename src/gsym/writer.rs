@@ -0,0 +1,504 @@
+//! Writer of the GSYM format.
+//!
+//! The writer produces standalone GSYM data in the layout understood by
+//! [`GsymContext`][super::parser::GsymContext]. Only symbol and line
+//! table information are emitted; inline information is not
+//! supported.
+
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::util::path_to_bytes;
+use crate::Addr;
+use crate::Error;
+use crate::ErrorExt as _;
+use crate::Result;
+
+use super::types::FileInfo;
+use super::types::GSYM_MAGIC;
+use super::types::GSYM_VERSION;
+use super::types::INFO_TYPE_END_OF_LIST;
+use super::types::INFO_TYPE_LINE_TABLE_INFO;
+
+
+/// The size of the GSYM header, in bytes.
+const HEADER_SIZE: usize = 48;
+/// The minimum line delta encodable by a special line table opcode.
+const MIN_LINE_DELTA: i64 = -4;
+/// The maximum line delta encodable by a special line table opcode.
+const MAX_LINE_DELTA: i64 = 10;
+
+// Line table opcodes; see `linetab` module.
+const END_SEQUENCE: u8 = 0x00;
+const SET_FILE: u8 = 0x01;
+const ADVANCE_PC: u8 = 0x02;
+const ADVANCE_LINE: u8 = 0x03;
+const FIRST_SPECIAL: u8 = 0x04;
+
+
+fn write_u64_leb128(data: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            let () = data.push(byte);
+            break
+        }
+        let () = data.push(byte | 0x80);
+    }
+}
+
+fn write_i64_leb128(data: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            let () = data.push(byte);
+            break
+        }
+        let () = data.push(byte | 0x80);
+    }
+}
+
+/// Pad `data` with zero bytes until its length is a multiple of
+/// `align`.
+fn align(data: &mut Vec<u8>, align: usize) {
+    let padding = (align - data.len() % align) % align;
+    let () = data.resize(data.len() + padding, 0);
+}
+
+
+/// A table of NUL terminated strings, deduplicating its contents.
+#[derive(Debug)]
+struct StrTab {
+    data: Vec<u8>,
+    offsets: HashMap<Vec<u8>, u32>,
+}
+
+impl StrTab {
+    fn new() -> Self {
+        let mut slf = Self {
+            data: Vec::new(),
+            offsets: HashMap::new(),
+        };
+        // By convention, offset zero refers to the empty string.
+        let _offset = slf.insert(b"");
+        slf
+    }
+
+    fn insert(&mut self, s: &[u8]) -> u32 {
+        if let Some(offset) = self.offsets.get(s) {
+            return *offset
+        }
+
+        let offset = self.data.len() as u32;
+        let () = self.data.extend_from_slice(s);
+        let () = self.data.push(b'\0');
+        let _prev = self.offsets.insert(s.to_vec(), offset);
+        offset
+    }
+}
+
+
+#[derive(Debug)]
+struct Func {
+    name: String,
+    addr: Addr,
+    size: usize,
+}
+
+#[derive(Debug)]
+struct Line {
+    addr: Addr,
+    path: PathBuf,
+    line: u32,
+}
+
+/// A line table row, with the file already resolved to its index in
+/// the file table.
+#[derive(Clone, Copy, Debug)]
+struct Row {
+    addr: Addr,
+    file_idx: u32,
+    line: u32,
+}
+
+
+/// Encode a line table for a function starting at `func_addr`.
+///
+/// `rows` is expected to be sorted by address and non-empty.
+fn encode_line_table(data: &mut Vec<u8>, func_addr: Addr, rows: &[Row]) {
+    debug_assert!(!rows.is_empty());
+
+    let first_line = rows.first().map(|row| row.line).unwrap_or(0);
+    let () = write_i64_leb128(data, MIN_LINE_DELTA);
+    let () = write_i64_leb128(data, MAX_LINE_DELTA);
+    let () = write_u64_leb128(data, first_line.into());
+
+    // The initial state of the line table state machine, as set up by
+    // the reader.
+    let mut prev = Row {
+        addr: func_addr,
+        file_idx: 1,
+        line: first_line,
+    };
+
+    for row in rows {
+        if row.file_idx != prev.file_idx {
+            let () = data.push(SET_FILE);
+            let () = write_u64_leb128(data, row.file_idx.into());
+        }
+
+        let addr_delta = row.addr - prev.addr;
+        let line_delta = i64::from(row.line) - i64::from(prev.line);
+        let range = (MAX_LINE_DELTA - MIN_LINE_DELTA + 1) as u64;
+        let special = (MIN_LINE_DELTA..=MAX_LINE_DELTA)
+            .contains(&line_delta)
+            .then(|| {
+                addr_delta
+                    .checked_mul(range)?
+                    .checked_add((line_delta - MIN_LINE_DELTA) as u64)?
+                    .checked_add(FIRST_SPECIAL.into())
+            })
+            .flatten()
+            .and_then(|op| u8::try_from(op).ok());
+
+        if let Some(op) = special {
+            let () = data.push(op);
+        } else {
+            if line_delta != 0 {
+                let () = data.push(ADVANCE_LINE);
+                let () = write_i64_leb128(data, line_delta);
+            }
+            let () = data.push(ADVANCE_PC);
+            let () = write_u64_leb128(data, addr_delta);
+        }
+        prev = *row;
+    }
+
+    let () = data.push(END_SEQUENCE);
+}
+
+
+/// A writer for creating GSYM data.
+///
+/// Function symbols and line information are added individually and
+/// serialized into a standalone GSYM blob using [`GsymWriter::write`].
+/// The resulting data can be symbolized using
+/// [`GsymResolver`][crate::helper::GsymResolver] or the
+/// [`Gsym`][crate::symbolize::source::Gsym] source.
+#[derive(Debug, Default)]
+pub struct GsymWriter {
+    funcs: Vec<Func>,
+    lines: Vec<Line>,
+}
+
+impl GsymWriter {
+    /// Create a new, empty `GsymWriter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a function symbol.
+    ///
+    /// A `size` of zero indicates that the size is unknown. If multiple
+    /// symbols are added for the same address, only the first one is
+    /// retained.
+    pub fn add_sym(&mut self, name: &str, addr: Addr, size: usize) -> &mut Self {
+        let func = Func {
+            name: name.to_string(),
+            addr,
+            size,
+        };
+        let () = self.funcs.push(func);
+        self
+    }
+
+    /// Add line information for the given address.
+    ///
+    /// Line information is attributed to the function symbol covering
+    /// `addr` and ignored if there is none.
+    pub fn add_line<P>(&mut self, addr: Addr, path: P, line: u32) -> &mut Self
+    where
+        P: AsRef<Path>,
+    {
+        let line = Line {
+            addr,
+            path: path.as_ref().to_path_buf(),
+            line,
+        };
+        let () = self.lines.push(line);
+        self
+    }
+
+    /// Serialize all added information into GSYM data.
+    pub fn write(&self) -> Result<Vec<u8>> {
+        let mut funcs = self.funcs.iter().collect::<Vec<_>>();
+        let () = funcs.sort_by_key(|func| func.addr);
+        let () = funcs.dedup_by_key(|func| func.addr);
+
+        let mut lines = self.lines.iter().collect::<Vec<_>>();
+        let () = lines.sort_by_key(|line| line.addr);
+        let () = lines.dedup_by_key(|line| line.addr);
+
+        let num_addrs = u32::try_from(funcs.len())
+            .map_err(Error::with_invalid_input)
+            .context("too many symbols for GSYM")?;
+        let base_addr = funcs.first().map(|func| func.addr).unwrap_or(0);
+        let max_offset = funcs.last().map(|func| func.addr - base_addr).unwrap_or(0);
+        let addr_off_size = if max_offset <= u64::from(u8::MAX) {
+            1
+        } else if max_offset <= u64::from(u16::MAX) {
+            2
+        } else if max_offset <= u64::from(u32::MAX) {
+            4
+        } else {
+            8
+        };
+
+        let mut strtab = StrTab::new();
+        // By convention, file index zero refers to an empty entry.
+        let mut files = vec![(0, 0)];
+        let mut file_idxs = HashMap::<&Path, u32>::new();
+
+        // Encode all function information up front, as it determines
+        // the contents of the string and file tables.
+        let mut infos = Vec::with_capacity(funcs.len());
+        let mut lines = lines.as_slice();
+        for (i, func) in funcs.iter().enumerate() {
+            let size = u32::try_from(func.size)
+                .map_err(Error::with_invalid_input)
+                .with_context(|| format!("size of symbol `{}` is too large", func.name))?;
+            let name = strtab.insert(func.name.as_bytes());
+
+            // Functions of unknown size extend up to the next one.
+            let end = if size > 0 {
+                func.addr.saturating_add(size.into())
+            } else {
+                funcs.get(i + 1).map(|next| next.addr).unwrap_or(Addr::MAX)
+            };
+
+            let start = lines.partition_point(|line| line.addr < func.addr);
+            let count = lines[start..].partition_point(|line| line.addr < end);
+            let mut rows = Vec::with_capacity(count);
+            for line in &lines[start..start + count] {
+                let file_idx = if let Some(idx) = file_idxs.get(line.path.as_path()) {
+                    *idx
+                } else {
+                    let dir = line.path.parent().unwrap_or_else(|| Path::new(""));
+                    let file = line
+                        .path
+                        .file_name()
+                        .map(Path::new)
+                        .unwrap_or_else(|| Path::new(""));
+                    let dir = strtab.insert(path_to_bytes(dir)?);
+                    let file = strtab.insert(path_to_bytes(file)?);
+                    let idx = files.len() as u32;
+                    let () = files.push((dir, file));
+                    let _prev = file_idxs.insert(line.path.as_path(), idx);
+                    idx
+                };
+                let row = Row {
+                    addr: line.addr,
+                    file_idx,
+                    line: line.line,
+                };
+                let () = rows.push(row);
+            }
+            lines = &lines[start + count..];
+
+            let mut info = Vec::new();
+            let () = info.extend_from_slice(&size.to_ne_bytes());
+            let () = info.extend_from_slice(&name.to_ne_bytes());
+            if !rows.is_empty() {
+                let mut linetab = Vec::new();
+                let () = encode_line_table(&mut linetab, func.addr, &rows);
+                let () = info.extend_from_slice(&INFO_TYPE_LINE_TABLE_INFO.to_ne_bytes());
+                let () = info.extend_from_slice(&(linetab.len() as u32).to_ne_bytes());
+                let () = info.extend_from_slice(&linetab);
+            }
+            let () = info.extend_from_slice(&INFO_TYPE_END_OF_LIST.to_ne_bytes());
+            let () = info.extend_from_slice(&0u32.to_ne_bytes());
+            let () = infos.push(info);
+        }
+
+        let mut data = Vec::new();
+        // The string table offset and size are patched in below, once
+        // known.
+        let () = data.extend_from_slice(&GSYM_MAGIC.to_ne_bytes());
+        let () = data.extend_from_slice(&GSYM_VERSION.to_ne_bytes());
+        let () = data.push(addr_off_size);
+        // We don't emit a UUID.
+        let () = data.push(0);
+        let () = data.extend_from_slice(&base_addr.to_ne_bytes());
+        let () = data.extend_from_slice(&num_addrs.to_ne_bytes());
+        let strtab_offset_pos = data.len();
+        let () = data.extend_from_slice(&0u32.to_ne_bytes());
+        let () = data.extend_from_slice(&(strtab.data.len() as u32).to_ne_bytes());
+        let () = data.extend_from_slice(&[0; 20]);
+        debug_assert_eq!(data.len(), HEADER_SIZE);
+
+        for func in &funcs {
+            let offset = func.addr - base_addr;
+            let () = match addr_off_size {
+                1 => data.push(offset as u8),
+                2 => data.extend_from_slice(&(offset as u16).to_ne_bytes()),
+                4 => data.extend_from_slice(&(offset as u32).to_ne_bytes()),
+                _ => data.extend_from_slice(&offset.to_ne_bytes()),
+            };
+        }
+        let () = align(&mut data, size_of::<u32>());
+
+        // Address information is laid out after all tables, with each
+        // entry being aligned to four bytes.
+        let addr_info_off_pos = data.len();
+        let () = data.resize(addr_info_off_pos + funcs.len() * size_of::<u32>(), 0);
+
+        let () = data.extend_from_slice(&(files.len() as u32).to_ne_bytes());
+        let () = align(&mut data, size_of::<FileInfo>());
+        for (dir, file) in files {
+            let () = data.extend_from_slice(&dir.to_ne_bytes());
+            let () = data.extend_from_slice(&file.to_ne_bytes());
+        }
+
+        let strtab_offset = u32::try_from(data.len())
+            .map_err(Error::with_invalid_input)
+            .context("GSYM data is too large")?;
+        let () = data[strtab_offset_pos..strtab_offset_pos + size_of::<u32>()]
+            .copy_from_slice(&strtab_offset.to_ne_bytes());
+        let () = data.extend_from_slice(&strtab.data);
+
+        for (i, info) in infos.iter().enumerate() {
+            let () = align(&mut data, size_of::<u32>());
+            let offset = u32::try_from(data.len())
+                .map_err(Error::with_invalid_input)
+                .context("GSYM data is too large")?;
+            let pos = addr_info_off_pos + i * size_of::<u32>();
+            let () = data[pos..pos + size_of::<u32>()].copy_from_slice(&offset.to_ne_bytes());
+            let () = data.extend_from_slice(info);
+        }
+
+        Ok(data)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::symbolize::FindSymOpts;
+    use crate::symbolize::Symbolize as _;
+    use crate::util::ReadRaw as _;
+
+    use super::super::GsymResolver;
+
+
+    /// Check that LEB128 encoded values can be read back.
+    #[test]
+    fn leb128_writing() {
+        let values = [0, 1, 63, 64, 127, 128, 255, 624485, u64::MAX];
+        for value in values {
+            let mut data = Vec::new();
+            let () = write_u64_leb128(&mut data, value);
+            assert_eq!(data.as_slice().read_u64_leb128(), Some(value));
+        }
+
+        let values = [0, 1, -1, 63, -64, 64, -65, -123456, i64::MIN, i64::MAX];
+        for value in values {
+            let mut data = Vec::new();
+            let () = write_i64_leb128(&mut data, value);
+            assert_eq!(data.as_slice().read_i64_leb128(), Some(value));
+        }
+    }
+
+    /// Check that we can symbolize addresses in written GSYM data.
+    #[test]
+    fn gsym_round_trip() {
+        let mut writer = GsymWriter::new();
+        let _writer = writer
+            .add_sym("main", 0x2000, 0x20)
+            .add_sym("factorial", 0x1000, 0x100)
+            .add_sym("unsized", 0x3000, 0)
+            .add_line(0x1000, "/src/factorial.c", 10)
+            .add_line(0x1004, "/src/factorial.c", 12)
+            // Requires a separate line advancement.
+            .add_line(0x1010, "/src/factorial.c", 3)
+            // Requires a separate program counter advancement.
+            .add_line(0x10f0, "/src/factorial.c", 4)
+            .add_line(0x2008, "/src/main.c", 42)
+            .add_line(0x2010, "/src/inc/util.h", 7);
+        let data = writer.write().unwrap();
+
+        let resolver = GsymResolver::with_data(&data).unwrap();
+        let check = |addr, name, sym_addr, file: Option<&str>, line| {
+            let sym = resolver
+                .find_sym(addr, &FindSymOpts::CodeInfo)
+                .unwrap()
+                .unwrap();
+            assert_eq!(sym.name, name);
+            assert_eq!(sym.addr, sym_addr);
+
+            let code_info = sym.code_info.map(|info| {
+                let path = info.to_path();
+                (path.to_str().unwrap().to_string(), info.line)
+            });
+            assert_eq!(
+                code_info,
+                file.map(|file| (file.to_string(), Some(line))),
+                "{addr:#x}"
+            );
+        };
+
+        let () = check(0x1000, "factorial", 0x1000, Some("/src/factorial.c"), 10);
+        let () = check(0x1006, "factorial", 0x1000, Some("/src/factorial.c"), 12);
+        let () = check(0x1010, "factorial", 0x1000, Some("/src/factorial.c"), 3);
+        let () = check(0x10ff, "factorial", 0x1000, Some("/src/factorial.c"), 4);
+        let () = check(0x2004, "main", 0x2000, None, 0);
+        let () = check(0x2008, "main", 0x2000, Some("/src/main.c"), 42);
+        let () = check(0x201f, "main", 0x2000, Some("/src/inc/util.h"), 7);
+        let () = check(0x3100, "unsized", 0x3000, None, 0);
+
+        let result = resolver.find_sym(0x1100, &FindSymOpts::Basic).unwrap();
+        assert!(result.is_err());
+        let result = resolver.find_sym(0xfff, &FindSymOpts::Basic).unwrap();
+        assert!(result.is_err());
+    }
+
+    /// Check that we pick an address table entry size fitting all
+    /// symbol addresses.
+    #[test]
+    fn addr_off_sizes() {
+        for (offset, addr_off_size) in [(0xff, 1), (0x100, 2), (0x10000, 4), (0x1_0000_0000, 8)] {
+            let base = 0xffff_0000_0000;
+            let mut writer = GsymWriter::new();
+            let _writer = writer
+                .add_sym("first", base, 1)
+                .add_sym("second", base + offset, 1);
+            let data = writer.write().unwrap();
+            assert_eq!(data[6], addr_off_size);
+
+            let resolver = GsymResolver::with_data(&data).unwrap();
+            for (addr, name) in [(base, "first"), (base + offset, "second")] {
+                let sym = resolver
+                    .find_sym(addr, &FindSymOpts::Basic)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(sym.name, name);
+                assert_eq!(sym.addr, addr);
+            }
+        }
+    }
+
+    /// Make sure that we can write GSYM data without any symbols.
+    #[test]
+    fn empty_writing() {
+        let data = GsymWriter::new().write().unwrap();
+        let resolver = GsymResolver::with_data(&data).unwrap();
+        let result = resolver.find_sym(0x1000, &FindSymOpts::Basic).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -6,10 +6,12 @@ use std::hash::Hash;
 use crate::Result;
 
 
-/// An insert-only map.
+/// A map allowing for insertion behind an immutable interface.
 ///
-/// This map allows only for insertion, but not removal of values. It
-/// does so behind an immutable interface.
+/// Through a shared reference, values can only be inserted but never
+/// removed, meaning that references handed out stay valid. Removal of
+/// all values is possible via [`clear`][InsertMap::clear], which
+/// requires exclusive access.
 #[derive(Debug)]
 pub(crate) struct InsertMap<K, V> {
     /// A proxy member used for making sure that we do not borrow `map` mutably
@@ -56,6 +58,39 @@ impl<K, V> InsertMap<K, V> {
             }
         }
     }
+
+    /// Remove all key-value pairs from the map.
+    ///
+    /// Exclusive access guarantees that no references to any of the
+    /// values are still being held.
+    pub(crate) fn clear(&mut self) {
+        let () = self.map.get_mut().clear();
+    }
+}
+
+impl InsertMap<Box<str>, ()> {
+    /// Retrieve the contents of the key equal to `key`, inserting an
+    /// owned copy of it if not yet present.
+    ///
+    /// This method effectively allows for using the map as a string
+    /// interner.
+    pub(crate) fn intern(&self, key: &str) -> &str {
+        let _borrow = self.refcell.borrow_mut();
+        // SAFETY: We are sure to not borrow mutably twice because the `_borrow`
+        //         guard protects us.
+        let map = unsafe { self.map.as_ptr().as_mut() }.unwrap();
+        if !map.contains_key(key) {
+            let _prev = map.insert(Box::from(key), ());
+        }
+        // SANITY: The key is guaranteed to be present at this point.
+        let (key, ()) = map.get_key_value(key).unwrap();
+        let key = key.as_ref() as *const str;
+        // SAFETY: The contents of a `Box<str>` live on the heap and are
+        //         not moved when the map reallocates. They are only
+        //         ever freed once the map is cleared or dropped, both
+        //         of which require exclusive access.
+        unsafe { &*key }
+    }
 }
 
 impl<K, V> Default for InsertMap<K, V> {
@@ -70,6 +105,8 @@ impl<K, V> Default for InsertMap<K, V> {
 mod tests {
     use super::*;
 
+    use std::ptr;
+
     use test_tag::tag;
 
     use crate::Error;
@@ -99,6 +136,35 @@ mod tests {
         assert_eq!(s, &"31 wins");
     }
 
+    /// Check that we can clear an `InsertMap`.
+    #[tag(miri)]
+    #[test]
+    fn clearing() {
+        let mut map = InsertMap::<usize, &'static str>::new();
+        let _s = map.get_or_try_insert(42, || Ok("foobar")).unwrap();
+
+        let () = map.clear();
+        let s = map.get_or_try_insert(42, || Ok("baz")).unwrap();
+        assert_eq!(s, &"baz");
+    }
+
+    /// Check that interning keys works as it should.
+    #[tag(miri)]
+    #[test]
+    fn interning() {
+        let map = InsertMap::<Box<str>, ()>::new();
+        let foo = map.intern("foo");
+        assert_eq!(foo, "foo");
+
+        // Insert a bunch of other keys to trigger reallocation.
+        for i in 0..128 {
+            let _s = map.intern(i.to_string().as_str());
+        }
+
+        let foo2 = map.intern(String::from("foo").as_str());
+        assert_eq!(foo, "foo");
+        assert!(ptr::eq(foo, foo2));
+    }
 
     /// Make sure that `InsertMap` does not allow for recursive
     /// access as part of initialization.
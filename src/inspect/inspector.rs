@@ -10,6 +10,7 @@ use crate::breakpad::BreakpadResolver;
 use crate::elf::ElfResolverData;
 use crate::elf::DEFAULT_DEBUG_DIRS;
 use crate::file_cache::FileCache;
+use crate::symbolize::SymSource;
 use crate::Result;
 
 #[cfg(feature = "breakpad")]
@@ -112,6 +113,8 @@ impl Inspector {
                     } else {
                         None
                     },
+                    SymSource::default(),
+                    false,
                 )?;
                 resolver.deref() as &dyn Inspect
             }
@@ -194,6 +197,8 @@ impl Inspector {
                         } else {
                             None
                         },
+                        SymSource::default(),
+                        false,
                     )?;
                     (resolver.deref() as &dyn Inspect, opts)
                 }
@@ -1,31 +1,218 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::fs::read_dir;
+use std::fs::read_to_string;
+use std::ops::Range;
 use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use crate::elf::ElfResolver;
 use crate::ksym::KSymResolver;
+use crate::log;
+use crate::symbolize::CodeInfo;
 use crate::symbolize::FindSymOpts;
 use crate::symbolize::Reason;
 use crate::symbolize::ResolvedSym;
 use crate::symbolize::Symbolize;
 use crate::Addr;
 use crate::Error;
+use crate::ErrorExt as _;
+use crate::IntoError as _;
 use crate::Result;
 
 
+/// The directory containing information about loaded kernel modules.
+pub(crate) const SYS_MODULE_DIR: &str = "/sys/module";
+/// The directory containing the installed kernel modules of all
+/// kernel releases.
+pub(crate) const LIB_MODULES_DIR: &str = "/lib/modules";
+
+
+/// Find the object files of all kernel modules listed in the
+/// `modules.dep` file in `dir`, indexed by module name.
+///
+/// Only uncompressed (`.ko`) object files are reported.
+pub(crate) fn find_module_files(dir: &Path) -> Result<HashMap<String, PathBuf>> {
+    let path = dir.join("modules.dep");
+    let content =
+        read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+
+    let files = content
+        .lines()
+        .filter_map(|line| {
+            let (file, _deps) = line.split_once(':')?;
+            let name = Path::new(file).file_name()?.to_str()?;
+            let name = name.strip_suffix(".ko")?;
+            // Dashes and underscores are interchangeable in module
+            // names, but only the latter are used at runtime.
+            Some((name.replace('-', "_"), dir.join(file)))
+        })
+        .collect();
+    Ok(files)
+}
+
+
+/// A section of a loaded kernel module.
+#[derive(Debug)]
+struct ModuleSection {
+    /// The range of runtime addresses covered by the section.
+    range: Range<Addr>,
+    /// The address of the section in the module's object file.
+    file_addr: Addr,
+}
+
+
+/// A loaded kernel module, backed by its object file.
+pub(crate) struct KernelModule {
+    /// The name of the module.
+    name: String,
+    /// The module's loaded sections, in address order.
+    sections: Box<[ModuleSection]>,
+    /// The resolver for the module's object file.
+    resolver: Rc<ElfResolver>,
+}
+
+impl KernelModule {
+    /// Create a `KernelModule` for the module `name`, reading the
+    /// runtime addresses of its sections from `sections_dir` (i.e.,
+    /// `/sys/module/<name>/sections/`).
+    pub(crate) fn new(
+        name: String,
+        sections_dir: &Path,
+        resolver: Rc<ElfResolver>,
+    ) -> Result<Self> {
+        let parser = resolver.parser();
+        let shdrs = parser.section_headers()?;
+
+        let mut sections = Vec::new();
+        for entry in read_dir(sections_dir)? {
+            let entry = entry?;
+            let section = entry.file_name();
+            let idx = match section.to_str() {
+                Some(section) => parser.find_section(section)?,
+                None => None,
+            };
+            let shdr = match idx.and_then(|idx| shdrs.get(idx)) {
+                Some(shdr) if shdr.sh_size != 0 => shdr,
+                _ => continue,
+            };
+
+            let path = entry.path();
+            let content = read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let addr = content.trim();
+            let addr = Addr::from_str_radix(addr.strip_prefix("0x").unwrap_or(addr), 16)
+                .ok()
+                .ok_or_invalid_data(|| {
+                    format!("encountered invalid section address in {}", path.display())
+                })?;
+            // Addresses are reported as zero if hidden from us (e.g.,
+            // because we lack privileges).
+            if addr == 0 {
+                continue
+            }
+
+            let () = sections.push(ModuleSection {
+                range: addr..addr.saturating_add(shdr.sh_size),
+                file_addr: shdr.sh_addr,
+            });
+        }
+        let () = sections.sort_by_key(|section| section.range.start);
+
+        let slf = Self {
+            name,
+            sections: sections.into_boxed_slice(),
+            resolver,
+        };
+        Ok(slf)
+    }
+
+    /// Translate the runtime address `addr` into an address inside the
+    /// module's object file, if it belongs to the module.
+    fn translate(&self, addr: Addr) -> Option<Addr> {
+        let idx = self
+            .sections
+            .partition_point(|section| section.range.start <= addr);
+        let section = self.sections.get(idx.checked_sub(1)?)?;
+        section
+            .range
+            .contains(&addr)
+            .then(|| addr - section.range.start + section.file_addr)
+    }
+}
+
+impl Debug for KernelModule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct(stringify!(KernelModule))
+            .field("name", &self.name)
+            .field("sections", &self.sections)
+            .finish()
+    }
+}
+
+
+/// Load all kernel modules present in `sys_dir` (typically
+/// `/sys/module`) for which an object file is listed in `files`.
+///
+/// Modules whose object file cannot be used are skipped.
+pub(crate) fn load_modules<F>(
+    sys_dir: &Path,
+    files: &HashMap<String, PathBuf>,
+    mut create_resolver: F,
+) -> Result<Vec<KernelModule>>
+where
+    F: FnMut(&Path) -> Result<Rc<ElfResolver>>,
+{
+    let mut modules = Vec::new();
+    for entry in read_dir(sys_dir)? {
+        let entry = entry?;
+        let sections_dir = entry.path().join("sections");
+        // Modules built into the kernel don't have any sections.
+        if !sections_dir.exists() {
+            continue
+        }
+
+        let name = entry.file_name();
+        let file = match name.to_str().and_then(|name| files.get(name)) {
+            Some(file) => file,
+            None => continue,
+        };
+
+        let result = create_resolver(file).and_then(|resolver| {
+            KernelModule::new(name.to_string_lossy().into_owned(), &sections_dir, resolver)
+        });
+        match result {
+            Ok(module) => modules.push(module),
+            Err(err) => {
+                log::warn!(
+                    "failed to load kernel module {}: {err}; ignoring...",
+                    file.display()
+                );
+            }
+        }
+    }
+    Ok(modules)
+}
+
+
 pub(crate) struct KernelResolver {
     pub ksym_resolver: Option<Rc<KSymResolver>>,
     pub elf_resolver: Option<Rc<ElfResolver>>,
+    pub modules: Vec<KernelModule>,
 }
 
 impl KernelResolver {
     pub(crate) fn new(
         ksym_resolver: Option<Rc<KSymResolver>>,
         elf_resolver: Option<Rc<ElfResolver>>,
+        modules: Vec<KernelModule>,
     ) -> Result<KernelResolver> {
-        if ksym_resolver.is_none() && elf_resolver.is_none() {
+        if ksym_resolver.is_none() && elf_resolver.is_none() && modules.is_empty() {
             return Err(Error::with_not_found(
                     "failed to create kernel resolver: neither ksym resolver nor kernel image ELF resolver are present",
             ))
@@ -34,12 +221,51 @@ impl KernelResolver {
         Ok(KernelResolver {
             ksym_resolver,
             elf_resolver,
+            modules,
         })
     }
+
+    /// Find the symbol for `addr` in the kernel module containing it,
+    /// if any.
+    fn find_module_sym(&self, addr: Addr, opts: &FindSymOpts) -> Result<Option<ResolvedSym<'_>>> {
+        for module in &self.modules {
+            if let Some(file_addr) = module.translate(addr) {
+                let mut sym = match module.resolver.find_sym(file_addr, opts)? {
+                    Ok(sym) => sym,
+                    Err(_reason) => return Ok(None),
+                };
+                // Symbols are reported relative to the module's object
+                // file, but we work with runtime addresses.
+                sym.addr = addr - (file_addr - sym.addr);
+                // Similar to kallsyms, report the module as the source
+                // file in the absence of better information.
+                if sym.code_info.is_none() {
+                    sym.code_info = Some(CodeInfo {
+                        dir: None,
+                        file: Cow::Borrowed(OsStr::new(&module.name)),
+                        line: None,
+                        column: None,
+                        discriminator: None,
+                        _non_exhaustive: (),
+                    });
+                }
+                return Ok(Some(sym))
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl Symbolize for KernelResolver {
     fn find_sym(&self, addr: Addr, opts: &FindSymOpts) -> Result<Result<ResolvedSym<'_>, Reason>> {
+        if let Some(sym) = self.find_module_sym(addr, opts)? {
+            return Ok(Ok(sym))
+        }
+
+        if self.ksym_resolver.is_none() && self.elf_resolver.is_none() {
+            return Ok(Err(Reason::UnknownAddr))
+        }
+
         // TODO: If an `ElfResolver` is available we probably should give
         //       preference to it, if for no other reason than the fact that it
         //       may report source code location information.
@@ -69,3 +295,68 @@ impl Debug for KernelResolver {
         )
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::copy;
+    use std::fs::create_dir_all;
+    use std::fs::write;
+
+    use tempfile::tempdir;
+
+
+    /// Check that we can symbolize addresses inside a loaded kernel
+    /// module.
+    #[test]
+    fn module_symbolization() {
+        let dir = tempdir().unwrap();
+        let modules_dir = dir.path().join("modules");
+        let sys_dir = dir.path().join("sys");
+
+        let obj = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-so.o");
+        let () = create_dir_all(modules_dir.join("kernel")).unwrap();
+        let _cnt = copy(obj, modules_dir.join("kernel").join("test-so.ko")).unwrap();
+        let () = write(modules_dir.join("modules.dep"), "kernel/test-so.ko:\n").unwrap();
+
+        let sections_dir = sys_dir.join("test_so").join("sections");
+        let () = create_dir_all(&sections_dir).unwrap();
+        let () = write(
+            sections_dir.join(".text.the_answer"),
+            "0xffffffffc0001000\n",
+        )
+        .unwrap();
+        // A built-in module, without any sections.
+        let () = create_dir_all(sys_dir.join("builtin")).unwrap();
+
+        let files = find_module_files(&modules_dir).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files.contains_key("test_so"));
+
+        let modules = load_modules(&sys_dir, &files, |path| {
+            ElfResolver::open(path).map(Rc::new)
+        })
+        .unwrap();
+        assert_eq!(modules.len(), 1);
+
+        let resolver = KernelResolver::new(None, None, modules).unwrap();
+        let sym = resolver
+            .find_sym(0xffffffffc0001001, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "the_answer");
+        assert_eq!(sym.addr, 0xffffffffc0001000);
+        let code_info = sym.code_info.unwrap();
+        assert_eq!(code_info.file, OsStr::new("test_so"));
+
+        let reason = resolver
+            .find_sym(0xffffffffc0000fff, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(reason, Reason::UnknownAddr);
+    }
+}
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
@@ -12,6 +13,7 @@ use crate::inspect::FindAddrOpts;
 use crate::inspect::Inspect;
 use crate::inspect::SymInfo;
 use crate::once::OnceCell;
+use crate::symbolize::CodeInfo;
 use crate::symbolize::FindSymOpts;
 use crate::symbolize::Reason;
 use crate::symbolize::ResolvedSym;
@@ -19,21 +21,27 @@ use crate::symbolize::SrcLang;
 use crate::symbolize::Symbolize;
 use crate::util::find_match_or_lower_bound_by_key;
 use crate::Addr;
+use crate::Error;
 use crate::Result;
 use crate::SymType;
 
 pub const KALLSYMS: &str = "/proc/kallsyms";
+/// The pseudo module name that kallsyms reports for JITed BPF
+/// programs.
+const BPF_MODULE: &str = "bpf";
 const DFL_KSYM_CAP: usize = 200000;
 
 #[derive(Debug)]
 pub struct Ksym {
     pub addr: Addr,
     pub name: String,
+    /// The name of the kernel module the symbol belongs to, if any.
+    pub module: Option<String>,
 }
 
 impl<'ksym> From<&'ksym Ksym> for ResolvedSym<'ksym> {
     fn from(other: &'ksym Ksym) -> Self {
-        let Ksym { name, addr } = other;
+        let Ksym { name, addr, module } = other;
         ResolvedSym {
             name,
             addr: *addr,
@@ -42,8 +50,18 @@ impl<'ksym> From<&'ksym Ksym> for ResolvedSym<'ksym> {
             // Kernel symbols don't carry any source code language
             // information.
             lang: SrcLang::Unknown,
+            section: None,
             // kallsyms doesn't have source code location information.
-            code_info: None,
+            // The best we can do is report the module a symbol belongs
+            // to, if any.
+            code_info: module.as_ref().map(|module| CodeInfo {
+                dir: None,
+                file: Cow::Borrowed(OsStr::new(module)),
+                line: None,
+                column: None,
+                discriminator: None,
+                _non_exhaustive: (),
+            }),
             inlined: Box::new([]),
         }
     }
@@ -51,7 +69,11 @@ impl<'ksym> From<&'ksym Ksym> for ResolvedSym<'ksym> {
 
 impl<'ksym> From<&'ksym Ksym> for SymInfo<'ksym> {
     fn from(other: &'ksym Ksym) -> Self {
-        let Ksym { name, addr } = other;
+        let Ksym {
+            name,
+            addr,
+            module: _,
+        } = other;
         SymInfo {
             name: Cow::Borrowed(name),
             addr: *addr,
@@ -68,6 +90,10 @@ impl<'ksym> From<&'ksym Ksym> for SymInfo<'ksym> {
 ///
 /// The users should provide the path of kallsyms, so you can provide
 /// a copy from other devices.
+///
+/// Symbols of loadable kernel modules are reported with the module
+/// name as the file of their [`CodeInfo`]. JITed BPF programs are
+/// reported under their kallsyms name (`bpf_prog_<tag>_<name>`).
 pub struct KSymResolver {
     /// An index over `syms` that is sorted by name.
     by_name_idx: OnceCell<Box<[usize]>>,
@@ -81,6 +107,7 @@ impl KSymResolver {
         let mut reader = BufReader::new(f);
         let mut line = String::new();
         let mut syms = Vec::with_capacity(DFL_KSYM_CAP);
+        let mut zero_addrs = 0;
 
         loop {
             let sz = reader.read_line(&mut line)?;
@@ -94,16 +121,39 @@ impl KSymResolver {
             let (addr, _symbol, func) = (tokens[0], tokens[1], tokens[2]);
             if let Ok(addr) = Addr::from_str_radix(addr, 16) {
                 if addr == 0 {
+                    zero_addrs += 1;
                     line.truncate(0);
                     continue
                 }
                 let name = String::from(func);
-                syms.push(Ksym { addr, name });
+                // Symbols of loadable modules are suffixed with the
+                // module name in brackets, e.g., `[ext4]`. JITed BPF
+                // programs are listed as part of the `[bpf]` pseudo
+                // module, which does not correspond to an actual
+                // module and so we don't report it.
+                let module = tokens
+                    .get(3)
+                    .and_then(|module| module.strip_prefix('['))
+                    .and_then(|module| module.strip_suffix(']'))
+                    .filter(|module| *module != BPF_MODULE)
+                    .map(String::from);
+                syms.push(Ksym { addr, name, module });
             }
 
             line.truncate(0);
         }
 
+        // With `kptr_restrict` in effect, the kernel reports all
+        // addresses as zero to unprivileged users. There is nothing we
+        // could symbolize with, so make this condition explicit instead
+        // of reporting each and every address as unknown.
+        if syms.is_empty() && zero_addrs > 0 {
+            return Err(Error::with_permission_denied(format!(
+                "all addresses in {} are zero; access is likely restricted by kernel.kptr_restrict",
+                filename.display()
+            )))
+        }
+
         syms.sort_by(|a, b| a.addr.cmp(&b.addr));
 
         let slf = Self {
@@ -204,6 +254,9 @@ impl Debug for KSymResolver {
 mod tests {
     use super::*;
 
+    use std::io::Write as _;
+
+    use tempfile::NamedTempFile;
     use test_log::test;
     use test_tag::tag;
 
@@ -224,6 +277,7 @@ mod tests {
         let ksym = Ksym {
             addr: 0x1337,
             name: "3l33t".to_string(),
+            module: None,
         };
         assert_ne!(format!("{ksym:?}"), "");
     }
@@ -286,6 +340,88 @@ mod tests {
         ensure_addr_for_name(found.name, addr);
     }
 
+    /// Check that we parse kernel module names from kallsyms.
+    #[test]
+    fn module_parsing() {
+        let mut file = NamedTempFile::new().unwrap();
+        let () = file
+            .write_all(
+                b"ffffffff81000000 T _text
+ffffffff81001000 t do_one_initcall
+ffffffffc0a00000 t ext4_fill_super	[ext4]
+",
+            )
+            .unwrap();
+        let resolver = KSymResolver::load_file_name(file.path().to_path_buf()).unwrap();
+        assert_eq!(resolver.syms.len(), 3);
+
+        let sym = resolver
+            .find_sym(0xffffffff81001010, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "do_one_initcall");
+        assert_eq!(sym.code_info, None);
+
+        let sym = resolver
+            .find_sym(0xffffffffc0a00042, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "ext4_fill_super");
+        assert_eq!(sym.addr, 0xffffffffc0a00000);
+        let code_info = sym.code_info.unwrap();
+        assert_eq!(code_info.file, OsStr::new("ext4"));
+        assert_eq!(code_info.dir, None);
+        assert_eq!(code_info.line, None);
+    }
+
+    /// Check that addresses inside JITed BPF programs resolve to the
+    /// corresponding kallsyms entries.
+    #[test]
+    fn bpf_program_lookup() {
+        let mut file = NamedTempFile::new().unwrap();
+        let () = file
+            .write_all(
+                b"ffffffff81000000 T _text
+ffffffffc0a00000 t ext4_fill_super\t[ext4]
+ffffffffc0b01000 t bpf_prog_6deef7357e7b4530_sd_fw_ingress\t[bpf]
+ffffffffc0b03000 t bpf_prog_a1b2c3d4e5f60718_trace_enter\t[bpf]
+",
+            )
+            .unwrap();
+        let resolver = KSymResolver::load_file_name(file.path().to_path_buf()).unwrap();
+
+        let sym = resolver
+            .find_sym(0xffffffffc0b01234, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "bpf_prog_6deef7357e7b4530_sd_fw_ingress");
+        assert_eq!(sym.addr, 0xffffffffc0b01000);
+        assert_eq!(sym.code_info, None);
+
+        let sym = resolver
+            .find_sym(0xffffffffc0b03010, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "bpf_prog_a1b2c3d4e5f60718_trace_enter");
+    }
+
+    /// Check that we report an error when all kallsyms addresses are
+    /// zero, as is the case with `kptr_restrict` in effect.
+    #[test]
+    fn restricted_kallsyms() {
+        let mut file = NamedTempFile::new().unwrap();
+        let () = file
+            .write_all(
+                b"0000000000000000 T _text
+0000000000000000 t do_one_initcall
+0000000000000000 t ext4_fill_super	[ext4]
+",
+            )
+            .unwrap();
+        let err = KSymResolver::load_file_name(file.path().to_path_buf()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
     #[tag(miri)]
     #[test]
     fn find_ksym() {
@@ -294,18 +430,22 @@ mod tests {
                 Ksym {
                     addr: 0x123,
                     name: "1".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x123,
                     name: "1.5".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x1234,
                     name: "2".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x12345,
                     name: "3".to_string(),
+                    module: None,
                 },
             ],
             by_name_idx: OnceCell::new(),
@@ -355,18 +495,22 @@ mod tests {
                 Ksym {
                     addr: 0x123,
                     name: "j".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x123,
                     name: "b".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x1234,
                     name: "a".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x12345,
                     name: "z".to_string(),
+                    module: None,
                 },
             ],
             by_name_idx: OnceCell::new(),
@@ -393,18 +537,22 @@ mod tests {
                 Ksym {
                     addr: 0x123,
                     name: "j".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x123,
                     name: "b".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x1234,
                     name: "a".to_string(),
+                    module: None,
                 },
                 Ksym {
                     addr: 0x12345,
                     name: "z".to_string(),
+                    module: None,
                 },
             ],
             by_name_idx: OnceCell::new(),
@@ -43,7 +43,9 @@
         feature = "apk",
         feature = "breakpad",
         feature = "dwarf",
-        feature = "gsym"
+        feature = "gsym",
+        feature = "macho",
+        feature = "pe"
     )),
     allow(dead_code, unused_imports)
 )]
@@ -55,6 +57,7 @@ extern crate test;
 
 #[macro_use]
 mod cfg;
+mod archive;
 #[cfg(feature = "breakpad")]
 mod breakpad;
 #[cfg(feature = "dwarf")]
@@ -68,12 +71,18 @@ mod insert_map;
 pub mod inspect;
 mod kernel;
 mod ksym;
+#[cfg(feature = "macho")]
+mod macho;
 mod maps;
 mod mmap;
 pub mod normalize;
 mod once;
+#[cfg(feature = "pe")]
+mod pe;
 mod pid;
 pub mod symbolize;
+#[cfg(any(feature = "macho", feature = "pe"))]
+mod symtab;
 #[cfg(test)]
 mod test_helper;
 mod util;
@@ -121,6 +130,7 @@ pub enum SymType {
 pub mod helper {
     use super::*;
 
+    pub use crate::normalize::buildid::elf_build_ids_match;
     pub use crate::normalize::buildid::read_elf_build_id;
     pub use crate::normalize::buildid::read_elf_build_id_from_mmap;
     pub use crate::normalize::ioctl::is_procmap_query_supported;
@@ -128,7 +138,12 @@ pub mod helper {
     cfg_breakpad! {
         pub use crate::breakpad::BreakpadResolver;
     }
+    #[cfg(feature = "dwarf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dwarf")))]
+    pub use crate::dwarf::LineRow;
+    pub use crate::elf::ElfReloc;
     pub use crate::elf::ElfResolver;
+    pub use crate::elf::ElfSym;
     cfg_gsym! {
         use std::path::Path;
         use crate::symbolize::Symbolize;
@@ -137,6 +152,8 @@ pub mod helper {
         use crate::symbolize::FindSymOpts;
         use crate::gsym;
 
+        pub use crate::gsym::GsymWriter;
+
         /// A symbol resolver for the GSYM format.
         // We provide a wrapper type here to eliminate the need for a lifetime.
         #[derive(Debug)]
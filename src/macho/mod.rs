@@ -0,0 +1,5 @@
+mod resolver;
+#[allow(dead_code, non_camel_case_types)]
+mod types;
+
+pub(crate) use resolver::MachOResolver;
@@ -0,0 +1,610 @@
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs::File;
+use std::mem::size_of;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::mmap::Mmap;
+use crate::symbolize::FindSymOpts;
+use crate::symbolize::Reason;
+use crate::symbolize::ResolvedSym;
+use crate::symbolize::SrcLang;
+use crate::symbolize::Symbolize;
+use crate::symbolize::TranslateFileOffset;
+use crate::symtab::find_sym;
+use crate::symtab::infer_sym_sizes;
+use crate::symtab::Symbol;
+use crate::util::ReadRaw as _;
+use crate::Addr;
+use crate::Error;
+use crate::ErrorExt as _;
+use crate::IntoError as _;
+use crate::Result;
+
+use super::types::fat_arch;
+use super::types::fat_arch_64;
+use super::types::fat_header;
+use super::types::load_command;
+use super::types::mach_header_64;
+use super::types::nlist_64;
+use super::types::section_64;
+use super::types::segment_command_64;
+use super::types::symtab_command;
+use super::types::CPU_TYPE_ARM64;
+use super::types::CPU_TYPE_X86_64;
+use super::types::FAT_MAGIC;
+use super::types::FAT_MAGIC_64;
+use super::types::LC_SEGMENT_64;
+use super::types::LC_SYMTAB;
+use super::types::MH_CIGAM;
+use super::types::MH_CIGAM_64;
+use super::types::MH_MAGIC;
+use super::types::MH_MAGIC_64;
+
+
+/// Retrieve the Mach-O CPU type of the host, if supported.
+fn host_cpu_type() -> Option<i32> {
+    if cfg!(target_arch = "x86_64") {
+        Some(CPU_TYPE_X86_64)
+    } else if cfg!(target_arch = "aarch64") {
+        Some(CPU_TYPE_ARM64)
+    } else {
+        None
+    }
+}
+
+/// Select the slice matching `cputype` from the fat binary contained in
+/// `data`.
+///
+/// On success, the offset of the slice inside `data` as well as the
+/// slice's data are returned.
+fn select_fat_slice(data: &[u8], cputype: Option<i32>) -> Result<(u64, &[u8])> {
+    let mut cursor = data;
+    let header = cursor
+        .read_pod::<fat_header>()
+        .ok_or_invalid_data(|| "failed to read fat header")?;
+    let magic = u32::from_be(header.magic);
+    let nfat_arch = u32::from_be(header.nfat_arch);
+
+    for _ in 0..nfat_arch {
+        // Fat headers are always stored in big endian byte order.
+        let (arch_cputype, offset, size) = if magic == FAT_MAGIC_64 {
+            let arch = cursor
+                .read_pod::<fat_arch_64>()
+                .ok_or_invalid_data(|| "failed to read fat architecture")?;
+            (
+                i32::from_be(arch.cputype),
+                u64::from_be(arch.offset),
+                u64::from_be(arch.size),
+            )
+        } else {
+            let arch = cursor
+                .read_pod::<fat_arch>()
+                .ok_or_invalid_data(|| "failed to read fat architecture")?;
+            (
+                i32::from_be(arch.cputype),
+                u64::from(u32::from_be(arch.offset)),
+                u64::from(u32::from_be(arch.size)),
+            )
+        };
+
+        if Some(arch_cputype) == cputype {
+            let slice = usize::try_from(offset)
+                .ok()
+                .zip(usize::try_from(size).ok())
+                .and_then(|(offset, size)| data.get(offset..offset.checked_add(size)?))
+                .ok_or_invalid_data(|| {
+                    format!("fat binary slice ({offset:#x}, {size:#x}) out of bounds")
+                })?;
+            return Ok((offset, slice))
+        }
+    }
+
+    Err(Error::with_unsupported(
+        "fat binary does not contain a slice for the host architecture",
+    ))
+}
+
+
+/// A segment of a Mach-O image.
+#[derive(Debug)]
+struct Segment {
+    /// The virtual address at which the segment is loaded.
+    vmaddr: Addr,
+    /// The offset of the segment's data inside the image.
+    fileoff: u64,
+    /// The size of the segment's data inside the image.
+    filesize: u64,
+}
+
+
+/// Parse the symbols and segments of the (thin) 64 bit Mach-O image
+/// contained in `image`.
+fn parse_image(image: &[u8]) -> Result<(Vec<Symbol>, Vec<Segment>)> {
+    let mut data = image;
+    let header = data
+        .read_pod::<mach_header_64>()
+        .ok_or_invalid_data(|| "failed to read Mach-O header")?;
+    match header.magic {
+        MH_MAGIC_64 => (),
+        MH_CIGAM_64 | MH_MAGIC | MH_CIGAM => {
+            return Err(Error::with_unsupported(
+                "only 64 bit Mach-O files in host byte order are supported",
+            ))
+        }
+        magic => {
+            return Err(Error::with_invalid_data(format!(
+                "encountered unexpected Mach-O magic ({magic:#x})"
+            )))
+        }
+    }
+
+    let mut cmds = data
+        .read_slice(header.sizeofcmds as usize)
+        .ok_or_invalid_data(|| "Mach-O load commands out of bounds")?;
+    // Sections, as (start, end) address pairs, in the order of their
+    // definition, which is what symbols refer to.
+    let mut sections = Vec::new();
+    let mut segments = Vec::new();
+    let mut symtab = None;
+
+    for _ in 0..header.ncmds {
+        let cmd = {
+            let mut peek = cmds;
+            peek.read_pod::<load_command>()
+                .ok_or_invalid_data(|| "failed to read Mach-O load command")?
+        };
+        if (cmd.cmdsize as usize) < size_of::<load_command>() {
+            return Err(Error::with_invalid_data(format!(
+                "Mach-O load command has invalid size ({})",
+                cmd.cmdsize
+            )))
+        }
+        let mut cmd_data = cmds
+            .read_slice(cmd.cmdsize as usize)
+            .ok_or_invalid_data(|| "Mach-O load command out of bounds")?;
+
+        match cmd.cmd {
+            LC_SEGMENT_64 => {
+                let segment = cmd_data
+                    .read_pod::<segment_command_64>()
+                    .ok_or_invalid_data(|| "failed to read Mach-O segment command")?;
+                let () = segments.push(Segment {
+                    vmaddr: segment.vmaddr,
+                    fileoff: segment.fileoff,
+                    filesize: segment.filesize,
+                });
+
+                for _ in 0..segment.nsects {
+                    let section = cmd_data
+                        .read_pod::<section_64>()
+                        .ok_or_invalid_data(|| "failed to read Mach-O section")?;
+                    let end = section.addr.saturating_add(section.size);
+                    let () = sections.push((section.addr, end));
+                }
+            }
+            LC_SYMTAB => {
+                let cmd = cmd_data
+                    .read_pod::<symtab_command>()
+                    .ok_or_invalid_data(|| "failed to read Mach-O symbol table command")?;
+                symtab = Some(cmd);
+            }
+            _ => (),
+        }
+    }
+
+    let symtab = if let Some(symtab) = symtab {
+        symtab
+    } else {
+        return Ok((Vec::new(), segments))
+    };
+
+    let mut nlists = image
+        .get(symtab.symoff as usize..)
+        .ok_or_invalid_data(|| "Mach-O symbol table out of bounds")?;
+    let strs = image
+        .get(symtab.stroff as usize..)
+        .and_then(|strs| strs.get(..symtab.strsize as usize))
+        .ok_or_invalid_data(|| "Mach-O string table out of bounds")?;
+
+    // Symbols along with the end address of the section they reside in.
+    let mut syms = Vec::new();
+    for _ in 0..symtab.nsyms {
+        let nlist = nlists
+            .read_pod::<nlist_64>()
+            .ok_or_invalid_data(|| "failed to read Mach-O symbol")?;
+        if !nlist.is_section_sym() {
+            continue
+        }
+
+        // Section indices are one-based.
+        let section_end = usize::from(nlist.n_sect)
+            .checked_sub(1)
+            .and_then(|idx| sections.get(idx))
+            .map(|(_start, end)| *end)
+            .ok_or_invalid_data(|| {
+                format!(
+                    "Mach-O symbol at {:#x} references invalid section ({})",
+                    nlist.n_value, nlist.n_sect
+                )
+            })?;
+
+        let name = strs
+            .get(nlist.n_strx as usize..)
+            .ok_or_invalid_data(|| "Mach-O string table index out of bounds")?
+            .read_cstr()
+            .ok_or_invalid_data(|| "no valid string found in Mach-O string table")?
+            .to_str()
+            .map_err(Error::with_invalid_data)
+            .context("invalid Mach-O symbol name")?;
+        // The compiler prefixes C level symbol names with an underscore.
+        let name = name.strip_prefix('_').unwrap_or(name);
+        if name.is_empty() {
+            continue
+        }
+
+        let sym = Symbol {
+            name: Box::from(name),
+            addr: nlist.n_value,
+            size: None,
+        };
+        let () = syms.push((sym, section_end));
+    }
+
+    let () = syms.sort_by_key(|(sym, _end)| sym.addr);
+
+    let syms = infer_sym_sizes(syms);
+    Ok((syms, segments))
+}
+
+
+/// A symbol resolver for a single Mach-O file.
+///
+/// Fat (universal) binaries are supported, in which case the slice
+/// matching the host architecture is used.
+pub(crate) struct MachOResolver {
+    /// Symbols, sorted by address.
+    syms: Box<[Symbol]>,
+    /// The segments of the Mach-O image.
+    segments: Box<[Segment]>,
+    /// The offset of the Mach-O image inside the file. This offset is
+    /// non-zero only for slices of fat binaries.
+    image_offset: u64,
+    /// The path of the Mach-O file in use.
+    path: PathBuf,
+}
+
+impl MachOResolver {
+    pub(crate) fn from_file(path: PathBuf, file: &File) -> Result<Self> {
+        let mmap = Mmap::map(file)
+            .with_context(|| format!("failed to memory map Mach-O file `{}`", path.display()))?;
+        Self::from_data(&mmap, host_cpu_type(), path)
+    }
+
+    /// Create a `MachOResolver` from the provided Mach-O data, picking
+    /// the slice for `cputype` if `data` is a fat binary.
+    fn from_data(data: &[u8], cputype: Option<i32>, path: PathBuf) -> Result<Self> {
+        let magic = {
+            let mut peek = data;
+            peek.read_array::<4>()
+                .map(u32::from_be_bytes)
+                .ok_or_invalid_data(|| "failed to read Mach-O magic")?
+        };
+        let (image_offset, image) = if magic == FAT_MAGIC || magic == FAT_MAGIC_64 {
+            select_fat_slice(data, cputype)
+        } else {
+            Ok((0, data))
+        }
+        .with_context(|| format!("failed to parse Mach-O file `{}`", path.display()))?;
+
+        let (syms, segments) = parse_image(image)
+            .with_context(|| format!("failed to parse Mach-O file `{}`", path.display()))?;
+        let slf = Self {
+            syms: syms.into_boxed_slice(),
+            segments: segments.into_boxed_slice(),
+            image_offset,
+            path,
+        };
+        Ok(slf)
+    }
+
+    /// Retrieve the path to the Mach-O file used by this resolver.
+    #[inline]
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Symbolize for MachOResolver {
+    fn find_sym(&self, addr: Addr, _opts: &FindSymOpts) -> Result<Result<ResolvedSym<'_>, Reason>> {
+        let result = find_sym(&self.syms, addr).map(|sym| ResolvedSym {
+            name: &sym.name,
+            addr: sym.addr,
+            size: sym.size,
+            // The symbol table does not carry source code language
+            // information.
+            lang: SrcLang::Unknown,
+            section: None,
+            // We don't support source code location information for
+            // Mach-O.
+            code_info: None,
+            inlined: Box::new([]),
+        });
+        Ok(result)
+    }
+}
+
+impl TranslateFileOffset for MachOResolver {
+    fn file_offset_to_virt_offset(&self, file_offset: u64) -> Result<Option<Addr>> {
+        let offset = if let Some(offset) = file_offset.checked_sub(self.image_offset) {
+            offset
+        } else {
+            return Ok(None)
+        };
+
+        for segment in self.segments.iter() {
+            if let Some(delta) = offset.checked_sub(segment.fileoff) {
+                if delta < segment.filesize {
+                    let addr = segment.vmaddr.checked_add(delta).ok_or_invalid_data(|| {
+                        format!(
+                            "segment virtual address {:#x} + offset {delta:#x} overflows",
+                            segment.vmaddr
+                        )
+                    })?;
+                    return Ok(Some(addr))
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Debug for MachOResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Mach-O {}", self.path().display())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::test_helper::push_pod;
+    use crate::ErrorKind;
+
+    use super::super::types::N_SECT;
+
+
+    /// Create a minimal 64 bit Mach-O image containing a `__TEXT`
+    /// segment with a single `__text` section at 0x100001000 spanning
+    /// 0x100 bytes, as well as a symbol table with a couple of symbols.
+    fn create_macho() -> Vec<u8> {
+        let mut strs = b"\0_main\0_helper\0_undefined\0".to_vec();
+        let () = strs.resize(32, 0);
+
+        let segment_size = size_of::<segment_command_64>() + size_of::<section_64>();
+        let cmds_size = segment_size + size_of::<symtab_command>();
+        let symoff = size_of::<mach_header_64>() + cmds_size;
+        let nsyms = 3;
+        let stroff = symoff + nsyms * size_of::<nlist_64>();
+
+        let mut data = Vec::new();
+        let header = mach_header_64 {
+            magic: MH_MAGIC_64,
+            cputype: CPU_TYPE_X86_64,
+            cpusubtype: 3,
+            // MH_EXECUTE
+            filetype: 2,
+            ncmds: 2,
+            sizeofcmds: cmds_size as u32,
+            flags: 0,
+            reserved: 0,
+        };
+        let () = push_pod(&mut data, &header);
+
+        let mut segname = [0; 16];
+        let () = segname[..6].copy_from_slice(b"__TEXT");
+        let segment = segment_command_64 {
+            cmd: LC_SEGMENT_64,
+            cmdsize: segment_size as u32,
+            segname,
+            vmaddr: 0x100000000,
+            vmsize: 0x2000,
+            fileoff: 0,
+            filesize: 0x2000,
+            maxprot: 5,
+            initprot: 5,
+            nsects: 1,
+            flags: 0,
+        };
+        let () = push_pod(&mut data, &segment);
+
+        let mut sectname = [0; 16];
+        let () = sectname[..6].copy_from_slice(b"__text");
+        let section = section_64 {
+            sectname,
+            segname,
+            addr: 0x100001000,
+            size: 0x100,
+            offset: 0x1000,
+            align: 4,
+            reloff: 0,
+            nreloc: 0,
+            flags: 0,
+            reserved1: 0,
+            reserved2: 0,
+            reserved3: 0,
+        };
+        let () = push_pod(&mut data, &section);
+
+        let symtab = symtab_command {
+            cmd: LC_SYMTAB,
+            cmdsize: size_of::<symtab_command>() as u32,
+            symoff: symoff as u32,
+            nsyms: nsyms as u32,
+            stroff: stroff as u32,
+            strsize: strs.len() as u32,
+        };
+        let () = push_pod(&mut data, &symtab);
+
+        let syms = [
+            nlist_64 {
+                n_strx: 7,
+                // N_SECT | N_EXT
+                n_type: N_SECT | 0x1,
+                n_sect: 1,
+                n_desc: 0,
+                n_value: 0x100001080,
+            },
+            nlist_64 {
+                n_strx: 1,
+                n_type: N_SECT | 0x1,
+                n_sect: 1,
+                n_desc: 0,
+                n_value: 0x100001000,
+            },
+            nlist_64 {
+                n_strx: 15,
+                // N_UNDF | N_EXT
+                n_type: 0x1,
+                n_sect: 0,
+                n_desc: 0,
+                n_value: 0,
+            },
+        ];
+        for sym in &syms {
+            let () = push_pod(&mut data, sym);
+        }
+        let () = data.extend_from_slice(&strs);
+        data
+    }
+
+    /// Wrap the provided Mach-O image in a fat binary, along with a
+    /// dummy slice for a different architecture.
+    fn create_fat(image: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let header = fat_header {
+            magic: FAT_MAGIC.to_be(),
+            nfat_arch: 2_u32.to_be(),
+        };
+        let () = push_pod(&mut data, &header);
+
+        let dummy = fat_arch {
+            cputype: 0x7_i32.to_be(),
+            cpusubtype: 3_i32.to_be(),
+            offset: 0x1000_u32.to_be(),
+            size: 0x10_u32.to_be(),
+            align: 12_u32.to_be(),
+        };
+        let () = push_pod(&mut data, &dummy);
+
+        let arch = fat_arch {
+            cputype: CPU_TYPE_X86_64.to_be(),
+            cpusubtype: 3_i32.to_be(),
+            offset: 0x2000_u32.to_be(),
+            size: (image.len() as u32).to_be(),
+            align: 12_u32.to_be(),
+        };
+        let () = push_pod(&mut data, &arch);
+
+        let () = data.resize(0x2000, 0);
+        let () = data.extend_from_slice(image);
+        data
+    }
+
+
+    /// Exercise the `Debug` representation of various types.
+    #[test]
+    fn debug_repr() {
+        let data = create_macho();
+        let resolver =
+            MachOResolver::from_data(&data, None, PathBuf::from("/tmp/test.macho")).unwrap();
+        assert_eq!(format!("{resolver:?}"), "Mach-O /tmp/test.macho");
+    }
+
+    /// Check that we can look up symbols in a Mach-O image.
+    #[test]
+    fn symbol_lookup() {
+        let data = create_macho();
+        let resolver = MachOResolver::from_data(&data, None, PathBuf::new()).unwrap();
+        // Undefined symbols should have been filtered out.
+        assert_eq!(resolver.syms.len(), 2);
+
+        let opts = FindSymOpts::Basic;
+        let sym = resolver.find_sym(0x100001000, &opts).unwrap().unwrap();
+        assert_eq!(sym.name, "main");
+        assert_eq!(sym.addr, 0x100001000);
+        assert_eq!(sym.size, Some(0x80));
+
+        let sym = resolver.find_sym(0x10000107f, &opts).unwrap().unwrap();
+        assert_eq!(sym.name, "main");
+
+        let sym = resolver.find_sym(0x1000010ff, &opts).unwrap().unwrap();
+        assert_eq!(sym.name, "helper");
+        assert_eq!(sym.size, Some(0x80));
+
+        // Past the end of the section.
+        let reason = resolver.find_sym(0x100001100, &opts).unwrap().unwrap_err();
+        assert_eq!(reason, Reason::UnknownAddr);
+
+        // Before the first symbol.
+        let reason = resolver.find_sym(0x100000fff, &opts).unwrap().unwrap_err();
+        assert_eq!(reason, Reason::UnknownAddr);
+    }
+
+    /// Check that we can translate file offsets into virtual offsets.
+    #[test]
+    fn file_offset_translation() {
+        let data = create_macho();
+        let resolver = MachOResolver::from_data(&data, None, PathBuf::new()).unwrap();
+        assert_eq!(
+            resolver.file_offset_to_virt_offset(0x1000).unwrap(),
+            Some(0x100001000)
+        );
+        assert_eq!(resolver.file_offset_to_virt_offset(0x2000).unwrap(), None);
+    }
+
+    /// Check that we pick the correct slice of a fat binary.
+    #[test]
+    fn fat_slice_selection() {
+        let image = create_macho();
+        let data = create_fat(&image);
+        let resolver =
+            MachOResolver::from_data(&data, Some(CPU_TYPE_X86_64), PathBuf::new()).unwrap();
+        assert_eq!(resolver.image_offset, 0x2000);
+
+        let sym = resolver
+            .find_sym(0x100001000, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "main");
+        // File offsets are relative to the start of the fat binary.
+        assert_eq!(
+            resolver.file_offset_to_virt_offset(0x3000).unwrap(),
+            Some(0x100001000)
+        );
+        assert_eq!(resolver.file_offset_to_virt_offset(0x1000).unwrap(), None);
+
+        let err =
+            MachOResolver::from_data(&data, Some(CPU_TYPE_ARM64), PathBuf::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    /// Check that we fail gracefully on malformed data.
+    #[test]
+    fn malformed_data() {
+        let err = MachOResolver::from_data(&[], None, PathBuf::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let data = create_macho();
+        for len in [8, 40, 120, 200] {
+            let err = MachOResolver::from_data(&data[..len], None, PathBuf::new()).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData, "{len}");
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::util::Pod;
+
+
+pub(crate) const MH_MAGIC_64: u32 = 0xfeedfacf;
+pub(crate) const MH_CIGAM_64: u32 = 0xcffaedfe;
+pub(crate) const MH_MAGIC: u32 = 0xfeedface;
+pub(crate) const MH_CIGAM: u32 = 0xcefaedfe;
+
+pub(crate) const FAT_MAGIC: u32 = 0xcafebabe;
+pub(crate) const FAT_MAGIC_64: u32 = 0xcafebabf;
+
+pub(crate) const CPU_TYPE_X86_64: i32 = 0x01000007;
+pub(crate) const CPU_TYPE_ARM64: i32 = 0x0100000c;
+
+pub(crate) const LC_SYMTAB: u32 = 0x2;
+pub(crate) const LC_SEGMENT_64: u32 = 0x19;
+
+pub(crate) const N_STAB: u8 = 0xe0;
+pub(crate) const N_TYPE: u8 = 0x0e;
+pub(crate) const N_SECT: u8 = 0xe;
+
+
+/// The header of a 64 bit Mach-O file.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct mach_header_64 {
+    pub magic: u32,
+    pub cputype: i32,
+    pub cpusubtype: i32,
+    pub filetype: u32,
+    pub ncmds: u32,
+    pub sizeofcmds: u32,
+    pub flags: u32,
+    pub reserved: u32,
+}
+
+// SAFETY: `mach_header_64` is valid for any bit pattern.
+unsafe impl Pod for mach_header_64 {}
+
+
+/// The header of a fat (universal) binary.
+///
+/// All members of fat headers are stored in big endian byte order.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct fat_header {
+    pub magic: u32,
+    pub nfat_arch: u32,
+}
+
+// SAFETY: `fat_header` is valid for any bit pattern.
+unsafe impl Pod for fat_header {}
+
+
+/// The description of a single architecture slice in a fat binary.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct fat_arch {
+    pub cputype: i32,
+    pub cpusubtype: i32,
+    pub offset: u32,
+    pub size: u32,
+    pub align: u32,
+}
+
+// SAFETY: `fat_arch` is valid for any bit pattern.
+unsafe impl Pod for fat_arch {}
+
+
+/// The description of a single architecture slice in a fat binary
+/// using 64 bit offsets.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct fat_arch_64 {
+    pub cputype: i32,
+    pub cpusubtype: i32,
+    pub offset: u64,
+    pub size: u64,
+    pub align: u32,
+    pub reserved: u32,
+}
+
+// SAFETY: `fat_arch_64` is valid for any bit pattern.
+unsafe impl Pod for fat_arch_64 {}
+
+
+/// The common prefix of all load commands.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct load_command {
+    pub cmd: u32,
+    pub cmdsize: u32,
+}
+
+// SAFETY: `load_command` is valid for any bit pattern.
+unsafe impl Pod for load_command {}
+
+
+/// A 64 bit segment load command (`LC_SEGMENT_64`).
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct segment_command_64 {
+    pub cmd: u32,
+    pub cmdsize: u32,
+    pub segname: [u8; 16],
+    pub vmaddr: u64,
+    pub vmsize: u64,
+    pub fileoff: u64,
+    pub filesize: u64,
+    pub maxprot: i32,
+    pub initprot: i32,
+    pub nsects: u32,
+    pub flags: u32,
+}
+
+// SAFETY: `segment_command_64` is valid for any bit pattern.
+unsafe impl Pod for segment_command_64 {}
+
+
+/// A 64 bit section, as it follows a `segment_command_64`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct section_64 {
+    pub sectname: [u8; 16],
+    pub segname: [u8; 16],
+    pub addr: u64,
+    pub size: u64,
+    pub offset: u32,
+    pub align: u32,
+    pub reloff: u32,
+    pub nreloc: u32,
+    pub flags: u32,
+    pub reserved1: u32,
+    pub reserved2: u32,
+    pub reserved3: u32,
+}
+
+// SAFETY: `section_64` is valid for any bit pattern.
+unsafe impl Pod for section_64 {}
+
+
+/// The symbol table load command (`LC_SYMTAB`).
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct symtab_command {
+    pub cmd: u32,
+    pub cmdsize: u32,
+    pub symoff: u32,
+    pub nsyms: u32,
+    pub stroff: u32,
+    pub strsize: u32,
+}
+
+// SAFETY: `symtab_command` is valid for any bit pattern.
+unsafe impl Pod for symtab_command {}
+
+
+/// A 64 bit symbol table entry.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct nlist_64 {
+    pub n_strx: u32,
+    pub n_type: u8,
+    pub n_sect: u8,
+    pub n_desc: u16,
+    pub n_value: u64,
+}
+
+// SAFETY: `nlist_64` is valid for any bit pattern.
+unsafe impl Pod for nlist_64 {}
+
+impl nlist_64 {
+    /// Check whether the symbol is defined in a section, as opposed to
+    /// being, say, undefined, absolute, or a debugging (stab) entry.
+    #[inline]
+    pub fn is_section_sym(&self) -> bool {
+        self.n_type & N_STAB == 0 && self.n_type & N_TYPE == N_SECT
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::size_of;
+
+
+    /// Check that our type definitions have the sizes mandated by the
+    /// Mach-O format.
+    #[test]
+    fn type_sizes() {
+        assert_eq!(size_of::<mach_header_64>(), 32);
+        assert_eq!(size_of::<fat_header>(), 8);
+        assert_eq!(size_of::<fat_arch>(), 20);
+        assert_eq!(size_of::<fat_arch_64>(), 32);
+        assert_eq!(size_of::<load_command>(), 8);
+        assert_eq!(size_of::<segment_command_64>(), 72);
+        assert_eq!(size_of::<section_64>(), 80);
+        assert_eq!(size_of::<symtab_command>(), 24);
+        assert_eq!(size_of::<nlist_64>(), 16);
+    }
+}
@@ -13,6 +13,7 @@ use std::ops::BitAndAssign;
 use std::ops::BitOr;
 use std::ops::BitOrAssign;
 use std::ops::Range;
+use std::path::Path;
 use std::path::PathBuf;
 
 use crate::util;
@@ -153,6 +154,15 @@ pub(crate) struct MapsEntry {
     pub build_id: Option<BuildId<'static>>,
 }
 
+impl MapsEntry {
+    /// Check whether the entry represents an anonymous (i.e., not file
+    /// or otherwise backed) executable memory mapping, as is commonly
+    /// used for JIT compiled code.
+    pub(crate) fn is_anon_exec(&self) -> bool {
+        self.path_name.is_none() && (self.perm & Perm::X) != Perm::default()
+    }
+}
+
 impl AsRef<MapsEntry> for MapsEntry {
     #[inline]
     fn as_ref(&self) -> &MapsEntry {
@@ -370,15 +380,25 @@ where
     }
 }
 
-/// Parse the maps file for the process with the given PID.
-pub(crate) fn parse(pid: Pid) -> Result<impl Iterator<Item = Result<MapsEntry>>> {
-    let path = format!("/proc/{pid}/maps");
-    let file =
-        File::open(&path).with_context(|| format!("failed to open proc maps file {path}"))?;
+/// Retrieve the path to the maps file of the process with the given PID.
+pub(crate) fn maps_path(pid: Pid) -> PathBuf {
+    PathBuf::from(format!("/proc/{pid}/maps"))
+}
+
+/// Parse the maps file at `path`, attributing all entries to the
+/// process with the given PID.
+pub(crate) fn parse_path(path: &Path, pid: Pid) -> Result<impl Iterator<Item = Result<MapsEntry>>> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open proc maps file {}", path.display()))?;
     let iter = parse_file(file, pid);
     Ok(iter)
 }
 
+/// Parse the maps file for the process with the given PID.
+pub(crate) fn parse(pid: Pid) -> Result<impl Iterator<Item = Result<MapsEntry>>> {
+    parse_path(&maps_path(pid), pid)
+}
+
 /// A helper function checking whether a `MapsEntry` has relevance to
 /// symbolization efforts.
 pub(crate) fn filter_relevant(entry: &MapsEntry) -> bool {
@@ -555,6 +575,32 @@ ffffffffff600000-ffffffffff601000 --xp 00000000 00:00 0                  [vsysca
         assert_eq!(entry.path_name, None);
     }
 
+    /// Check that we correctly classify anonymous executable mappings.
+    #[tag(miri)]
+    #[test]
+    fn anon_exec_classification() {
+        let lines = [
+            (
+                b"7f2321e00000-7f2321e37000 r-xp 00000000 00:00 0".as_slice(),
+                true,
+            ),
+            (b"7f2321e00000-7f2321e37000 rw-p 00000000 00:00 0", false),
+            (
+                b"7fff9c9e9000-7fff9c9eb000 r-xp 00000000 00:00 0 [vdso]",
+                false,
+            ),
+            (
+                b"00401000-00402000 r-xp 00001000 00:29 47459 /tmp/test/test",
+                false,
+            ),
+        ];
+
+        for (line, expected) in lines {
+            let entry = parse_maps_line(line, Pid::Slf).unwrap();
+            assert_eq!(entry.is_anon_exec(), expected, "{line:?}");
+        }
+    }
+
     /// Check that we error out as expected on malformed proc maps lines.
     #[tag(miri)]
     #[test]
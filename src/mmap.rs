@@ -5,6 +5,7 @@ use std::path::Path;
 use std::rc::Rc;
 
 use memmap2::Mmap as Mapping;
+use memmap2::MmapMut;
 use memmap2::MmapOptions;
 
 use crate::Error;
@@ -90,6 +91,27 @@ impl Mmap {
         Self::builder().map(file)
     }
 
+    /// Create an anonymous memory mapping containing a copy of `data`.
+    pub(crate) fn from_bytes(data: &[u8]) -> Result<Self> {
+        // Similar to the file case, we can't map a region of size 0.
+        if data.is_empty() {
+            return Ok(Mmap {
+                mapping: None,
+                view: 0..1,
+            })
+        }
+
+        let mut mapping = MmapMut::map_anon(data.len())?;
+        let () = mapping.copy_from_slice(data);
+        let mapping = mapping.make_read_only()?;
+
+        let mmap = Mmap {
+            mapping: Some(Rc::new(mapping)),
+            view: 0..data.len() as u64,
+        };
+        Ok(mmap)
+    }
+
     /// Create a new `Mmap` object (sharing the same underlying memory mapping
     /// as the current one) that restricts its view to the provided `range`.
     /// Adjustment happens relative to the current view.
@@ -141,6 +163,19 @@ mod tests {
         assert_ne!(format!("{builder:?}"), "");
     }
 
+    /// Check that we can create a memory mapping from in-memory data.
+    #[test]
+    fn mmap_from_bytes() {
+        let mmap = Mmap::from_bytes(b"abcdefgh").unwrap();
+        assert_eq!(mmap.deref(), b"abcdefgh");
+
+        let mmap = mmap.constrain(2..4).unwrap();
+        assert_eq!(mmap.deref(), b"cd");
+
+        let mmap = Mmap::from_bytes(&[]).unwrap();
+        assert_eq!(mmap.deref(), &[]);
+    }
+
     /// Check that we can `mmap` an empty file.
     #[test]
     fn mmap_empty_file() {
@@ -2,11 +2,9 @@ use std::borrow::Cow;
 use std::path::Path;
 
 use crate::elf;
-use crate::elf::types::Elf64_Nhdr;
 use crate::elf::ElfParser;
 use crate::file_cache::FileCache;
 use crate::log::warn;
-use crate::util::ReadRaw as _;
 use crate::Error;
 use crate::IntoError as _;
 use crate::Mmap;
@@ -17,28 +15,63 @@ use crate::Result;
 pub type BuildId<'src> = Cow<'src, [u8]>;
 
 
+/// Round `len` up to the next multiple of the ELF note alignment.
+#[inline]
+fn note_align(len: u32) -> usize {
+    (len as usize).saturating_add(3) & !3
+}
+
+/// Search the ELF notes contained in `notes` for a GNU build ID.
+fn find_build_id_note<'data>(
+    parser: &ElfParser,
+    mut notes: &'data [u8],
+) -> Result<Option<BuildId<'data>>> {
+    while !notes.is_empty() {
+        let header = parser
+            .read_nhdr(&mut notes)?
+            .ok_or_invalid_data(|| "failed to read ELF note header")?;
+        let name = notes
+            .get(..header.n_namesz as usize)
+            .ok_or_invalid_data(|| "failed to read ELF note name")?;
+        notes = notes.get(note_align(header.n_namesz)..).unwrap_or(&[]);
+        let desc = notes
+            .get(..header.n_descsz as usize)
+            .ok_or_invalid_data(|| "failed to read ELF note descriptor")?;
+        notes = notes.get(note_align(header.n_descsz)..).unwrap_or(&[]);
+
+        // Other notes, e.g., those of SystemTap, may share the type
+        // value, so we have to check the name as well.
+        if header.n_type == elf::types::NT_GNU_BUILD_ID && name == b"GNU\0" {
+            return Ok(Some(Cow::Borrowed(desc)))
+        }
+    }
+    Ok(None)
+}
+
 /// Iterate over all note sections to find one of type
 /// [`NT_GNU_BUILD_ID`][elf::types::NT_GNU_BUILD_ID].
 fn read_build_id_from_notes(parser: &ElfParser) -> Result<Option<BuildId<'_>>> {
-    let shdrs = parser.section_headers()?;
-    for (idx, shdr) in shdrs.iter().enumerate() {
-        if shdr.sh_type == elf::types::SHT_NOTE {
-            // SANITY: We just found the index so the section data should always
-            //         be found.
-            let mut bytes = parser.section_data(idx).unwrap();
-            let header = bytes
-                .read_pod_ref::<Elf64_Nhdr>()
-                .ok_or_invalid_data(|| "failed to read build ID section header")?;
-            if header.n_type == elf::types::NT_GNU_BUILD_ID {
-                // Type check is assumed to suffice, but we still need
-                // to skip the name bytes.
-                let _name = bytes
-                    .read_slice(header.n_namesz as _)
-                    .ok_or_invalid_data(|| "failed to read build ID section name")?;
-                let build_id = bytes
-                    .read_slice(header.n_descsz as _)
-                    .ok_or_invalid_data(|| "failed to read build ID section contents")?;
-                return Ok(Some(Cow::Borrowed(build_id)))
+    for idx in parser.find_sections_by_type(elf::types::SHT_NOTE)? {
+        let notes = parser.section_data(idx)?;
+        if let Some(build_id) = find_build_id_note(parser, notes)? {
+            return Ok(Some(build_id))
+        }
+    }
+    Ok(None)
+}
+
+/// Iterate over all note segments to find one of type
+/// [`NT_GNU_BUILD_ID`][elf::types::NT_GNU_BUILD_ID].
+///
+/// Program headers are consulted as a last resort, for files that lack
+/// section headers.
+fn read_build_id_from_segments(parser: &ElfParser) -> Result<Option<BuildId<'_>>> {
+    let phdrs = parser.program_headers()?;
+    for phdr in phdrs.iter() {
+        if phdr.p_type == elf::types::PT_NOTE {
+            let notes = parser.segment_data(phdr)?;
+            if let Some(build_id) = find_build_id_note(parser, notes)? {
+                return Ok(Some(build_id))
             }
         }
     }
@@ -63,36 +96,22 @@ fn read_build_id_from_section_name(parser: &ElfParser) -> Result<Option<BuildId<
             return Ok(None)
         }
 
-        // SANITY: We just found the index so the section should always be
-        //         found.
-        let mut bytes = parser.section_data(idx).unwrap();
-        let header = bytes
-            .read_pod_ref::<Elf64_Nhdr>()
-            .ok_or_invalid_data(|| "failed to read build ID section header")?;
-        let name = bytes
-            .read_slice(header.n_namesz as _)
-            .and_then(|mut name| name.read_cstr())
-            .ok_or_invalid_data(|| "failed to read build ID section name")?;
-        if name.to_bytes() != b"GNU" {
-            warn!("encountered unsupported build ID type {:?}; ignoring", name);
-            Ok(None)
-        } else {
-            let build_id = bytes
-                .read_slice(header.n_descsz as _)
-                .ok_or_invalid_data(|| "failed to read build ID section contents")?;
-            Ok(Some(Cow::Borrowed(build_id)))
-        }
+        let notes = parser.section_data(idx)?;
+        find_build_id_note(parser, notes)
     } else {
         Ok(None)
     }
 }
 
 
-fn read_build_id_impl(parser: &ElfParser) -> Result<Option<BuildId>> {
+/// Read the build ID of the ELF file represented by `parser`, if any.
+pub(crate) fn read_build_id(parser: &ElfParser) -> Result<Option<BuildId>> {
     if let Some(build_id) = read_build_id_from_section_name(parser)? {
         Ok(Some(build_id))
     } else if let Some(build_id) = read_build_id_from_notes(parser)? {
         Ok(Some(build_id))
+    } else if let Some(build_id) = read_build_id_from_segments(parser)? {
+        Ok(Some(build_id))
     } else {
         Ok(None)
     }
@@ -124,7 +143,7 @@ impl BuildIdReader<'_> for DefaultBuildIdReader {
     /// Attempt to read an ELF binary's build ID from a file.
     fn read_build_id_fallible(&self, path: &Path) -> Result<Option<BuildId<'static>>> {
         let parser = ElfParser::open(path)?;
-        let buildid = read_build_id_impl(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
+        let buildid = read_build_id(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
         Ok(buildid)
     }
 }
@@ -149,8 +168,7 @@ impl<'src> BuildIdReader<'src> for CachingBuildIdReader<'src> {
         let build_id = cell
             .get_or_try_init(|| {
                 let parser = ElfParser::open_file(file, path)?;
-                let buildid =
-                    read_build_id_impl(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
+                let buildid = read_build_id(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
                 Result::<_, Error>::Ok(buildid)
             })?
             .as_deref()
@@ -213,7 +231,7 @@ where
     P: AsRef<Path>,
 {
     let parser = ElfParser::open(path.as_ref())?;
-    let buildid = read_build_id_impl(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
+    let buildid = read_build_id(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
     Ok(buildid)
 }
 
@@ -226,22 +244,55 @@ where
 #[inline]
 pub fn read_elf_build_id_from_mmap(mmap: &Mmap) -> Result<Option<BuildId<'static>>> {
     let parser = ElfParser::from_mmap(mmap.clone(), None);
-    let buildid = read_build_id_impl(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
+    let buildid = read_build_id(&parser)?.map(|buildid| Cow::Owned(buildid.to_vec()));
     Ok(buildid)
 }
 
+/// Check whether the ELF files represented by `parser_a` and
+/// `parser_b` have the same build ID.
+///
+/// Files lacking a build ID are never considered matching.
+pub(crate) fn build_ids_match(parser_a: &ElfParser, parser_b: &ElfParser) -> Result<bool> {
+    let build_id_a = read_build_id(parser_a)?;
+    let build_id_b = read_build_id(parser_b)?;
+    let matches = match (build_id_a, build_id_b) {
+        (Some(build_id_a), Some(build_id_b)) => build_id_a == build_id_b,
+        _ => false,
+    };
+    Ok(matches)
+}
+
+/// Check whether two ELF files have the same build ID.
+///
+/// This function can be used to verify that a separately retrieved
+/// debug information file belongs to a given (stripped) binary.
+///
+/// Returns `false` if either of the files does not contain a build
+/// ID.
+#[inline]
+pub fn elf_build_ids_match<P, Q>(path_a: &P, path_b: &Q) -> Result<bool>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let parser_a = ElfParser::open(path_a.as_ref())?;
+    let parser_b = ElfParser::open(path_b.as_ref())?;
+    build_ids_match(&parser_a, &parser_b)
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use std::fs::File;
+    use std::mem::size_of;
 
     use test_log::test;
 
 
     /// Check that we can read a binary's build ID based on the ELF section name
-    /// as well as ELF section type.
+    /// as well as ELF section and segment type.
     #[test]
     fn build_id_reading_from_name_and_notes() {
         fn test(f: fn(&ElfParser) -> Result<Option<BuildId>>) {
@@ -257,6 +308,40 @@ mod tests {
 
         test(read_build_id_from_section_name);
         test(read_build_id_from_notes);
+        test(read_build_id_from_segments);
+    }
+
+    /// Check that we skip over unrelated notes when searching for a
+    /// build ID.
+    #[test]
+    fn build_id_note_search() {
+        let elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = ElfParser::open(&elf).unwrap();
+
+        let nhdr_size = size_of::<elf::types::Elf64_Nhdr>();
+        let mut notes = Vec::new();
+        // A note with the same type but a different name, whose name
+        // requires padding.
+        let () = notes.extend_from_slice(&6u32.to_ne_bytes());
+        let () = notes.extend_from_slice(&2u32.to_ne_bytes());
+        let () = notes.extend_from_slice(&elf::types::NT_GNU_BUILD_ID.to_ne_bytes());
+        let () = notes.extend_from_slice(b"stapx\0\0\0");
+        let () = notes.extend_from_slice(&[0xff, 0xff, 0, 0]);
+        assert_eq!(notes.len(), nhdr_size + 12);
+        // The actual build ID note.
+        let () = notes.extend_from_slice(&4u32.to_ne_bytes());
+        let () = notes.extend_from_slice(&3u32.to_ne_bytes());
+        let () = notes.extend_from_slice(&elf::types::NT_GNU_BUILD_ID.to_ne_bytes());
+        let () = notes.extend_from_slice(b"GNU\0");
+        let () = notes.extend_from_slice(&[1, 2, 3]);
+
+        let build_id = find_build_id_note(&parser, &notes).unwrap().unwrap();
+        assert_eq!(build_id.as_ref(), [1, 2, 3]);
+
+        let build_id = find_build_id_note(&parser, &notes[..nhdr_size + 12]).unwrap();
+        assert_eq!(build_id, None);
     }
 
     /// Check that we can read a binary's build ID.
@@ -288,4 +373,21 @@ mod tests {
         let build_id = read_elf_build_id(&elf).unwrap();
         assert_eq!(build_id, None);
     }
+
+    /// Check that we can compare the build IDs of two ELF files.
+    #[test]
+    fn build_id_matching() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+        let so = data.join("libtest-so.so");
+        let stripped = data.join("libtest-so-stripped.so");
+        let md5 = data.join("libtest-so-no-separate-code.so");
+        let no_build_id = data.join("test-no-debug.bin");
+
+        assert!(elf_build_ids_match(&so, &stripped).unwrap());
+        assert!(elf_build_ids_match(&stripped, &so).unwrap());
+        assert!(!elf_build_ids_match(&so, &md5).unwrap());
+        assert!(!elf_build_ids_match(&so, &no_build_id).unwrap());
+        // Files without build ID never match.
+        assert!(!elf_build_ids_match(&no_build_id, &no_build_id).unwrap());
+    }
 }
@@ -0,0 +1,5 @@
+mod resolver;
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+mod types;
+
+pub(crate) use resolver::PeResolver;
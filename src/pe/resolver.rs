@@ -0,0 +1,732 @@
+use std::cmp::min;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs::File;
+use std::mem::size_of;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str;
+
+use crate::mmap::Mmap;
+use crate::symbolize::FindSymOpts;
+use crate::symbolize::Reason;
+use crate::symbolize::ResolvedSym;
+use crate::symbolize::SrcLang;
+use crate::symbolize::Symbolize;
+use crate::symbolize::TranslateFileOffset;
+use crate::symtab::find_sym;
+use crate::symtab::infer_sym_sizes;
+use crate::symtab::Symbol;
+use crate::util::ReadRaw as _;
+use crate::Addr;
+use crate::Error;
+use crate::ErrorExt as _;
+use crate::IntoError as _;
+use crate::Result;
+
+use super::types::IMAGE_DATA_DIRECTORY;
+use super::types::IMAGE_DIRECTORY_ENTRY_EXPORT;
+use super::types::IMAGE_DOS_HEADER;
+use super::types::IMAGE_DOS_SIGNATURE;
+use super::types::IMAGE_EXPORT_DIRECTORY;
+use super::types::IMAGE_FILE_HEADER;
+use super::types::IMAGE_NT_OPTIONAL_HDR32_MAGIC;
+use super::types::IMAGE_NT_OPTIONAL_HDR64_MAGIC;
+use super::types::IMAGE_NT_SIGNATURE;
+use super::types::IMAGE_OPTIONAL_HEADER32;
+use super::types::IMAGE_OPTIONAL_HEADER64;
+use super::types::IMAGE_SECTION_HEADER;
+use super::types::IMAGE_SYMBOL;
+use super::types::IMAGE_SYM_CLASS_EXTERNAL;
+
+
+/// A section of a PE image.
+#[derive(Debug)]
+struct Section {
+    /// The relative virtual address at which the section is loaded.
+    virt_addr: Addr,
+    /// The (exclusive) relative virtual end address of the section
+    /// when loaded into memory.
+    virt_end: Addr,
+    /// The offset of the section's data inside the file.
+    raw_offset: u64,
+    /// The size of the section's data inside the file.
+    raw_size: u64,
+}
+
+/// Retrieve the data backing the provided relative virtual address,
+/// up to the end of the containing section.
+fn rva_data<'dat>(data: &'dat [u8], sections: &[Section], rva: Addr) -> Option<&'dat [u8]> {
+    sections.iter().find_map(|section| {
+        let delta = rva.checked_sub(section.virt_addr)?;
+        if delta >= section.raw_size {
+            return None
+        }
+        let start = usize::try_from(section.raw_offset.checked_add(delta)?).ok()?;
+        let end = usize::try_from(section.raw_offset.checked_add(section.raw_size)?).ok()?;
+        data.get(start..end)
+    })
+}
+
+/// Read the NUL terminated string at the provided relative virtual
+/// address.
+fn rva_str<'dat>(data: &'dat [u8], sections: &[Section], rva: Addr) -> Result<&'dat str> {
+    let name = rva_data(data, sections, rva)
+        .ok_or_invalid_data(|| format!("PE string at {rva:#x} out of bounds"))?
+        .read_cstr()
+        .ok_or_invalid_data(|| format!("no valid string found at {rva:#x}"))?
+        .to_str()
+        .map_err(Error::with_invalid_data)
+        .context("invalid PE symbol name")?;
+    Ok(name)
+}
+
+
+/// Parse the symbols contained in the COFF symbol table, if any.
+fn parse_coff_syms(
+    data: &[u8],
+    file_header: &IMAGE_FILE_HEADER,
+    sections: &[Section],
+    syms: &mut Vec<(Symbol, Addr)>,
+) -> Result<()> {
+    if file_header.PointerToSymbolTable == 0 || file_header.NumberOfSymbols == 0 {
+        return Ok(())
+    }
+
+    let mut entries = data
+        .get(file_header.PointerToSymbolTable as usize..)
+        .ok_or_invalid_data(|| "COFF symbol table out of bounds")?;
+    // The string table immediately follows the symbol table.
+    let strs = entries
+        .get(file_header.NumberOfSymbols as usize * size_of::<IMAGE_SYMBOL>()..)
+        .ok_or_invalid_data(|| "COFF string table out of bounds")?;
+
+    let mut remaining = file_header.NumberOfSymbols;
+    while remaining > 0 {
+        let sym = entries
+            .read_pod::<IMAGE_SYMBOL>()
+            .ok_or_invalid_data(|| "failed to read COFF symbol")?;
+        // Auxiliary records follow the symbol they belong to and do
+        // not describe symbols themselves.
+        let () = entries
+            .advance(usize::from(sym.NumberOfAuxSymbols) * size_of::<IMAGE_SYMBOL>())
+            .ok_or_invalid_data(|| "COFF auxiliary symbol records out of bounds")?;
+        remaining = remaining.saturating_sub(1 + u32::from(sym.NumberOfAuxSymbols));
+
+        if !(sym.is_function() || sym.StorageClass == IMAGE_SYM_CLASS_EXTERNAL) {
+            continue
+        }
+
+        // Section numbers are one-based. Zero and negative values
+        // denote undefined, absolute, and debug symbols.
+        let section = usize::try_from(sym.SectionNumber)
+            .ok()
+            .and_then(|number| number.checked_sub(1))
+            .and_then(|idx| sections.get(idx));
+        let section = if let Some(section) = section {
+            section
+        } else {
+            continue
+        };
+
+        let name = if sym.Name[..4] == [0; 4] {
+            let offset = u32::from_le_bytes([sym.Name[4], sym.Name[5], sym.Name[6], sym.Name[7]]);
+            strs.get(offset as usize..)
+                .ok_or_invalid_data(|| "COFF string table index out of bounds")?
+                .read_cstr()
+                .ok_or_invalid_data(|| "no valid string found in COFF string table")?
+                .to_bytes()
+        } else {
+            let len = sym
+                .Name
+                .iter()
+                .position(|b| *b == b'\0')
+                .unwrap_or(sym.Name.len());
+            &sym.Name[..len]
+        };
+        let name = str::from_utf8(name)
+            .map_err(Error::with_invalid_data)
+            .context("invalid COFF symbol name")?;
+        if name.is_empty() {
+            continue
+        }
+
+        let addr = section
+            .virt_addr
+            .checked_add(Addr::from(sym.Value))
+            .ok_or_invalid_data(|| {
+                format!(
+                    "COFF symbol `{name}` address ({:#x} + {:#x}) overflows",
+                    section.virt_addr, sym.Value
+                )
+            })?;
+        let sym = Symbol {
+            name: Box::from(name),
+            addr,
+            size: None,
+        };
+        let () = syms.push((sym, section.virt_end));
+    }
+    Ok(())
+}
+
+
+/// Parse the symbols exported by name via the export directory.
+fn parse_exports(
+    data: &[u8],
+    export_dir: &IMAGE_DATA_DIRECTORY,
+    sections: &[Section],
+    syms: &mut Vec<(Symbol, Addr)>,
+) -> Result<()> {
+    if export_dir.VirtualAddress == 0 || export_dir.Size == 0 {
+        return Ok(())
+    }
+
+    let dir_start = Addr::from(export_dir.VirtualAddress);
+    let dir_end = dir_start
+        .checked_add(Addr::from(export_dir.Size))
+        .ok_or_invalid_data(|| "PE export directory end address overflows")?;
+    let dir = rva_data(data, sections, dir_start)
+        .ok_or_invalid_data(|| "PE export directory out of bounds")?
+        .read_pod::<IMAGE_EXPORT_DIRECTORY>()
+        .ok_or_invalid_data(|| "failed to read PE export directory")?;
+
+    let funcs = rva_data(data, sections, Addr::from(dir.AddressOfFunctions))
+        .ok_or_invalid_data(|| "PE export address table out of bounds")?;
+    let mut names = rva_data(data, sections, Addr::from(dir.AddressOfNames))
+        .ok_or_invalid_data(|| "PE export name table out of bounds")?;
+    let mut ordinals = rva_data(data, sections, Addr::from(dir.AddressOfNameOrdinals))
+        .ok_or_invalid_data(|| "PE export ordinal table out of bounds")?;
+
+    for _ in 0..dir.NumberOfNames {
+        let name_rva = names
+            .read_u32()
+            .ok_or_invalid_data(|| "failed to read PE export name")?;
+        let ordinal = ordinals
+            .read_u16()
+            .ok_or_invalid_data(|| "failed to read PE export ordinal")?;
+        if u32::from(ordinal) >= dir.NumberOfFunctions {
+            return Err(Error::with_invalid_data(format!(
+                "PE export ordinal ({ordinal}) out of bounds"
+            )))
+        }
+        let addr = funcs
+            .get(usize::from(ordinal) * size_of::<u32>()..)
+            .and_then(|mut funcs| funcs.read_u32())
+            .map(Addr::from)
+            .ok_or_invalid_data(|| "PE export address out of bounds")?;
+
+        // Addresses pointing into the export directory itself denote
+        // forwarders to other modules, which we cannot symbolize.
+        if (dir_start..dir_end).contains(&addr) {
+            continue
+        }
+
+        let section = sections
+            .iter()
+            .find(|section| (section.virt_addr..section.virt_end).contains(&addr));
+        let section = if let Some(section) = section {
+            section
+        } else {
+            continue
+        };
+
+        let name = rva_str(data, sections, Addr::from(name_rva))?;
+        if name.is_empty() {
+            continue
+        }
+
+        let sym = Symbol {
+            name: Box::from(name),
+            addr,
+            size: None,
+        };
+        let () = syms.push((sym, section.virt_end));
+    }
+    Ok(())
+}
+
+
+/// Parse the symbols and sections of the PE image contained in `data`.
+fn parse_image(data: &[u8]) -> Result<(Vec<Symbol>, Vec<Section>)> {
+    let dos_header = {
+        let mut peek = data;
+        peek.read_pod::<IMAGE_DOS_HEADER>()
+            .ok_or_invalid_data(|| "failed to read MS-DOS header")?
+    };
+    if dos_header.e_magic != IMAGE_DOS_SIGNATURE {
+        return Err(Error::with_invalid_data(format!(
+            "encountered unexpected MS-DOS magic ({:#x})",
+            dos_header.e_magic
+        )))
+    }
+
+    let mut nt_data = data
+        .get(dos_header.e_lfanew as usize..)
+        .ok_or_invalid_data(|| "PE header out of bounds")?;
+    let signature = nt_data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read PE signature")?;
+    if signature != IMAGE_NT_SIGNATURE {
+        return Err(Error::with_invalid_data(format!(
+            "encountered unexpected PE signature ({signature:#x})"
+        )))
+    }
+
+    let file_header = nt_data
+        .read_pod::<IMAGE_FILE_HEADER>()
+        .ok_or_invalid_data(|| "failed to read COFF file header")?;
+    let mut opt_data = nt_data
+        .read_slice(usize::from(file_header.SizeOfOptionalHeader))
+        .ok_or_invalid_data(|| "PE optional header out of bounds")?;
+    // The section table follows the optional header.
+    let mut shdrs = nt_data;
+
+    let magic = {
+        let mut peek = opt_data;
+        peek.read_u16()
+            .ok_or_invalid_data(|| "failed to read PE optional header magic")?
+    };
+    let dir_count = match magic {
+        IMAGE_NT_OPTIONAL_HDR32_MAGIC => {
+            opt_data
+                .read_pod::<IMAGE_OPTIONAL_HEADER32>()
+                .ok_or_invalid_data(|| "failed to read PE32 optional header")?
+                .NumberOfRvaAndSizes
+        }
+        IMAGE_NT_OPTIONAL_HDR64_MAGIC => {
+            opt_data
+                .read_pod::<IMAGE_OPTIONAL_HEADER64>()
+                .ok_or_invalid_data(|| "failed to read PE32+ optional header")?
+                .NumberOfRvaAndSizes
+        }
+        magic => {
+            return Err(Error::with_invalid_data(format!(
+                "encountered unexpected PE optional header magic ({magic:#x})"
+            )))
+        }
+    };
+
+    let export_dir = if dir_count as usize > IMAGE_DIRECTORY_ENTRY_EXPORT {
+        let () = opt_data
+            .advance(IMAGE_DIRECTORY_ENTRY_EXPORT * size_of::<IMAGE_DATA_DIRECTORY>())
+            .ok_or_invalid_data(|| "PE data directory out of bounds")?;
+        let dir = opt_data
+            .read_pod::<IMAGE_DATA_DIRECTORY>()
+            .ok_or_invalid_data(|| "failed to read PE export data directory")?;
+        Some(dir)
+    } else {
+        None
+    };
+
+    let sections = (0..file_header.NumberOfSections)
+        .map(|_| {
+            let shdr = shdrs
+                .read_pod::<IMAGE_SECTION_HEADER>()
+                .ok_or_invalid_data(|| "failed to read PE section header")?;
+            // A virtual size of zero is used by some linkers to
+            // indicate that the raw data size should be used.
+            let virt_size = if shdr.VirtualSize == 0 {
+                shdr.SizeOfRawData
+            } else {
+                shdr.VirtualSize
+            };
+            let virt_addr = Addr::from(shdr.VirtualAddress);
+            let virt_end = virt_addr
+                .checked_add(u64::from(virt_size))
+                .ok_or_invalid_data(|| {
+                    format!("PE section ({virt_addr:#x}, {virt_size:#x}) end address overflows")
+                })?;
+            let section = Section {
+                virt_addr,
+                virt_end,
+                raw_offset: u64::from(shdr.PointerToRawData),
+                // Section data in the file may be padded beyond the
+                // size of the section in memory; ignore said padding.
+                raw_size: u64::from(min(shdr.SizeOfRawData, virt_size)),
+            };
+            Ok(section)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut syms = Vec::new();
+    let () = parse_coff_syms(data, &file_header, &sections, &mut syms)?;
+    if let Some(export_dir) = export_dir {
+        let () = parse_exports(data, &export_dir, &sections, &mut syms)?;
+    }
+
+    let () = syms.sort_by(|(sym1, _end1), (sym2, _end2)| {
+        (sym1.addr, &sym1.name).cmp(&(sym2.addr, &sym2.name))
+    });
+    // Exported symbols are commonly also present in the symbol table.
+    let () = syms
+        .dedup_by(|(sym1, _end1), (sym2, _end2)| sym1.addr == sym2.addr && sym1.name == sym2.name);
+
+    let syms = infer_sym_sizes(syms);
+    Ok((syms, sections))
+}
+
+
+/// A symbol resolver for a single PE file.
+///
+/// Addresses handled by this resolver are relative virtual addresses
+/// (RVAs), i.e., they are relative to the image's load address.
+pub(crate) struct PeResolver {
+    /// Symbols, sorted by address.
+    syms: Box<[Symbol]>,
+    /// The sections of the PE image.
+    sections: Box<[Section]>,
+    /// The path of the PE file in use.
+    path: PathBuf,
+}
+
+impl PeResolver {
+    pub(crate) fn from_file(path: PathBuf, file: &File) -> Result<Self> {
+        let mmap = Mmap::map(file)
+            .with_context(|| format!("failed to memory map PE file `{}`", path.display()))?;
+        Self::from_data(&mmap, path)
+    }
+
+    /// Create a `PeResolver` from the provided PE data.
+    fn from_data(data: &[u8], path: PathBuf) -> Result<Self> {
+        let (syms, sections) = parse_image(data)
+            .with_context(|| format!("failed to parse PE file `{}`", path.display()))?;
+        let slf = Self {
+            syms: syms.into_boxed_slice(),
+            sections: sections.into_boxed_slice(),
+            path,
+        };
+        Ok(slf)
+    }
+
+    /// Retrieve the path to the PE file used by this resolver.
+    #[inline]
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Symbolize for PeResolver {
+    fn find_sym(&self, addr: Addr, _opts: &FindSymOpts) -> Result<Result<ResolvedSym<'_>, Reason>> {
+        let result = find_sym(&self.syms, addr).map(|sym| ResolvedSym {
+            name: &sym.name,
+            addr: sym.addr,
+            size: sym.size,
+            // Neither the symbol table nor the export directory carry
+            // source code language information.
+            lang: SrcLang::Unknown,
+            section: None,
+            // We don't support source code location information for
+            // PE.
+            code_info: None,
+            inlined: Box::new([]),
+        });
+        Ok(result)
+    }
+}
+
+impl TranslateFileOffset for PeResolver {
+    fn file_offset_to_virt_offset(&self, file_offset: u64) -> Result<Option<Addr>> {
+        for section in self.sections.iter() {
+            if let Some(delta) = file_offset.checked_sub(section.raw_offset) {
+                if delta < section.raw_size {
+                    let addr = section
+                        .virt_addr
+                        .checked_add(delta)
+                        .ok_or_invalid_data(|| {
+                            format!(
+                                "PE section address {:#x} + offset {delta:#x} overflows",
+                                section.virt_addr
+                            )
+                        })?;
+                    return Ok(Some(addr))
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Debug for PeResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "PE {}", self.path().display())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::test_helper::write_pod;
+    use crate::ErrorKind;
+
+    use super::super::types::IMAGE_SYM_DTYPE_FUNCTION;
+
+
+    /// Create a section header with the given properties.
+    fn section_header(
+        name: &[u8],
+        virt_addr: u32,
+        virt_size: u32,
+        raw_offset: u32,
+        raw_size: u32,
+    ) -> IMAGE_SECTION_HEADER {
+        let mut shdr_name = [0; 8];
+        let () = shdr_name[..name.len()].copy_from_slice(name);
+        IMAGE_SECTION_HEADER {
+            Name: shdr_name,
+            VirtualSize: virt_size,
+            VirtualAddress: virt_addr,
+            SizeOfRawData: raw_size,
+            PointerToRawData: raw_offset,
+            PointerToRelocations: 0,
+            PointerToLinenumbers: 0,
+            NumberOfRelocations: 0,
+            NumberOfLinenumbers: 0,
+            Characteristics: 0,
+        }
+    }
+
+    /// Create a COFF symbol with the given properties.
+    fn coff_sym(name: [u8; 8], value: u32, type_: u16, class: u8, aux: u8) -> IMAGE_SYMBOL {
+        IMAGE_SYMBOL {
+            Name: name,
+            Value: value,
+            SectionNumber: 1,
+            Type: type_,
+            StorageClass: class,
+            NumberOfAuxSymbols: aux,
+        }
+    }
+
+    /// Create a minimal PE32+ image containing a `.text` section at RVA
+    /// 0x1000 spanning 0x100 bytes, an `.rdata` section containing an
+    /// export directory, as well as a COFF symbol table.
+    fn create_pe() -> Vec<u8> {
+        let mut data = vec![0; 0x600];
+
+        let dos_header = IMAGE_DOS_HEADER {
+            e_magic: IMAGE_DOS_SIGNATURE,
+            e_cblp: 0,
+            e_cp: 0,
+            e_crlc: 0,
+            e_cparhdr: 0,
+            e_minalloc: 0,
+            e_maxalloc: 0,
+            e_ss: 0,
+            e_sp: 0,
+            e_csum: 0,
+            e_ip: 0,
+            e_cs: 0,
+            e_lfarlc: 0,
+            e_ovno: 0,
+            e_res: [0; 4],
+            e_oemid: 0,
+            e_oeminfo: 0,
+            e_res2: [0; 10],
+            e_lfanew: size_of::<IMAGE_DOS_HEADER>() as u32,
+        };
+        let () = write_pod(&mut data, 0, &dos_header);
+
+        let mut offset = dos_header.e_lfanew as usize;
+        let () = write_pod(&mut data, offset, &IMAGE_NT_SIGNATURE);
+        offset += size_of::<u32>();
+
+        let dir_count = 16;
+        let symtab_offset = 0x600;
+        let file_header = IMAGE_FILE_HEADER {
+            // IMAGE_FILE_MACHINE_AMD64
+            Machine: 0x8664,
+            NumberOfSections: 2,
+            TimeDateStamp: 0,
+            PointerToSymbolTable: symtab_offset,
+            NumberOfSymbols: 4,
+            SizeOfOptionalHeader: (size_of::<IMAGE_OPTIONAL_HEADER64>()
+                + dir_count * size_of::<IMAGE_DATA_DIRECTORY>())
+                as u16,
+            Characteristics: 0,
+        };
+        let () = write_pod(&mut data, offset, &file_header);
+        offset += size_of::<IMAGE_FILE_HEADER>();
+
+        let opt_header = IMAGE_OPTIONAL_HEADER64 {
+            Magic: IMAGE_NT_OPTIONAL_HDR64_MAGIC,
+            MajorLinkerVersion: 0,
+            MinorLinkerVersion: 0,
+            SizeOfCode: 0x200,
+            SizeOfInitializedData: 0x200,
+            SizeOfUninitializedData: 0,
+            AddressOfEntryPoint: 0x1000,
+            BaseOfCode: 0x1000,
+            ImageBase: 0x140000000,
+            SectionAlignment: 0x1000,
+            FileAlignment: 0x200,
+            MajorOperatingSystemVersion: 6,
+            MinorOperatingSystemVersion: 0,
+            MajorImageVersion: 0,
+            MinorImageVersion: 0,
+            MajorSubsystemVersion: 6,
+            MinorSubsystemVersion: 0,
+            Win32VersionValue: 0,
+            SizeOfImage: 0x3000,
+            SizeOfHeaders: 0x200,
+            CheckSum: 0,
+            Subsystem: 3,
+            DllCharacteristics: 0,
+            SizeOfStackReserve: 0,
+            SizeOfStackCommit: 0,
+            SizeOfHeapReserve: 0,
+            SizeOfHeapCommit: 0,
+            LoaderFlags: 0,
+            NumberOfRvaAndSizes: dir_count as u32,
+        };
+        let () = write_pod(&mut data, offset, &opt_header);
+        offset += size_of::<IMAGE_OPTIONAL_HEADER64>();
+
+        let export_dir = IMAGE_DATA_DIRECTORY {
+            VirtualAddress: 0x2000,
+            Size: 0x80,
+        };
+        let () = write_pod(&mut data, offset, &export_dir);
+        offset += dir_count * size_of::<IMAGE_DATA_DIRECTORY>();
+
+        let text = section_header(b".text", 0x1000, 0x100, 0x200, 0x200);
+        let () = write_pod(&mut data, offset, &text);
+        offset += size_of::<IMAGE_SECTION_HEADER>();
+        let rdata = section_header(b".rdata", 0x2000, 0x100, 0x400, 0x200);
+        let () = write_pod(&mut data, offset, &rdata);
+
+        // The export directory, at RVA 0x2000 (file offset 0x400).
+        let exports = IMAGE_EXPORT_DIRECTORY {
+            Characteristics: 0,
+            TimeDateStamp: 0,
+            MajorVersion: 0,
+            MinorVersion: 0,
+            Name: 0,
+            Base: 1,
+            NumberOfFunctions: 3,
+            NumberOfNames: 3,
+            AddressOfFunctions: 0x2028,
+            AddressOfNames: 0x2034,
+            AddressOfNameOrdinals: 0x2058,
+        };
+        let () = write_pod(&mut data, 0x400, &exports);
+        // The last export is a forwarder, pointing into the export
+        // directory.
+        for (i, addr) in [0x1000_u32, 0x1080, 0x2070].iter().enumerate() {
+            let () = write_pod(&mut data, 0x428 + i * size_of::<u32>(), addr);
+        }
+        for (i, name) in [0x2040_u32, 0x2045, 0x204e].iter().enumerate() {
+            let () = write_pod(&mut data, 0x434 + i * size_of::<u32>(), name);
+        }
+        let () = data[0x440..0x458].copy_from_slice(b"main\0exported\0forwarded\0");
+        for (i, ordinal) in [0_u16, 1, 2].iter().enumerate() {
+            let () = write_pod(&mut data, 0x458 + i * size_of::<u16>(), ordinal);
+        }
+        let () = data[0x470..0x47d].copy_from_slice(b"KERNEL32.Foo\0");
+
+        let function = IMAGE_SYM_DTYPE_FUNCTION << 4;
+        let syms = [
+            coff_sym(*b"main\0\0\0\0", 0x0, function, IMAGE_SYM_CLASS_EXTERNAL, 0),
+            // A section definition symbol with a single auxiliary
+            // record, both of which should be ignored.
+            coff_sym(*b".text\0\0\0", 0x0, 0, 3, 1),
+            coff_sym([0; 8], 0x0, 0, 0, 0),
+            // A symbol with a long name, stored in the string table at
+            // offset 4.
+            coff_sym([0, 0, 0, 0, 4, 0, 0, 0], 0x40, function, 3, 0),
+        ];
+        for sym in &syms {
+            let start = data.len();
+            let () = data.resize(start + size_of::<IMAGE_SYMBOL>(), 0);
+            let () = write_pod(&mut data, start, sym);
+        }
+        let strs = b"a_long_function_name\0";
+        let () = data.extend_from_slice(&(4 + strs.len() as u32).to_le_bytes());
+        let () = data.extend_from_slice(strs);
+        data
+    }
+
+
+    /// Exercise the `Debug` representation of various types.
+    #[test]
+    fn debug_repr() {
+        let data = create_pe();
+        let resolver = PeResolver::from_data(&data, PathBuf::from("/tmp/test.dll")).unwrap();
+        assert_eq!(format!("{resolver:?}"), "PE /tmp/test.dll");
+    }
+
+    /// Check that we can look up symbols in a PE image.
+    #[test]
+    fn symbol_lookup() {
+        let data = create_pe();
+        let resolver = PeResolver::from_data(&data, PathBuf::new()).unwrap();
+        // The section symbol and the forwarder should have been
+        // filtered out and the duplicate `main` merged.
+        assert_eq!(resolver.syms.len(), 3);
+
+        let opts = FindSymOpts::Basic;
+        let sym = resolver.find_sym(0x1000, &opts).unwrap().unwrap();
+        assert_eq!(sym.name, "main");
+        assert_eq!(sym.addr, 0x1000);
+        assert_eq!(sym.size, Some(0x40));
+
+        let sym = resolver.find_sym(0x1041, &opts).unwrap().unwrap();
+        assert_eq!(sym.name, "a_long_function_name");
+        assert_eq!(sym.addr, 0x1040);
+        assert_eq!(sym.size, Some(0x40));
+
+        let sym = resolver.find_sym(0x10ff, &opts).unwrap().unwrap();
+        assert_eq!(sym.name, "exported");
+        assert_eq!(sym.size, Some(0x80));
+
+        // Past the end of the section.
+        let reason = resolver.find_sym(0x1100, &opts).unwrap().unwrap_err();
+        assert_eq!(reason, Reason::UnknownAddr);
+
+        // Before the first symbol.
+        let reason = resolver.find_sym(0xfff, &opts).unwrap().unwrap_err();
+        assert_eq!(reason, Reason::UnknownAddr);
+    }
+
+    /// Check that we can translate file offsets into relative virtual
+    /// addresses.
+    #[test]
+    fn file_offset_translation() {
+        let data = create_pe();
+        let resolver = PeResolver::from_data(&data, PathBuf::new()).unwrap();
+        assert_eq!(
+            resolver.file_offset_to_virt_offset(0x200).unwrap(),
+            Some(0x1000)
+        );
+        assert_eq!(
+            resolver.file_offset_to_virt_offset(0x250).unwrap(),
+            Some(0x1050)
+        );
+        assert_eq!(
+            resolver.file_offset_to_virt_offset(0x400).unwrap(),
+            Some(0x2000)
+        );
+        // Section padding in the file is not mapped.
+        assert_eq!(resolver.file_offset_to_virt_offset(0x300).unwrap(), None);
+        // Headers are not part of any section.
+        assert_eq!(resolver.file_offset_to_virt_offset(0x100).unwrap(), None);
+    }
+
+    /// Check that we fail gracefully on malformed data.
+    #[test]
+    fn malformed_data() {
+        let err = PeResolver::from_data(&[], PathBuf::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let data = create_pe();
+        for len in [8, 70, 100, 300, 0x610] {
+            let err = PeResolver::from_data(&data[..len], PathBuf::new()).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData, "{len}");
+        }
+    }
+}
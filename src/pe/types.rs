@@ -0,0 +1,246 @@
+use crate::util::Pod;
+
+
+/// The magic of the MS-DOS stub header ("MZ").
+pub(crate) const IMAGE_DOS_SIGNATURE: u16 = 0x5a4d;
+/// The signature preceding the COFF file header ("PE\0\0").
+pub(crate) const IMAGE_NT_SIGNATURE: u32 = 0x00004550;
+
+pub(crate) const IMAGE_NT_OPTIONAL_HDR32_MAGIC: u16 = 0x10b;
+pub(crate) const IMAGE_NT_OPTIONAL_HDR64_MAGIC: u16 = 0x20b;
+
+pub(crate) const IMAGE_DIRECTORY_ENTRY_EXPORT: usize = 0;
+
+pub(crate) const IMAGE_SYM_CLASS_EXTERNAL: u8 = 2;
+pub(crate) const IMAGE_SYM_DTYPE_FUNCTION: u16 = 2;
+
+
+/// The MS-DOS stub header at the very start of a PE file.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct IMAGE_DOS_HEADER {
+    pub e_magic: u16,
+    pub e_cblp: u16,
+    pub e_cp: u16,
+    pub e_crlc: u16,
+    pub e_cparhdr: u16,
+    pub e_minalloc: u16,
+    pub e_maxalloc: u16,
+    pub e_ss: u16,
+    pub e_sp: u16,
+    pub e_csum: u16,
+    pub e_ip: u16,
+    pub e_cs: u16,
+    pub e_lfarlc: u16,
+    pub e_ovno: u16,
+    pub e_res: [u16; 4],
+    pub e_oemid: u16,
+    pub e_oeminfo: u16,
+    pub e_res2: [u16; 10],
+    pub e_lfanew: u32,
+}
+
+// SAFETY: `IMAGE_DOS_HEADER` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_DOS_HEADER {}
+
+
+/// The COFF file header.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct IMAGE_FILE_HEADER {
+    pub Machine: u16,
+    pub NumberOfSections: u16,
+    pub TimeDateStamp: u32,
+    pub PointerToSymbolTable: u32,
+    pub NumberOfSymbols: u32,
+    pub SizeOfOptionalHeader: u16,
+    pub Characteristics: u16,
+}
+
+// SAFETY: `IMAGE_FILE_HEADER` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_FILE_HEADER {}
+
+
+/// The optional header of a PE32 image, excluding the trailing data
+/// directories.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct IMAGE_OPTIONAL_HEADER32 {
+    pub Magic: u16,
+    pub MajorLinkerVersion: u8,
+    pub MinorLinkerVersion: u8,
+    pub SizeOfCode: u32,
+    pub SizeOfInitializedData: u32,
+    pub SizeOfUninitializedData: u32,
+    pub AddressOfEntryPoint: u32,
+    pub BaseOfCode: u32,
+    pub BaseOfData: u32,
+    pub ImageBase: u32,
+    pub SectionAlignment: u32,
+    pub FileAlignment: u32,
+    pub MajorOperatingSystemVersion: u16,
+    pub MinorOperatingSystemVersion: u16,
+    pub MajorImageVersion: u16,
+    pub MinorImageVersion: u16,
+    pub MajorSubsystemVersion: u16,
+    pub MinorSubsystemVersion: u16,
+    pub Win32VersionValue: u32,
+    pub SizeOfImage: u32,
+    pub SizeOfHeaders: u32,
+    pub CheckSum: u32,
+    pub Subsystem: u16,
+    pub DllCharacteristics: u16,
+    pub SizeOfStackReserve: u32,
+    pub SizeOfStackCommit: u32,
+    pub SizeOfHeapReserve: u32,
+    pub SizeOfHeapCommit: u32,
+    pub LoaderFlags: u32,
+    pub NumberOfRvaAndSizes: u32,
+}
+
+// SAFETY: `IMAGE_OPTIONAL_HEADER32` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_OPTIONAL_HEADER32 {}
+
+
+/// The optional header of a PE32+ image, excluding the trailing data
+/// directories.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct IMAGE_OPTIONAL_HEADER64 {
+    pub Magic: u16,
+    pub MajorLinkerVersion: u8,
+    pub MinorLinkerVersion: u8,
+    pub SizeOfCode: u32,
+    pub SizeOfInitializedData: u32,
+    pub SizeOfUninitializedData: u32,
+    pub AddressOfEntryPoint: u32,
+    pub BaseOfCode: u32,
+    pub ImageBase: u64,
+    pub SectionAlignment: u32,
+    pub FileAlignment: u32,
+    pub MajorOperatingSystemVersion: u16,
+    pub MinorOperatingSystemVersion: u16,
+    pub MajorImageVersion: u16,
+    pub MinorImageVersion: u16,
+    pub MajorSubsystemVersion: u16,
+    pub MinorSubsystemVersion: u16,
+    pub Win32VersionValue: u32,
+    pub SizeOfImage: u32,
+    pub SizeOfHeaders: u32,
+    pub CheckSum: u32,
+    pub Subsystem: u16,
+    pub DllCharacteristics: u16,
+    pub SizeOfStackReserve: u64,
+    pub SizeOfStackCommit: u64,
+    pub SizeOfHeapReserve: u64,
+    pub SizeOfHeapCommit: u64,
+    pub LoaderFlags: u32,
+    pub NumberOfRvaAndSizes: u32,
+}
+
+// SAFETY: `IMAGE_OPTIONAL_HEADER64` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_OPTIONAL_HEADER64 {}
+
+
+/// An entry of the data directory following the optional header.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct IMAGE_DATA_DIRECTORY {
+    pub VirtualAddress: u32,
+    pub Size: u32,
+}
+
+// SAFETY: `IMAGE_DATA_DIRECTORY` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_DATA_DIRECTORY {}
+
+
+/// An entry of the section table.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct IMAGE_SECTION_HEADER {
+    pub Name: [u8; 8],
+    pub VirtualSize: u32,
+    pub VirtualAddress: u32,
+    pub SizeOfRawData: u32,
+    pub PointerToRawData: u32,
+    pub PointerToRelocations: u32,
+    pub PointerToLinenumbers: u32,
+    pub NumberOfRelocations: u16,
+    pub NumberOfLinenumbers: u16,
+    pub Characteristics: u32,
+}
+
+// SAFETY: `IMAGE_SECTION_HEADER` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_SECTION_HEADER {}
+
+
+/// The export directory, as referenced by the
+/// `IMAGE_DIRECTORY_ENTRY_EXPORT` data directory entry.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct IMAGE_EXPORT_DIRECTORY {
+    pub Characteristics: u32,
+    pub TimeDateStamp: u32,
+    pub MajorVersion: u16,
+    pub MinorVersion: u16,
+    pub Name: u32,
+    pub Base: u32,
+    pub NumberOfFunctions: u32,
+    pub NumberOfNames: u32,
+    pub AddressOfFunctions: u32,
+    pub AddressOfNames: u32,
+    pub AddressOfNameOrdinals: u32,
+}
+
+// SAFETY: `IMAGE_EXPORT_DIRECTORY` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_EXPORT_DIRECTORY {}
+
+
+/// An entry of the COFF symbol table.
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed(2))]
+pub(crate) struct IMAGE_SYMBOL {
+    /// The symbol's name, if at most eight bytes long. Otherwise the
+    /// first four bytes are zero and the last four contain an offset
+    /// into the string table.
+    pub Name: [u8; 8],
+    pub Value: u32,
+    pub SectionNumber: i16,
+    pub Type: u16,
+    pub StorageClass: u8,
+    pub NumberOfAuxSymbols: u8,
+}
+
+// SAFETY: `IMAGE_SYMBOL` is valid for any bit pattern.
+unsafe impl Pod for IMAGE_SYMBOL {}
+
+impl IMAGE_SYMBOL {
+    /// Check whether the symbol describes a function.
+    #[inline]
+    pub fn is_function(&self) -> bool {
+        self.Type >> 4 == IMAGE_SYM_DTYPE_FUNCTION
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::size_of;
+
+
+    /// Check that our type definitions have the sizes mandated by the
+    /// PE/COFF format.
+    #[test]
+    fn type_sizes() {
+        assert_eq!(size_of::<IMAGE_DOS_HEADER>(), 64);
+        assert_eq!(size_of::<IMAGE_FILE_HEADER>(), 20);
+        assert_eq!(size_of::<IMAGE_OPTIONAL_HEADER32>(), 96);
+        assert_eq!(size_of::<IMAGE_OPTIONAL_HEADER64>(), 112);
+        assert_eq!(size_of::<IMAGE_DATA_DIRECTORY>(), 8);
+        assert_eq!(size_of::<IMAGE_SECTION_HEADER>(), 40);
+        assert_eq!(size_of::<IMAGE_EXPORT_DIRECTORY>(), 40);
+        assert_eq!(size_of::<IMAGE_SYMBOL>(), 18);
+    }
+}
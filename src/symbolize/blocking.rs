@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::io;
+use std::panic::catch_unwind;
+use std::panic::resume_unwind;
+use std::panic::AssertUnwindSafe;
+use std::thread;
+use std::thread::JoinHandle;
+
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+use crate::Addr;
+use crate::Error;
+use crate::Result;
+
+use super::Builder;
+use super::Input;
+use super::Source;
+use super::Symbolized;
+use super::Symbolizer;
+
+
+/// The outcome of a symbolization request, with panics captured.
+type Response = Result<Result<Vec<Symbolized<'static>>>, Box<dyn Any + Send>>;
+
+/// A symbolization request sent to the worker thread.
+struct Request {
+    src: Source<'static>,
+    input: Input<Vec<Addr>>,
+    response: oneshot::Sender<Response>,
+}
+
+
+fn worker_error(msg: &'static str) -> Error {
+    Error::from(io::Error::new(io::ErrorKind::Other, msg))
+}
+
+
+fn run_worker(symbolizer: Symbolizer, mut requests: mpsc::UnboundedReceiver<Request>) {
+    while let Some(request) = requests.blocking_recv() {
+        let Request {
+            src,
+            input,
+            response,
+        } = request;
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let input = match &input {
+                Input::AbsAddr(addrs) => Input::AbsAddr(addrs.as_slice()),
+                Input::VirtOffset(addrs) => Input::VirtOffset(addrs.as_slice()),
+                Input::FileOffset(addrs) => Input::FileOffset(addrs.as_slice()),
+            };
+            let syms = symbolizer.symbolize(&src, input)?;
+            let syms = syms.iter().map(Symbolized::to_owned).collect();
+            Ok(syms)
+        }));
+        // The requester may have gone away in the meantime, in which
+        // case there is nobody to report the result to.
+        let _result = response.send(result);
+    }
+}
+
+
+/// A symbolizer for use from `async` contexts.
+///
+/// Symbolization performs synchronous file I/O. An `AsyncSymbolizer`
+/// moves this work onto a dedicated worker thread, which owns a
+/// [`Symbolizer`] configured by the user. Because all requests are
+/// served by this one [`Symbolizer`], cached symbolization data are
+/// shared between them.
+///
+/// Requests are processed one at a time, in the order in which they
+/// were submitted. That is, concurrent calls to
+/// [`symbolize`][Self::symbolize] do not symbolize in parallel. Use
+/// multiple `AsyncSymbolizer` objects if that is desired, at the cost
+/// of each one caching data separately.
+///
+/// The worker thread is shut down when the object is dropped.
+pub struct AsyncSymbolizer {
+    /// The channel used for submitting requests to the worker.
+    requests: Option<mpsc::UnboundedSender<Request>>,
+    /// The worker thread.
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AsyncSymbolizer {
+    /// Create a new [`AsyncSymbolizer`] with the default configuration.
+    pub fn new() -> Result<Self> {
+        Self::with_builder(Symbolizer::builder)
+    }
+
+    /// Create a new [`AsyncSymbolizer`] using a [`Symbolizer`] created
+    /// from the [`Builder`] returned by `builder`.
+    ///
+    /// Because a [`Builder`] may hold functions that can't be sent
+    /// across threads, `builder` is invoked on the worker thread.
+    pub fn with_builder<F>(builder: F) -> Result<Self>
+    where
+        F: FnOnce() -> Builder + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let worker = thread::Builder::new()
+            .name("blazesym-symbolizer".to_string())
+            .spawn(move || run_worker(builder().build(), receiver))?;
+
+        let slf = Self {
+            requests: Some(sender),
+            worker: Some(worker),
+        };
+        Ok(slf)
+    }
+
+    /// Symbolize a list of addresses without blocking the async runtime.
+    ///
+    /// This method reports the same results as
+    /// [`Symbolizer::symbolize`] would. Panics on the worker thread are
+    /// propagated to the caller.
+    pub async fn symbolize(
+        &self,
+        src: Source<'static>,
+        input: Input<Vec<Addr>>,
+    ) -> Result<Vec<Symbolized<'static>>> {
+        let (response, receiver) = oneshot::channel();
+        let request = Request {
+            src,
+            input,
+            response,
+        };
+        let () = self
+            .requests
+            .as_ref()
+            .and_then(|requests| requests.send(request).ok())
+            .ok_or_else(|| worker_error("symbolization worker thread is not running"))?;
+
+        match receiver.await {
+            Ok(Ok(result)) => result,
+            Ok(Err(panic)) => resume_unwind(panic),
+            Err(_err) => Err(worker_error(
+                "symbolization worker thread terminated unexpectedly",
+            )),
+        }
+    }
+}
+
+impl Debug for AsyncSymbolizer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct(stringify!(AsyncSymbolizer)).finish()
+    }
+}
+
+impl Drop for AsyncSymbolizer {
+    fn drop(&mut self) {
+        // Closing the channel causes the worker to exit.
+        let () = drop(self.requests.take());
+        if let Some(worker) = self.worker.take() {
+            // Panics are reported to requesters, so there is nothing
+            // left to do with the result.
+            let _result = worker.join();
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+
+    use crate::symbolize::Elf;
+
+
+    /// Check that `AsyncSymbolizer` and the futures it creates can be
+    /// used with multi-threaded runtimes.
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T>(_value: &T)
+        where
+            T: Send + Sync,
+        {
+        }
+
+        let symbolizer = AsyncSymbolizer::new().unwrap();
+        let () = assert_send_sync(&symbolizer);
+
+        let src = Source::Elf(Elf::new("/does/not/exist"));
+        let future = symbolizer.symbolize(src, Input::VirtOffset(Vec::new()));
+        let () = assert_send_sync(&future);
+    }
+
+    /// Check that the worker uses the symbolizer configuration provided
+    /// by the user.
+    #[tokio::test]
+    async fn custom_configuration() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let src = Source::Elf(Elf::new(path));
+        let symbolizer =
+            AsyncSymbolizer::with_builder(|| Symbolizer::builder().enable_code_info(false))
+                .unwrap();
+        let results = symbolizer
+            .symbolize(src, Input::VirtOffset(vec![0x2000100]))
+            .await
+            .unwrap();
+        let sym = results[0].as_sym().unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert_eq!(sym.code_info, None);
+    }
+}
@@ -0,0 +1,587 @@
+/// A module for working with JIT dump files.
+///
+/// See <https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/tools/perf/Documentation/jitdump-specification.txt>
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs::File;
+use std::mem::size_of;
+use std::path::Path;
+
+use crate::mmap::Mmap;
+use crate::symbolize::CodeInfo;
+use crate::symbolize::FindSymOpts;
+use crate::symbolize::Reason;
+use crate::symbolize::ResolvedSym;
+use crate::symbolize::SrcLang;
+use crate::symbolize::Symbolize;
+use crate::util::find_match_or_lower_bound_by_key;
+use crate::util::Pod;
+use crate::util::ReadRaw as _;
+use crate::Addr;
+use crate::Error;
+use crate::ErrorExt as _;
+use crate::IntoError as _;
+use crate::Pid;
+use crate::Result;
+
+
+/// The magic number identifying a JIT dump file ("JiTD").
+const JITHEADER_MAGIC: u32 = 0x4a695444;
+/// The magic number of a JIT dump file written in the opposite byte
+/// order.
+const JITHEADER_MAGIC_SW: u32 = 0x4454694a;
+
+const JIT_CODE_LOAD: u32 = 0;
+const JIT_CODE_MOVE: u32 = 1;
+const JIT_CODE_DEBUG_INFO: u32 = 2;
+
+
+/// The header at the start of a JIT dump file.
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C)]
+struct FileHeader {
+    magic: u32,
+    version: u32,
+    total_size: u32,
+    elf_mach: u32,
+    pad1: u32,
+    pid: u32,
+    timestamp: u64,
+    flags: u64,
+}
+
+// SAFETY: `FileHeader` is valid for any bit pattern.
+unsafe impl Pod for FileHeader {}
+
+
+/// The header common to all records.
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C)]
+struct RecordHeader {
+    id: u32,
+    total_size: u32,
+    timestamp: u64,
+}
+
+// SAFETY: `RecordHeader` is valid for any bit pattern.
+unsafe impl Pod for RecordHeader {}
+
+
+/// The fixed size part of a `JIT_CODE_LOAD` record, which is followed
+/// by the function name and the native code.
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C)]
+struct CodeLoad {
+    pid: u32,
+    tid: u32,
+    vma: u64,
+    code_addr: u64,
+    code_size: u64,
+    code_index: u64,
+}
+
+// SAFETY: `CodeLoad` is valid for any bit pattern.
+unsafe impl Pod for CodeLoad {}
+
+
+/// A `JIT_CODE_MOVE` record.
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C)]
+struct CodeMove {
+    pid: u32,
+    tid: u32,
+    vma: u64,
+    old_code_addr: u64,
+    new_code_addr: u64,
+    code_size: u64,
+    code_index: u64,
+}
+
+// SAFETY: `CodeMove` is valid for any bit pattern.
+unsafe impl Pod for CodeMove {}
+
+
+/// A source line entry of a function.
+#[derive(Debug, Eq, PartialEq)]
+struct LineEntry {
+    /// The offset of the first instruction belonging to the line,
+    /// relative to the start of the function.
+    offset: u64,
+    /// The line number.
+    line: u32,
+    /// The path to the source file.
+    file: Box<str>,
+}
+
+impl LineEntry {
+    fn to_code_info(&self) -> CodeInfo<'_> {
+        let path = Path::new(&*self.file);
+        let (dir, file) = match (path.parent(), path.file_name()) {
+            (Some(dir), Some(file)) if !dir.as_os_str().is_empty() => {
+                (Some(Cow::Borrowed(dir)), Cow::Borrowed(file))
+            }
+            _ => (None, Cow::Borrowed(path.as_os_str())),
+        };
+
+        CodeInfo {
+            dir,
+            file,
+            line: Some(self.line),
+            column: None,
+            discriminator: None,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq)]
+struct Function {
+    /// The name of the function.
+    name: Box<str>,
+    /// The function's start address.
+    addr: Addr,
+    /// The size of the function.
+    size: usize,
+    /// The unique index the JIT assigned to the function.
+    index: u64,
+    /// Source line information, sorted by offset.
+    lines: Box<[LineEntry]>,
+}
+
+
+/// Parse the line entries of a `JIT_CODE_DEBUG_INFO` record.
+fn parse_debug_info(mut data: &[u8]) -> Result<(Addr, Vec<(Addr, u32, Box<str>)>)> {
+    let code_addr = data
+        .read_u64()
+        .ok_or_invalid_data(|| "failed to read debug info code address")?;
+    let count = data
+        .read_u64()
+        .ok_or_invalid_data(|| "failed to read debug info entry count")?;
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let addr = data
+            .read_u64()
+            .ok_or_invalid_data(|| "failed to read debug entry address")?;
+        let line = data
+            .read_u32()
+            .ok_or_invalid_data(|| "failed to read debug entry line")?;
+        let _discrim = data
+            .read_u32()
+            .ok_or_invalid_data(|| "failed to read debug entry discriminator")?;
+        let file = data
+            .read_cstr()
+            .ok_or_invalid_data(|| "failed to read debug entry file name")?
+            .to_str()
+            .map_err(Error::with_invalid_data)
+            .context("debug entry file name is not valid UTF-8")?;
+        let () = entries.push((addr, line, Box::from(file)));
+    }
+    Ok((code_addr, entries))
+}
+
+
+fn parse_jit_dump(data: &[u8]) -> Result<Vec<Function>> {
+    let header = {
+        let mut peek = data;
+        peek.read_pod::<FileHeader>()
+            .ok_or_invalid_data(|| "failed to read JIT dump header")?
+    };
+    match header.magic {
+        JITHEADER_MAGIC => (),
+        JITHEADER_MAGIC_SW => {
+            return Err(Error::with_unsupported(
+                "JIT dump files in non-native byte order are not supported",
+            ))
+        }
+        magic => {
+            return Err(Error::with_invalid_data(format!(
+                "encountered unexpected JIT dump magic ({magic:#x})"
+            )))
+        }
+    }
+
+    let mut records = data
+        .get(header.total_size as usize..)
+        .ok_or_invalid_data(|| "JIT dump header size out of bounds")?;
+    let mut functions = Vec::new();
+    // Debug information is emitted before the load record of the
+    // function it belongs to, so we have to stash it until then.
+    let mut debug_infos = HashMap::new();
+
+    while !records.is_empty() {
+        let record_header = {
+            let mut peek = records;
+            peek.read_pod::<RecordHeader>()
+        };
+        let record_header = if let Some(record_header) = record_header {
+            record_header
+        } else {
+            // The JIT may still be writing to the file, so a
+            // truncated trailing record is to be expected.
+            break
+        };
+        if (record_header.total_size as usize) < size_of::<RecordHeader>() {
+            return Err(Error::with_invalid_data(format!(
+                "JIT dump record has invalid size ({})",
+                record_header.total_size
+            )))
+        }
+        let mut record = if let Some(record) = records.read_slice(record_header.total_size as usize)
+        {
+            record
+        } else {
+            break
+        };
+        let () = record
+            .advance(size_of::<RecordHeader>())
+            .ok_or_invalid_data(|| "failed to skip JIT dump record header")?;
+
+        match record_header.id {
+            JIT_CODE_LOAD => {
+                let load = record
+                    .read_pod::<CodeLoad>()
+                    .ok_or_invalid_data(|| "failed to read JIT code load record")?;
+                let name = record
+                    .read_cstr()
+                    .ok_or_invalid_data(|| "failed to read JIT function name")?
+                    .to_str()
+                    .map_err(Error::with_invalid_data)
+                    .context("JIT function name is not valid UTF-8")?;
+                let entries = debug_infos.remove(&load.code_addr).unwrap_or_default();
+                let mut lines = entries
+                    .into_iter()
+                    .filter_map(|(addr, line, file)| {
+                        let offset = Addr::checked_sub(addr, load.code_addr)?;
+                        (offset < load.code_size).then_some(LineEntry { offset, line, file })
+                    })
+                    .collect::<Vec<_>>();
+                let () = lines.sort_by_key(|entry| entry.offset);
+
+                let function = Function {
+                    name: Box::from(name),
+                    addr: load.code_addr,
+                    size: usize::try_from(load.code_size).unwrap_or(usize::MAX),
+                    index: load.code_index,
+                    lines: lines.into_boxed_slice(),
+                };
+                let () = functions.push(function);
+            }
+            JIT_CODE_MOVE => {
+                let move_ = record
+                    .read_pod::<CodeMove>()
+                    .ok_or_invalid_data(|| "failed to read JIT code move record")?;
+                if let Some(function) = functions.iter_mut().rev().find(|function| {
+                    function.index == move_.code_index && function.addr == move_.old_code_addr
+                }) {
+                    function.addr = move_.new_code_addr;
+                }
+            }
+            JIT_CODE_DEBUG_INFO => {
+                let (code_addr, entries) = parse_debug_info(record)?;
+                let _prev = debug_infos.insert(code_addr, entries);
+            }
+            // Other records (e.g., unwinding information) are not
+            // relevant to us.
+            _ => (),
+        }
+    }
+
+    // Code memory may be reused by the JIT over time, in which case the
+    // most recently loaded function is the one of relevance. Sorting is
+    // stable, so by reversing first we end up with the most recent
+    // function at a given address first.
+    let () = functions.reverse();
+    let () = functions.sort_by_key(|function| function.addr);
+    let () = functions.dedup_by_key(|function| function.addr);
+    Ok(functions)
+}
+
+
+pub(crate) struct JitDump {
+    /// All functions found in the JIT dump, ordered by start address.
+    functions: Vec<Function>,
+}
+
+impl JitDump {
+    /// Check whether the file at `path` is a JIT dump for the process
+    /// with the given `pid`, judging by its name.
+    pub(crate) fn is_jit_dump(path: &Path, pid: Pid) -> bool {
+        let pid = pid.resolve();
+        path.file_name()
+            .map(|name| name == format!("jit-{pid}.dump").as_str())
+            .unwrap_or(false)
+    }
+
+    /// Load the [`JitDump`] from the provided file.
+    pub(crate) fn from_file(path: &Path, file: &File) -> Result<Self> {
+        let mmap = Mmap::map(file)
+            .with_context(|| format!("failed to mmap JIT dump `{}`", path.display()))?;
+        let functions = parse_jit_dump(&mmap)
+            .with_context(|| format!("failed to parse JIT dump `{}`", path.display()))?;
+
+        let slf = Self { functions };
+        Ok(slf)
+    }
+}
+
+impl Symbolize for JitDump {
+    fn find_sym(&self, addr: Addr, _opts: &FindSymOpts) -> Result<Result<ResolvedSym<'_>, Reason>> {
+        let result = find_match_or_lower_bound_by_key(&self.functions, addr, |f| f.addr)
+            .and_then(|idx| self.functions.get(idx))
+            .filter(|function| addr - function.addr < function.size as Addr);
+
+        match result {
+            Some(function) => {
+                let Function {
+                    name,
+                    addr: fn_addr,
+                    size,
+                    index: _,
+                    lines,
+                } = function;
+
+                let offset = addr - fn_addr;
+                let idx = lines.partition_point(|entry| entry.offset <= offset);
+                let code_info = idx
+                    .checked_sub(1)
+                    .and_then(|idx| lines.get(idx))
+                    .map(LineEntry::to_code_info);
+
+                let sym = ResolvedSym {
+                    name,
+                    addr: *fn_addr,
+                    size: Some(*size),
+                    lang: SrcLang::Unknown,
+                    section: None,
+                    code_info,
+                    inlined: Box::new([]),
+                };
+                Ok(Ok(sym))
+            }
+            None => Ok(Err(Reason::UnknownAddr)),
+        }
+    }
+}
+
+impl Debug for JitDump {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("JitDump").finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::OsStr;
+    use std::io::Write as _;
+    use tempfile::NamedTempFile;
+
+    use crate::test_helper::push_pod;
+    use crate::ErrorKind;
+
+
+    const JIT_CODE_UNWINDING_INFO: u32 = 4;
+
+
+    /// Append a record with the given `id` and `payload` to `data`.
+    fn push_record(data: &mut Vec<u8>, id: u32, payload: &[u8]) {
+        let header = RecordHeader {
+            id,
+            total_size: (size_of::<RecordHeader>() + payload.len()) as u32,
+            timestamp: 0,
+        };
+        let () = push_pod(data, &header);
+        let () = data.extend_from_slice(payload);
+    }
+
+    fn code_load(name: &str, code_addr: u64, code_size: u64, code_index: u64) -> Vec<u8> {
+        let mut payload = Vec::new();
+        let load = CodeLoad {
+            pid: 1234,
+            tid: 1234,
+            vma: code_addr,
+            code_addr,
+            code_size,
+            code_index,
+        };
+        let () = push_pod(&mut payload, &load);
+        let () = payload.extend_from_slice(name.as_bytes());
+        let () = payload.push(b'\0');
+        // The native code, which we don't care about.
+        let () = payload.resize(payload.len() + code_size as usize, 0xcc);
+        payload
+    }
+
+    /// Create a JIT dump containing a couple of functions, one with
+    /// source line information and one that got moved.
+    fn create_jit_dump() -> Vec<u8> {
+        let mut data = Vec::new();
+        let header = FileHeader {
+            magic: JITHEADER_MAGIC,
+            version: 1,
+            total_size: size_of::<FileHeader>() as u32,
+            // EM_X86_64
+            elf_mach: 62,
+            pad1: 0,
+            pid: 1234,
+            timestamp: 0,
+            flags: 0,
+        };
+        let () = push_pod(&mut data, &header);
+
+        let mut debug_info = Vec::new();
+        let () = push_pod(&mut debug_info, &0x1000_u64);
+        let () = push_pod(&mut debug_info, &2_u64);
+        for (addr, line) in [(0x1010_u64, 12_u32), (0x1000, 10)] {
+            let () = push_pod(&mut debug_info, &addr);
+            let () = push_pod(&mut debug_info, &line);
+            let () = push_pod(&mut debug_info, &0_u32);
+            let () = debug_info.extend_from_slice(b"/src/app/Main.java\0");
+        }
+        let () = push_record(&mut data, JIT_CODE_DEBUG_INFO, &debug_info);
+        let () = push_record(
+            &mut data,
+            JIT_CODE_LOAD,
+            &code_load("Main::run", 0x1000, 0x40, 1),
+        );
+        let () = push_record(
+            &mut data,
+            JIT_CODE_LOAD,
+            &code_load("Main::helper", 0x2000, 0x20, 2),
+        );
+        let () = push_record(&mut data, JIT_CODE_UNWINDING_INFO, &[0; 24]);
+
+        let mut move_ = Vec::new();
+        let record = CodeMove {
+            pid: 1234,
+            tid: 1234,
+            vma: 0x3000,
+            old_code_addr: 0x2000,
+            new_code_addr: 0x3000,
+            code_size: 0x20,
+            code_index: 2,
+        };
+        let () = push_pod(&mut move_, &record);
+        let () = push_record(&mut data, JIT_CODE_MOVE, &move_);
+        data
+    }
+
+
+    /// Exercise the `Debug` representation of various types.
+    #[test]
+    fn debug_repr() {
+        let data = create_jit_dump();
+        let mut file = NamedTempFile::new().unwrap();
+        let () = file.write_all(&data).unwrap();
+        let jit_dump = JitDump::from_file(file.path(), file.as_file()).unwrap();
+        assert_eq!(format!("{jit_dump:?}"), "JitDump");
+        assert_ne!(format!("{:?}", jit_dump.functions[0]), "");
+    }
+
+    /// Check that we correctly recognize JIT dump file names.
+    #[test]
+    fn jit_dump_file_names() {
+        let pid = Pid::from(1234);
+        assert!(JitDump::is_jit_dump(
+            Path::new("/home/user/.debug/jit/java-jit-20240101.abc/jit-1234.dump"),
+            pid
+        ));
+        assert!(!JitDump::is_jit_dump(Path::new("/tmp/jit-4321.dump"), pid));
+        assert!(!JitDump::is_jit_dump(Path::new("/tmp/perf-1234.map"), pid));
+    }
+
+    /// Make sure that we can parse a valid JIT dump successfully.
+    #[test]
+    fn jit_dump_parsing() {
+        let data = create_jit_dump();
+        let functions = parse_jit_dump(&data).unwrap();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(&*functions[0].name, "Main::run");
+        assert_eq!(functions[0].lines.len(), 2);
+        assert_eq!(functions[0].lines[0].offset, 0);
+        assert_eq!(functions[0].lines[0].line, 10);
+        assert_eq!(&*functions[1].name, "Main::helper");
+        assert_eq!(functions[1].addr, 0x3000);
+
+        // A truncated trailing record should just be ignored.
+        let functions = parse_jit_dump(&data[..data.len() - 8]).unwrap();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[1].addr, 0x2000);
+    }
+
+    /// Check that we fail parsing JIT dumps with unexpected magic.
+    #[test]
+    fn jit_dump_parsing_errors() {
+        let err = parse_jit_dump(&[]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut data = create_jit_dump();
+        let () = data[..4].copy_from_slice(&JITHEADER_MAGIC_SW.to_ne_bytes());
+        let err = parse_jit_dump(&data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let () = data[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        let err = parse_jit_dump(&data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that we can load a JIT dump and use it to symbolize
+    /// addresses.
+    #[test]
+    fn jit_dump_symbolization() {
+        let data = create_jit_dump();
+        let mut file = NamedTempFile::new().unwrap();
+        let () = file.write_all(&data).unwrap();
+        let jit_dump = JitDump::from_file(file.path(), file.as_file()).unwrap();
+
+        let sym = jit_dump
+            .find_sym(0x1004, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "Main::run");
+        assert_eq!(sym.addr, 0x1000);
+        assert_eq!(sym.size, Some(0x40));
+        let code_info = sym.code_info.unwrap();
+        assert_eq!(code_info.dir, Some(Cow::Borrowed(Path::new("/src/app"))));
+        assert_eq!(code_info.file, OsStr::new("Main.java"));
+        assert_eq!(code_info.line, Some(10));
+
+        let sym = jit_dump
+            .find_sym(0x103f, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "Main::run");
+        assert_eq!(sym.code_info.unwrap().line, Some(12));
+
+        let sym = jit_dump
+            .find_sym(0x3010, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "Main::helper");
+        assert_eq!(sym.code_info, None);
+
+        // The function got moved away from this address.
+        let reason = jit_dump
+            .find_sym(0x2010, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(reason, Reason::UnknownAddr);
+
+        let reason = jit_dump
+            .find_sym(0x1040, &FindSymOpts::Basic)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(reason, Reason::UnknownAddr);
+    }
+}
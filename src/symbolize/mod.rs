@@ -91,6 +91,9 @@
 //! [`gsym-in-apk`](https://github.com/libbpf/blazesym/blob/main/examples/gsym-in-apk)
 //! example, which illustrates the basic workflow.
 
+#[cfg(feature = "tokio")]
+mod blocking;
+mod jit_dump;
 mod perf_map;
 mod source;
 mod symbolizer;
@@ -102,11 +105,16 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::path::Path;
+use std::path::PathBuf;
 use std::str;
 
+cfg_tokio! {
+    pub use blocking::AsyncSymbolizer;
+}
 cfg_apk! {
     pub use source::Apk;
 }
+pub use source::Archive;
 cfg_breakpad! {
     pub use source::Breakpad;
 }
@@ -117,6 +125,14 @@ cfg_gsym! {
     pub use source::GsymFile;
 }
 pub use source::Kernel;
+cfg_macho! {
+    pub use source::MachO;
+}
+pub use source::MapEntry;
+pub use source::MappedElf;
+cfg_pe! {
+    pub use source::Pe;
+}
 pub use source::Process;
 pub use source::Source;
 
@@ -124,9 +140,12 @@ cfg_apk! {
     pub use symbolizer::ApkDispatch;
     pub use symbolizer::ApkMemberInfo;
 }
+pub use symbolizer::AddrFilter;
 pub use symbolizer::Builder;
+pub use symbolizer::Demangle;
 pub use symbolizer::ProcessDispatch;
 pub use symbolizer::ProcessMemberInfo;
+pub use symbolizer::Progress;
 pub use symbolizer::Symbolizer;
 
 // Strictly speaking these types are applicable to the entire crate, but right
@@ -137,6 +156,7 @@ pub use crate::maps::PathName as ProcessMemberType;
 
 use crate::normalize;
 use crate::Addr;
+use crate::BuildId;
 use crate::Result;
 
 
@@ -152,6 +172,13 @@ pub enum FindSymOpts {
     /// Look up symbol data, source code location information, and inlined
     /// function information.
     CodeInfoAndInlined,
+    /// Look up symbol data, source code location information, and
+    /// information about at most the given number of inlined functions,
+    /// starting with the outermost one.
+    ///
+    /// The source code location of the address itself is reported as
+    /// part of the last inlined function reported.
+    CodeInfoAndInlinedUpTo(usize),
 }
 
 impl FindSymOpts {
@@ -159,7 +186,7 @@ impl FindSymOpts {
     pub(crate) fn code_info(&self) -> bool {
         match self {
             Self::Basic => false,
-            Self::CodeInfo | Self::CodeInfoAndInlined => true,
+            Self::CodeInfo | Self::CodeInfoAndInlined | Self::CodeInfoAndInlinedUpTo(..) => true,
         }
     }
 
@@ -167,7 +194,17 @@ impl FindSymOpts {
     pub(crate) fn inlined_fns(&self) -> bool {
         match self {
             Self::Basic | Self::CodeInfo => false,
-            Self::CodeInfoAndInlined => true,
+            Self::CodeInfoAndInlined | Self::CodeInfoAndInlinedUpTo(..) => true,
+        }
+    }
+
+    /// Retrieve the maximum number of inlined functions to look up.
+    #[inline]
+    pub(crate) fn max_inline_depth(&self) -> usize {
+        match self {
+            Self::Basic | Self::CodeInfo => 0,
+            Self::CodeInfoAndInlined => usize::MAX,
+            Self::CodeInfoAndInlinedUpTo(depth) => *depth,
         }
     }
 }
@@ -263,6 +300,12 @@ pub struct CodeInfo<'src> {
     /// The column number of the symbolized instruction in the source
     /// code.
     pub column: Option<u16>,
+    /// The DWARF discriminator of the symbolized instruction.
+    ///
+    /// Discriminators distinguish between multiple basic blocks
+    /// associated with the same source code line. Only sources with
+    /// DWARF debug information may report one.
+    pub discriminator: Option<u32>,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -290,6 +333,7 @@ impl CodeInfo<'_> {
             file: Cow::Owned(self.file.to_os_string()),
             line: self.line,
             column: self.column,
+            discriminator: self.discriminator,
             _non_exhaustive: (),
         }
     }
@@ -301,6 +345,17 @@ impl CodeInfo<'_> {
 pub struct InlinedFn<'src> {
     /// The symbol name of the inlined function.
     pub name: Cow<'src, str>,
+    /// The start address of the inlined code covering the symbolized
+    /// address, if known.
+    ///
+    /// An inlined function instance may span multiple address ranges,
+    /// in which case this is the start of the range containing the
+    /// symbolized address. Currently only DWARF based sources report
+    /// this information.
+    pub addr: Option<Addr>,
+    /// The byte offset of the symbolized address from `addr`, if
+    /// known.
+    pub offset: Option<usize>,
     /// Source code location information for the call to the function.
     pub code_info: Option<CodeInfo<'src>>,
     /// The struct is non-exhaustive and open to extension.
@@ -308,6 +363,41 @@ pub struct InlinedFn<'src> {
     pub _non_exhaustive: (),
 }
 
+impl InlinedFn<'_> {
+    /// Convert this object into one with all references converted into
+    /// guaranteed owned (i.e., heap allocated) members.
+    pub fn to_owned(&self) -> InlinedFn<'static> {
+        InlinedFn {
+            name: Cow::Owned(self.name.to_string()),
+            addr: self.addr,
+            offset: self.offset,
+            code_info: self.code_info.as_ref().map(CodeInfo::to_owned),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+
+/// A single frame of a symbolized address, as reported by
+/// [`Sym::frames`].
+///
+/// A frame represents either the function containing the symbolized
+/// address or one of the functions inlined into it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame<'sym> {
+    /// The name of the function.
+    pub name: &'sym str,
+    /// The start address of the function or inlined code, if known.
+    pub addr: Option<Addr>,
+    /// Source code location information for the instruction executing
+    /// inside the function, i.e., either the symbolized address itself
+    /// or the call to the next inlined function.
+    pub code_info: Option<&'sym CodeInfo<'sym>>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
 
 /// The source code language from which a symbol originates.
 #[derive(Clone, Copy, Default, Debug, PartialEq)]
@@ -323,6 +413,21 @@ pub enum SrcLang {
 }
 
 
+/// The ELF symbol table(s) to consult when symbolizing.
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SymSource {
+    /// Consult `.symtab` and fall back to `.dynsym` for addresses not
+    /// covered by it.
+    #[default]
+    Auto,
+    /// Only consult `.symtab`.
+    SymTab,
+    /// Only consult `.dynsym`.
+    DynSymOnly,
+}
+
+
 /// A type representing a symbol as produced by a [`Resolve`] object.
 #[derive(Debug, PartialEq)]
 pub struct ResolvedSym<'src> {
@@ -334,6 +439,8 @@ pub struct ResolvedSym<'src> {
     pub size: Option<usize>,
     /// The source code language from which the symbol originates.
     pub lang: SrcLang,
+    /// The name of the section in which the symbol resides, if known.
+    pub section: Option<&'src str>,
     /// Source code location information.
     pub code_info: Option<CodeInfo<'src>>,
     /// Inlined function information.
@@ -346,6 +453,12 @@ pub struct ResolvedSym<'src> {
 pub struct Sym<'src> {
     /// The symbol name that an address belongs to.
     pub name: Cow<'src, str>,
+    /// The symbol's original (mangled) name, if `name` was produced by
+    /// demangling it.
+    ///
+    /// This member is `None` if demangling is disabled or did not
+    /// change the name.
+    pub mangled_name: Option<Cow<'src, str>>,
     /// The address at which the symbol is located (i.e., its "start").
     ///
     /// This is the "normalized" address of the symbol, as present in
@@ -364,6 +477,41 @@ pub struct Sym<'src> {
     pub offset: usize,
     /// The symbol's size, if available.
     pub size: Option<usize>,
+    /// The source code language from which the symbol originates.
+    ///
+    /// Only some symbolization sources (e.g., DWARF) carry language
+    /// information. For all others the language is reported as
+    /// [`SrcLang::Unknown`].
+    pub lang: SrcLang,
+    /// The name of the section (e.g., `.text` or `.plt`) in which the
+    /// symbol resides.
+    ///
+    /// Only ELF based sources carry section information. For all
+    /// others this member is `None`.
+    pub section: Option<Cow<'src, str>>,
+    /// The label closest to the symbolized address inside the symbol,
+    /// if reporting of labels is enabled (see
+    /// [`Builder::enable_labels`]) and such a label exists.
+    ///
+    /// Labels are named locations inside a function (e.g., the
+    /// `STT_NOTYPE` symbols found in hand-written assembly). Only ELF
+    /// based sources report labels.
+    pub label: Option<Cow<'src, str>>,
+    /// The path to the object file (executable, shared object, ...) in
+    /// which the symbol resides.
+    ///
+    /// This member is currently only set for ELF files symbolized
+    /// through [`Source::Elf`], [`Source::MappedElf`], or
+    /// [`Source::Archive`] (using the `archive(member)` notation), as
+    /// well as for file backed ELF mappings symbolized through
+    /// [`Source::Process`]. For all others, including the vDSO of a
+    /// process, it is `None`.
+    pub module: Option<Cow<'src, Path>>,
+    /// The build ID of the object file in which the symbol resides, if
+    /// it carries one.
+    ///
+    /// Availability is the same as for [`Sym::module`].
+    pub build_id: Option<BuildId<'src>>,
     /// Source code location information for the symbol.
     pub code_info: Option<CodeInfo<'src>>,
     /// Inlined function information, if requested and available.
@@ -378,6 +526,243 @@ pub struct Sym<'src> {
     /// contains an inlined call to `h`, the symbols will be reported in the
     /// order `f`, `g`, `h`.
     pub inlined: Box<[InlinedFn<'src>]>,
+    /// Other symbols covering the address, if reporting of all symbols
+    /// is enabled (see [`Builder::enable_all_syms`]).
+    ///
+    /// A binary may contain multiple symbols at a given address, e.g.,
+    /// a function and an alias of it. Only symbols starting at the same
+    /// address as the reported one are considered. Only ELF based
+    /// sources currently report such symbols, ordered by binding
+    /// strength and then by name.
+    pub other_syms: Box<[Sym<'src>]>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl<'src> Sym<'src> {
+    /// Retrieve the source code location of the symbolized address
+    /// itself, i.e., the location in the most deeply inlined function
+    /// if the address falls into inlined code.
+    ///
+    /// With inlined functions present, [`Sym::code_info`] refers to the
+    /// location of the call to the first inlined function, while each
+    /// [`InlinedFn::code_info`] refers to the location of the call to
+    /// the next one. Only the last inlined function's location is
+    /// that of the address in question. This method takes care of
+    /// these details, falling back to less deeply nested locations if
+    /// more deeply nested ones are not available.
+    pub fn innermost_location(&self) -> Option<&CodeInfo<'src>> {
+        self.inlined
+            .iter()
+            .rev()
+            .find_map(|inlined_fn| inlined_fn.code_info.as_ref())
+            .or(self.code_info.as_ref())
+    }
+
+    /// Check whether the symbolized address falls into code inlined
+    /// into the function represented by this symbol.
+    ///
+    /// If `true`, [`Sym::inlined`] describes the chain of inlined
+    /// functions the address belongs to, with the last entry being the
+    /// innermost one. If `false`, the address was resolved to the flat
+    /// symbol only.
+    #[inline]
+    pub fn is_inlined_site(&self) -> bool {
+        !self.inlined.is_empty()
+    }
+
+    /// Retrieve the source code location of the inlined call inside
+    /// the function represented by this symbol, if the symbolized
+    /// address falls into inlined code.
+    ///
+    /// `None` is returned if no inlined functions are reported for the
+    /// address, in which case [`Sym::code_info`] is the location of
+    /// the address itself.
+    pub fn callsite_location(&self) -> Option<&CodeInfo<'src>> {
+        if self.inlined.is_empty() {
+            None
+        } else {
+            self.code_info.as_ref()
+        }
+    }
+
+    /// Retrieve the symbol and its inlined functions as a flat list of
+    /// frames.
+    ///
+    /// The symbol itself is reported first, followed by all inlined
+    /// functions from the outermost to the innermost one. The source
+    /// code location of each frame is that of the instruction
+    /// executing inside it.
+    pub fn frames(&self) -> impl Iterator<Item = Frame<'_>> {
+        let sym = Frame {
+            name: &self.name,
+            addr: Some(self.addr),
+            code_info: self.code_info.as_ref(),
+            _non_exhaustive: (),
+        };
+        let inlined = self.inlined.iter().map(|inlined_fn| Frame {
+            name: &inlined_fn.name,
+            addr: inlined_fn.addr,
+            code_info: inlined_fn.code_info.as_ref(),
+            _non_exhaustive: (),
+        });
+        [sym].into_iter().chain(inlined)
+    }
+
+    /// Convert this object into one with all references converted into
+    /// guaranteed owned (i.e., heap allocated) members.
+    pub fn to_owned(&self) -> Sym<'static> {
+        Sym {
+            name: Cow::Owned(self.name.to_string()),
+            mangled_name: self
+                .mangled_name
+                .as_ref()
+                .map(|name| Cow::Owned(name.to_string())),
+            addr: self.addr,
+            offset: self.offset,
+            size: self.size,
+            lang: self.lang,
+            section: self
+                .section
+                .as_ref()
+                .map(|section| Cow::Owned(section.to_string())),
+            label: self
+                .label
+                .as_ref()
+                .map(|label| Cow::Owned(label.to_string())),
+            module: self
+                .module
+                .as_ref()
+                .map(|module| Cow::Owned(module.to_path_buf())),
+            build_id: self
+                .build_id
+                .as_ref()
+                .map(|build_id| Cow::Owned(build_id.to_vec())),
+            code_info: self.code_info.as_ref().map(CodeInfo::to_owned),
+            inlined: self.inlined.iter().map(InlinedFn::to_owned).collect(),
+            other_syms: self.other_syms.iter().map(Sym::to_owned).collect(),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+
+/// The format of a symbolization source.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SrcFormat {
+    /// The source is a Breakpad file.
+    Breakpad,
+    /// The source is an ELF file.
+    Elf,
+    /// The source is Gsym data.
+    Gsym,
+    /// The source is a Mach-O file.
+    MachO,
+    /// The source is a PE file.
+    Pe,
+}
+
+
+/// The availability of debug information for a symbolization source,
+/// as reported as part of [`SourceInfo`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum DebugInfo {
+    /// The source contains DWARF debug information itself.
+    Present,
+    /// The source does not contain DWARF debug information, but
+    /// references a separate debug information file via a debug link
+    /// (`.gnu_debuglink`).
+    ///
+    /// Debug links are only honored during symbolization with the
+    /// `dwarf` feature enabled.
+    External {
+        /// The file name of the debug information file.
+        debug_link: PathBuf,
+        /// The CRC32 checksum of the debug information file.
+        checksum: u32,
+        /// The source's build ID, if present, which can be used to
+        /// retrieve the debug information file (e.g., from a
+        /// `debuginfod` server).
+        build_id: Option<BuildId<'static>>,
+    },
+    /// No debug information is available.
+    None,
+}
+
+
+/// Meta data about a symbolization source, as reported by
+/// [`Symbolizer::inspect`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceInfo {
+    /// The format of the source.
+    pub format: SrcFormat,
+    /// The machine architecture the source was built for, as an ELF
+    /// `e_machine` value (e.g., `62` for x86-64), if known.
+    pub arch: Option<u16>,
+    /// Whether the source contains a regular symbol table
+    /// (`.symtab`).
+    pub has_symtab: bool,
+    /// Whether the source contains a dynamic symbol table (`.dynsym`).
+    pub has_dynsym: bool,
+    /// Whether the source contains DWARF line number information
+    /// (`.debug_line`).
+    ///
+    /// Note that only the source itself is checked; debug information
+    /// residing in a separate file (referenced by a debug link, for
+    /// example) is not taken into account.
+    pub has_debug_line: bool,
+    /// The source's build ID, if present.
+    pub build_id: Option<BuildId<'static>>,
+    /// The availability of DWARF debug information.
+    ///
+    /// Only ELF sources can carry or reference DWARF debug
+    /// information. For all others this member is [`DebugInfo::None`].
+    pub debug_info: DebugInfo,
+    /// Statistics about the symbol table used for symbolization, if
+    /// applicable to the source format.
+    pub sym_stats: Option<SymStats>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// An ELF symbol table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SymTable {
+    /// The regular symbol table (`.symtab`).
+    SymTab,
+    /// The dynamic symbol table (`.dynsym`).
+    DynSym,
+}
+
+
+/// Statistics about the symbol table of an ELF file, as reported as
+/// part of [`SourceInfo`].
+///
+/// These are mostly meant for diagnostic purposes, e.g., to help
+/// understand why addresses of a source could not be symbolized.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SymStats {
+    /// The symbol table consulted for symbolization, if any usable one
+    /// is present.
+    ///
+    /// `.symtab` is preferred, with `.dynsym` acting as a fallback,
+    /// subject to the configured [`SymSource`].
+    pub table: Option<SymTable>,
+    /// The total number of symbols in the table.
+    pub total: usize,
+    /// The number of function symbols (`STT_FUNC` and
+    /// `STT_GNU_IFUNC`) in the table.
+    pub funcs: usize,
+    /// The number of undefined symbols (`SHN_UNDEF`) in the table.
+    pub undefined: usize,
+    /// Whether the file is stripped, i.e., lacks a `.symtab` section.
+    pub stripped: bool,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -409,6 +794,15 @@ pub enum Reason {
     Unsupported,
     /// The address could not be found in the symbolization source.
     UnknownAddr,
+    /// The symbolization source's symbol table is corrupt.
+    ///
+    /// The source could be parsed, but the symbol table that would
+    /// have been consulted for the address is malformed.
+    CorruptSymtab,
+    /// The address belongs to an anonymous executable memory mapping
+    /// (e.g., JIT compiled code) for which no perf map or JIT dump
+    /// was available.
+    AnonMapping,
 }
 
 impl Reason {
@@ -422,6 +816,8 @@ impl Reason {
             Self::MissingSyms => b"symbolization source has no or no relevant symbols\0",
             Self::Unsupported => b"address belongs to unsupported entity\0",
             Self::UnknownAddr => b"address not found in symbolization source\0",
+            Self::CorruptSymtab => b"symbolization source has a corrupt symbol table\0",
+            Self::AnonMapping => b"address belongs to anonymous executable mapping\0",
         }
     }
 }
@@ -482,6 +878,15 @@ impl<'src> Symbolized<'src> {
             Self::Unknown(..) => None,
         }
     }
+
+    /// Convert this object into one with all references converted into
+    /// guaranteed owned (i.e., heap allocated) members.
+    pub fn to_owned(&self) -> Symbolized<'static> {
+        match self {
+            Self::Sym(sym) => Symbolized::Sym(sym.to_owned()),
+            Self::Unknown(reason) => Symbolized::Unknown(*reason),
+        }
+    }
 }
 
 
@@ -500,6 +905,61 @@ where
 {
     /// Find the symbol corresponding to the given address.
     fn find_sym(&self, addr: Addr, opts: &FindSymOpts) -> Result<Result<ResolvedSym<'_>, Reason>>;
+
+    /// Find all symbols covering the given address.
+    ///
+    /// The first symbol reported should be the one that
+    /// [`find_sym`][Self::find_sym] reports. The default implementation
+    /// reports only this symbol.
+    fn find_syms(
+        &self,
+        addr: Addr,
+        opts: &FindSymOpts,
+    ) -> Result<Result<Vec<ResolvedSym<'_>>, Reason>> {
+        let result = self.find_sym(addr, opts)?;
+        Ok(result.map(|sym| vec![sym]))
+    }
+
+    /// Augment a symbol previously found for `addr` by
+    /// [`find_sym`][Self::find_sym] (e.g., using
+    /// [`FindSymOpts::Basic`]) with source code location and inlined
+    /// function information, as requested by `opts`.
+    ///
+    /// Basic symbol data (name, address, size, ...) of `sym` are left
+    /// untouched. The default implementation looks up the symbol anew
+    /// and copies over the relevant data; implementations may be able
+    /// to avoid part of this work.
+    fn fill_code_info<'slf>(
+        &'slf self,
+        sym: &mut ResolvedSym<'slf>,
+        addr: Addr,
+        opts: &FindSymOpts,
+    ) -> Result<()> {
+        if !opts.code_info() {
+            return Ok(())
+        }
+
+        if let Ok(found) = self.find_sym(addr, opts)? {
+            sym.code_info = found.code_info;
+            sym.inlined = found.inlined;
+        }
+        Ok(())
+    }
+
+    /// Find the label closest to `addr` inside the symbol `sym`
+    /// previously reported for it by [`find_sym`][Self::find_sym].
+    ///
+    /// Labels are named locations inside a symbol, as commonly present
+    /// in hand-written assembly. The default implementation reports
+    /// none.
+    fn find_label<'slf>(
+        &'slf self,
+        addr: Addr,
+        sym: &ResolvedSym<'slf>,
+    ) -> Result<Option<&'slf str>> {
+        let (_addr, _sym) = (addr, sym);
+        Ok(None)
+    }
 }
 
 impl<S> AsSymbolize for S
@@ -555,20 +1015,30 @@ mod tests {
             file: Cow::Borrowed(OsStr::new("test.c")),
             line: Some(1337),
             column: None,
+            discriminator: None,
             _non_exhaustive: (),
         };
 
         let sym = Sym {
             name: Cow::Borrowed("test"),
+            mangled_name: None,
             addr: 1337,
             offset: 42,
             size: None,
+            lang: SrcLang::Unknown,
+            section: None,
+            label: None,
+            module: None,
+            build_id: None,
             code_info: None,
             inlined: Box::new([InlinedFn {
                 name: Cow::Borrowed("inlined_test"),
+                addr: None,
+                offset: None,
                 code_info: Some(code_info.clone()),
                 _non_exhaustive: (),
             }]),
+            other_syms: Box::new([]),
             _non_exhaustive: (),
         };
         assert_ne!(format!("{sym:?}"), "");
@@ -609,4 +1079,184 @@ mod tests {
         assert_eq!(symbolized.as_sym(), None);
         assert_eq!(symbolized.into_sym(), None);
     }
+
+    /// Check that we can convert a `Symbolized` object into one with
+    /// owned members.
+    #[test]
+    fn symbolized_to_owned() {
+        let code_info = CodeInfo {
+            dir: Some(Cow::Borrowed(Path::new("/tmp/some-dir"))),
+            file: Cow::Borrowed(OsStr::new("test.c")),
+            line: Some(1337),
+            column: Some(42),
+            discriminator: Some(3),
+            _non_exhaustive: (),
+        };
+        let sym = Sym {
+            name: Cow::Borrowed("test"),
+            mangled_name: Some(Cow::Borrowed("_ZN4test17h0123456789abcdefE")),
+            addr: 1337,
+            offset: 42,
+            size: Some(24),
+            lang: SrcLang::Rust,
+            section: Some(Cow::Borrowed(".text")),
+            label: None,
+            module: None,
+            build_id: None,
+            code_info: Some(code_info.clone()),
+            inlined: Box::new([InlinedFn {
+                name: Cow::Borrowed("inlined_test"),
+                addr: None,
+                offset: None,
+                code_info: Some(code_info),
+                _non_exhaustive: (),
+            }]),
+            other_syms: Box::new([Sym {
+                name: Cow::Borrowed("test_alias"),
+                mangled_name: None,
+                addr: 1337,
+                offset: 42,
+                size: None,
+                lang: SrcLang::Unknown,
+                section: Some(Cow::Borrowed(".text")),
+                label: None,
+                module: None,
+                build_id: None,
+                code_info: None,
+                inlined: Box::new([]),
+                other_syms: Box::new([]),
+                _non_exhaustive: (),
+            }]),
+            _non_exhaustive: (),
+        };
+        let symbolized = Symbolized::Sym(sym);
+        let owned = symbolized.to_owned();
+        assert_eq!(owned, symbolized);
+
+        let sym = owned.as_sym().unwrap();
+        assert!(matches!(sym.name, Cow::Owned(..)));
+        assert!(matches!(
+            sym.code_info.as_ref().unwrap().file,
+            Cow::Owned(..)
+        ));
+        assert!(matches!(sym.inlined[0].name, Cow::Owned(..)));
+        assert!(matches!(sym.other_syms[0].name, Cow::Owned(..)));
+
+        let symbolized = Symbolized::Unknown(Reason::UnknownAddr);
+        assert_eq!(symbolized.to_owned(), symbolized);
+    }
+
+    /// Check that we report the correct source code locations for
+    /// symbols with and without inlined functions.
+    #[test]
+    fn sym_locations() {
+        let code_info = |line| CodeInfo {
+            dir: None,
+            file: Cow::Borrowed(OsStr::new("test.c")),
+            line: Some(line),
+            column: None,
+            discriminator: None,
+            _non_exhaustive: (),
+        };
+        let inlined_fn = |name, line| InlinedFn {
+            name: Cow::Borrowed(name),
+            addr: None,
+            offset: None,
+            code_info: line.map(code_info),
+            _non_exhaustive: (),
+        };
+
+        let mut sym = Sym {
+            name: Cow::Borrowed("f"),
+            mangled_name: None,
+            addr: 0x1000,
+            offset: 0x10,
+            size: None,
+            lang: SrcLang::Unknown,
+            section: None,
+            label: None,
+            module: None,
+            build_id: None,
+            code_info: Some(code_info(1)),
+            inlined: Box::new([]),
+            other_syms: Box::new([]),
+            _non_exhaustive: (),
+        };
+        assert!(!sym.is_inlined_site());
+        assert_eq!(sym.innermost_location(), Some(&code_info(1)));
+        assert_eq!(sym.callsite_location(), None);
+
+        sym.inlined = Box::new([inlined_fn("g", Some(2)), inlined_fn("h", Some(3))]);
+        assert!(sym.is_inlined_site());
+        assert_eq!(sym.innermost_location(), Some(&code_info(3)));
+        assert_eq!(sym.callsite_location(), Some(&code_info(1)));
+
+        sym.inlined = Box::new([inlined_fn("g", Some(2)), inlined_fn("h", None)]);
+        assert_eq!(sym.innermost_location(), Some(&code_info(2)));
+
+        sym.code_info = None;
+        sym.inlined = Box::new([inlined_fn("g", None)]);
+        assert_eq!(sym.innermost_location(), None);
+        assert_eq!(sym.callsite_location(), None);
+    }
+
+    /// Check that we can flatten a symbol and its inlined functions
+    /// into a list of frames.
+    #[test]
+    fn sym_frames() {
+        let code_info = |line| CodeInfo {
+            dir: None,
+            file: Cow::Borrowed(OsStr::new("test.c")),
+            line: Some(line),
+            column: None,
+            discriminator: None,
+            _non_exhaustive: (),
+        };
+
+        let mut sym = Sym {
+            name: Cow::Borrowed("f"),
+            mangled_name: None,
+            addr: 0x1000,
+            offset: 0x10,
+            size: None,
+            lang: SrcLang::Unknown,
+            section: None,
+            label: None,
+            module: None,
+            build_id: None,
+            code_info: Some(code_info(1)),
+            inlined: Box::new([]),
+            other_syms: Box::new([]),
+            _non_exhaustive: (),
+        };
+        let frames = sym.frames().collect::<Vec<_>>();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].name, "f");
+        assert_eq!(frames[0].addr, Some(0x1000));
+        assert_eq!(frames[0].code_info, Some(&code_info(1)));
+
+        sym.inlined = Box::new([
+            InlinedFn {
+                name: Cow::Borrowed("g"),
+                addr: Some(0x1008),
+                offset: Some(0x8),
+                code_info: Some(code_info(2)),
+                _non_exhaustive: (),
+            },
+            InlinedFn {
+                name: Cow::Borrowed("h"),
+                addr: None,
+                offset: None,
+                code_info: None,
+                _non_exhaustive: (),
+            },
+        ]);
+        let frames = sym.frames().collect::<Vec<_>>();
+        let names = frames.iter().map(|frame| frame.name).collect::<Vec<_>>();
+        assert_eq!(names, ["f", "g", "h"]);
+        assert_eq!(frames[1].addr, Some(0x1008));
+        assert_eq!(frames[1].code_info, Some(&code_info(2)));
+        assert_eq!(frames[2].addr, None);
+        assert_eq!(frames[2].code_info, None);
+    }
 }
@@ -173,6 +173,7 @@ impl Symbolize for PerfMap {
                             addr: *addr,
                             size: Some(*size),
                             lang: SrcLang::Unknown,
+                            section: None,
                             code_info: None,
                             inlined: Box::new([]),
                         };
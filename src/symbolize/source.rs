@@ -2,9 +2,17 @@ use std::cmp::min;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::ops::Range;
 use std::path::PathBuf;
 
+use crate::maps;
+use crate::maps::EntryPath;
+use crate::maps::PathName;
+use crate::normalize::buildid::read_elf_build_id;
+use crate::Addr;
+use crate::BuildId;
 use crate::Pid;
+use crate::Result;
 
 #[cfg(doc)]
 use super::Symbolizer;
@@ -65,6 +73,75 @@ impl Debug for Apk {
 }
 
 
+/// A member of an `ar` archive (`.a` file).
+///
+/// This type is used in the [`Source::Archive`] variant.
+///
+/// The member has to be an ELF file (typically a relocatable object).
+/// Both regular and thin archives are supported. File offsets are
+/// interpreted relative to the start of the member.
+#[derive(Clone)]
+pub struct Archive {
+    /// The path to the archive.
+    pub path: PathBuf,
+    /// The name of the member, as recorded in the archive.
+    ///
+    /// Members of thin archives are stored outside of the archive
+    /// itself and their name is a path relative to the directory
+    /// containing the archive.
+    pub member: PathBuf,
+    /// Whether or not to consult debug symbols to satisfy the request
+    /// (if present).
+    ///
+    /// On top of this runtime configuration, the crate needs to be
+    /// built with the `dwarf` feature to actually consult debug
+    /// symbols. If neither is satisfied, ELF symbols will be used.
+    pub debug_syms: bool,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl Archive {
+    /// Create a new [`Archive`] object, referencing the provided member
+    /// of the archive at `path`.
+    ///
+    /// `debug_syms` defaults to `true` when using this constructor.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>, member: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            member: member.into(),
+            debug_syms: true,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<Archive> for Source<'static> {
+    #[inline]
+    fn from(archive: Archive) -> Self {
+        Source::Archive(archive)
+    }
+}
+
+impl Debug for Archive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            path,
+            member,
+            debug_syms: _,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(Archive))
+            .field(path)
+            .field(member)
+            .finish()
+    }
+}
+
+
 cfg_breakpad! {
 /// A single Breakpad file.
 ///
@@ -123,6 +200,15 @@ pub struct Elf {
     /// built with the `dwarf` feature to actually consult debug
     /// symbols. If neither is satisfied, ELF symbols will be used.
     pub debug_syms: bool,
+    /// The load bias of the ELF file, if known.
+    ///
+    /// When set, [`Input::AbsAddr`][crate::symbolize::Input::AbsAddr]
+    /// inputs are supported and converted to virtual offsets by
+    /// subtracting this value, mirroring the relocation adjustment
+    /// performed for [`Process`] sources. This is useful for
+    /// symbolizing addresses captured outside of a live process, e.g.,
+    /// from a core dump.
+    pub base_addr: Option<Addr>,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -131,12 +217,14 @@ pub struct Elf {
 impl Elf {
     /// Create a new [`Elf`] object, referencing the provided path.
     ///
-    /// `debug_syms` defaults to `true` when using this constructor.
+    /// `debug_syms` defaults to `true` and `base_addr` to `None` when
+    /// using this constructor.
     #[inline]
     pub fn new(path: impl Into<PathBuf>) -> Self {
         Self {
             path: path.into(),
             debug_syms: true,
+            base_addr: None,
             _non_exhaustive: (),
         }
     }
@@ -154,6 +242,7 @@ impl Debug for Elf {
         let Self {
             path,
             debug_syms: _,
+            base_addr: _,
             _non_exhaustive: (),
         } = self;
 
@@ -162,6 +251,105 @@ impl Debug for Elf {
 }
 
 
+cfg_macho! {
+/// A single Mach-O file.
+///
+/// This type is used in the [`Source::MachO`] variant.
+///
+/// Fat (universal) binaries are supported, in which case the slice
+/// matching the host architecture is used. Symbolization is based on
+/// the file's symbol table and does not provide source code location
+/// information.
+#[derive(Clone)]
+pub struct MachO {
+    /// The path to a Mach-O file.
+    pub path: PathBuf,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl MachO {
+    /// Create a new [`MachO`] object, referencing the provided path.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<MachO> for Source<'static> {
+    #[inline]
+    fn from(macho: MachO) -> Self {
+        Source::MachO(macho)
+    }
+}
+
+impl Debug for MachO {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            path,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(MachO)).field(path).finish()
+    }
+}
+}
+
+
+cfg_pe! {
+/// A single PE (Portable Executable) file, e.g., a Windows executable
+/// or DLL.
+///
+/// This type is used in the [`Source::Pe`] variant.
+///
+/// Symbolization is based on the file's COFF symbol table as well as
+/// its export directory and does not provide source code location
+/// information. Virtual offsets are interpreted as relative virtual
+/// addresses (RVAs), i.e., relative to the image's load address.
+#[derive(Clone)]
+pub struct Pe {
+    /// The path to a PE file.
+    pub path: PathBuf,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl Pe {
+    /// Create a new [`Pe`] object, referencing the provided path.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<Pe> for Source<'static> {
+    #[inline]
+    fn from(pe: Pe) -> Self {
+        Source::Pe(pe)
+    }
+}
+
+impl Debug for Pe {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            path,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(Pe)).field(path).finish()
+    }
+}
+}
+
+
 /// Linux Kernel's binary image and a copy of `/proc/kallsyms`.
 ///
 /// This type is used in the [`Source::Kernel`] variant.
@@ -174,6 +362,12 @@ pub struct Kernel {
     /// In that situation, you should give the path of the
     /// copy.  Passing `None`, by default, will be
     /// `"/proc/kallsyms"`.
+    ///
+    /// Symbols of kernel modules report the module name as the `file`
+    /// of their [`CodeInfo`][crate::symbolize::CodeInfo]. If
+    /// addresses are hidden (e.g., because of `kernel.kptr_restrict`),
+    /// the default kallsyms file is ignored in favor of the kernel
+    /// image, while an explicitly provided one results in an error.
     pub kallsyms: Option<PathBuf>,
     /// The path of a kernel image.
     ///
@@ -189,6 +383,15 @@ pub struct Kernel {
     /// built with the `dwarf` feature to actually consult debug
     /// symbols. If neither is satisfied, ELF symbols will be used.
     pub debug_syms: bool,
+    /// Whether or not to symbolize addresses inside loaded kernel
+    /// modules using their object files.
+    ///
+    /// If enabled, the runtime addresses of module sections are read
+    /// from `/sys/module/<module>/sections/` and the corresponding
+    /// `.ko` files are looked up via `modules.dep` in
+    /// `/lib/modules/<release>/`. Doing so generally requires root
+    /// privileges. Modules that cannot be loaded are ignored.
+    pub module_syms: bool,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -200,6 +403,7 @@ impl Default for Kernel {
             kallsyms: None,
             kernel_image: None,
             debug_syms: true,
+            module_syms: false,
             _non_exhaustive: (),
         }
     }
@@ -213,6 +417,83 @@ impl From<Kernel> for Source<'static> {
 }
 
 
+/// An ELF file that got mapped into memory manually, i.e., not by the
+/// dynamic loader.
+///
+/// This type is used in the [`Source::MappedElf`] variant.
+///
+/// Such mappings are commonly created by JIT compilers or custom
+/// loaders and show up as anonymous in `/proc/<pid>/maps`, meaning
+/// that [`Process`] based symbolization cannot attribute them to a
+/// file. The file is assumed to be mapped (starting at file offset
+/// zero) at `base`, covering `len` bytes.
+///
+/// Only [`Input::AbsAddr`][crate::symbolize::Input::AbsAddr] inputs
+/// are supported. Addresses outside of the mapping are reported as
+/// [`Reason::Unmapped`][crate::symbolize::Reason::Unmapped].
+#[derive(Clone)]
+pub struct MappedElf {
+    /// The path to the ELF file that got mapped.
+    pub path: PathBuf,
+    /// The address at which the start of the file is mapped.
+    pub base: Addr,
+    /// The length of the mapping, in bytes.
+    pub len: u64,
+    /// Whether or not to consult debug symbols to satisfy the request
+    /// (if present).
+    ///
+    /// On top of this runtime configuration, the crate needs to be
+    /// built with the `dwarf` feature to actually consult debug
+    /// symbols. If neither is satisfied, ELF symbols will be used.
+    pub debug_syms: bool,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl MappedElf {
+    /// Create a new [`MappedElf`] object, referencing the provided
+    /// path and describing a mapping of `len` bytes at `base`.
+    ///
+    /// `debug_syms` defaults to `true` when using this constructor.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>, base: Addr, len: u64) -> Self {
+        Self {
+            path: path.into(),
+            base,
+            len,
+            debug_syms: true,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<MappedElf> for Source<'static> {
+    #[inline]
+    fn from(mapped: MappedElf) -> Self {
+        Source::MappedElf(mapped)
+    }
+}
+
+impl Debug for MappedElf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            path,
+            base,
+            len,
+            debug_syms: _,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(MappedElf))
+            .field(path)
+            .field(&format_args!("{base:#x}"))
+            .field(&format_args!("{len:#x}"))
+            .finish()
+    }
+}
+
+
 /// Configuration for process based address symbolization.
 ///
 /// This type is used in the [`Source::Process`] variant.
@@ -237,14 +518,18 @@ pub struct Process {
     /// built with the `dwarf` feature to actually consult debug
     /// symbols. If neither is satisfied, ELF symbols will be used.
     pub debug_syms: bool,
-    /// Whether to incorporate a process' [perf map][] file into the
-    /// symbolization procedure.
+    /// Whether to incorporate a process' [perf map][] and [JIT dump][]
+    /// files into the symbolization procedure.
     ///
     /// Perf map files mostly have relevance in just-in-time compiled languages,
     /// where they provide an interface for the runtime to expose addresses of
-    /// dynamic symbols to profiling tools.
+    /// dynamic symbols to profiling tools. JIT dump files are a richer
+    /// alternative that may also provide source code location information.
+    /// They are discovered by means of the JIT mapping them into the process'
+    /// address space.
     ///
     /// [perf map]: https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/tools/perf/Documentation/jit-interface.txt
+    /// [JIT dump]: https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/tools/perf/Documentation/jitdump-specification.txt
     pub perf_map: bool,
     /// Whether to work with `/proc/<pid>/map_files/` entries or with
     /// symbolic paths mentioned in `/proc/<pid>/maps` instead.
@@ -255,6 +540,15 @@ pub struct Process {
     /// However, by using symbolic paths the need for requiring the
     /// `SYS_ADMIN` capability is eliminated.
     pub map_files: bool,
+    /// The path to a file to read memory mappings from, instead of
+    /// `/proc/<pid>/maps`.
+    ///
+    /// The file is expected to be in the same format as a proc maps
+    /// file. Overriding it can be useful for replaying a previously
+    /// captured environment. In such a setting, you will likely also
+    /// want to disable [`map_files`][Process::map_files], because its
+    /// usage relies on the presence of the live process.
+    pub maps_override: Option<PathBuf>,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -272,11 +566,74 @@ impl Process {
             debug_syms: true,
             perf_map: true,
             map_files: true,
+            maps_override: None,
             _non_exhaustive: (),
         }
     }
 }
 
+impl Process {
+    /// Retrieve the file backed memory mappings of the process.
+    ///
+    /// Mappings are read from `/proc/<pid>/maps` (or from
+    /// [`maps_override`][Process::maps_override], if set) and filtered
+    /// in the same way as is done for symbolization. Build IDs are read
+    /// from the mapped files, using the `/proc/<pid>/map_files/` entry
+    /// or the symbolic path depending on
+    /// [`map_files`][Process::map_files]. Files that are not ELF files
+    /// or that could not be read are reported without a build ID.
+    pub fn maps(&self) -> Result<Vec<MapEntry>> {
+        let Self {
+            pid,
+            debug_syms: _,
+            perf_map: _,
+            map_files,
+            maps_override,
+            _non_exhaustive: (),
+        } = self;
+
+        let default_maps;
+        let maps_file = if let Some(maps_file) = maps_override {
+            maps_file
+        } else {
+            default_maps = maps::maps_path(*pid);
+            &default_maps
+        };
+
+        let mut entries = Vec::new();
+        for result in maps::parse_path(maps_file, *pid)? {
+            let entry = result?;
+            if !maps::filter_relevant(&entry) {
+                continue
+            }
+
+            let path = match entry.path_name {
+                Some(PathName::Path(path)) => path,
+                Some(PathName::Component(..)) | None => continue,
+            };
+
+            let build_id = entry.build_id.or_else(|| {
+                let file = if *map_files {
+                    &path.maps_file
+                } else {
+                    &path.symbolic_path
+                };
+                read_elf_build_id(file).ok().flatten()
+            });
+
+            let entry = MapEntry {
+                range: entry.range,
+                path,
+                offset: entry.offset,
+                build_id,
+                _non_exhaustive: (),
+            };
+            let () = entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
 impl Debug for Process {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let Self {
@@ -284,6 +641,7 @@ impl Debug for Process {
             debug_syms: _,
             perf_map: _,
             map_files: _,
+            maps_override: _,
             _non_exhaustive: (),
         } = self;
 
@@ -302,6 +660,25 @@ impl From<Process> for Source<'static> {
 }
 
 
+/// A file backed memory mapping of a process, as reported by
+/// [`Process::maps`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MapEntry {
+    /// The virtual address range covered by the mapping.
+    pub range: Range<Addr>,
+    /// The path to the mapped file.
+    pub path: EntryPath,
+    /// The offset of the mapping into the file.
+    pub offset: u64,
+    /// The build ID of the mapped file, if it is an ELF file carrying
+    /// one.
+    pub build_id: Option<BuildId<'static>>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
 cfg_gsym! {
 /// Enumeration of supported Gsym sources.
 ///
@@ -426,14 +803,43 @@ pub enum Source<'dat> {
     #[cfg(feature = "apk")]
     #[cfg_attr(docsrs, doc(cfg(feature = "apk")))]
     Apk(Apk),
+    /// A member of an `ar` archive.
+    Archive(Archive),
     /// A single Breakpad file.
     #[cfg(feature = "breakpad")]
     #[cfg_attr(docsrs, doc(cfg(feature = "breakpad")))]
     Breakpad(Breakpad),
     /// A single ELF file.
     Elf(Elf),
+    /// An ordered list of sources to consult.
+    ///
+    /// Each address is symbolized using the first source, with those
+    /// that could not be symbolized being passed on to the next one,
+    /// and so on. Hence, the first successful symbolization result is
+    /// reported. If all sources fail to symbolize an address, the
+    /// [`Reason`][crate::symbolize::Reason] of the last one is
+    /// reported.
+    ///
+    /// This can be used to, for example, consult a separate debug
+    /// information file first, falling back to the dynamic symbols of
+    /// a stripped binary.
+    ///
+    /// A source failing with an error is treated as if it was unable
+    /// to symbolize any of the addresses passed to it and the error is
+    /// logged. An error is only reported if all sources fail.
+    Fallback(Vec<Source<'dat>>),
     /// Information about the Linux kernel.
     Kernel(Kernel),
+    /// A manually memory mapped ELF file.
+    MappedElf(MappedElf),
+    /// A single Mach-O file.
+    #[cfg(feature = "macho")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "macho")))]
+    MachO(MachO),
+    /// A single PE file.
+    #[cfg(feature = "pe")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pe")))]
+    Pe(Pe),
     /// Information about a process.
     Process(Process),
     /// A Gsym file.
@@ -449,10 +855,17 @@ impl Debug for Source<'_> {
         match self {
             #[cfg(feature = "apk")]
             Self::Apk(apk) => Debug::fmt(apk, f),
+            Self::Archive(archive) => Debug::fmt(archive, f),
             #[cfg(feature = "breakpad")]
             Self::Breakpad(breakpad) => Debug::fmt(breakpad, f),
             Self::Elf(elf) => Debug::fmt(elf, f),
+            Self::Fallback(srcs) => f.debug_tuple(stringify!(Fallback)).field(srcs).finish(),
             Self::Kernel(kernel) => Debug::fmt(kernel, f),
+            Self::MappedElf(mapped) => Debug::fmt(mapped, f),
+            #[cfg(feature = "macho")]
+            Self::MachO(macho) => Debug::fmt(macho, f),
+            #[cfg(feature = "pe")]
+            Self::Pe(pe) => Debug::fmt(pe, f),
             Self::Process(process) => Debug::fmt(process, f),
             #[cfg(feature = "gsym")]
             Self::Gsym(gsym) => Debug::fmt(gsym, f),
@@ -486,10 +899,26 @@ mod tests {
             "Breakpad(\"/a-path/with/components.sym\")"
         );
 
+        let archive = Archive::new("/a-path/libfoo.a", "foo.o");
+        assert_eq!(
+            format!("{archive:?}"),
+            "Archive(\"/a-path/libfoo.a\", \"foo.o\")"
+        );
+        let src = Source::from(archive);
+        assert_eq!(
+            format!("{src:?}"),
+            "Archive(\"/a-path/libfoo.a\", \"foo.o\")"
+        );
+
         let elf = Elf::new("/a-path/with/components.elf");
         assert_eq!(format!("{elf:?}"), "Elf(\"/a-path/with/components.elf\")");
         let src = Source::from(elf);
         assert_eq!(format!("{src:?}"), "Elf(\"/a-path/with/components.elf\")");
+        let src = Source::Fallback(vec![src]);
+        assert_eq!(
+            format!("{src:?}"),
+            "Fallback([Elf(\"/a-path/with/components.elf\")])"
+        );
 
         let gsym_data = GsymData::new(b"12345");
         assert_eq!(format!("{gsym_data:?}"), "GsymData([49, 50, 51, 52, 53])");
@@ -510,6 +939,33 @@ mod tests {
         let src = Source::from(kernel);
         assert_ne!(format!("{src:?}"), "");
 
+        let mapped = MappedElf::new("/a-path/with/components.elf", 0x1000, 0x2000);
+        assert_eq!(
+            format!("{mapped:?}"),
+            "MappedElf(\"/a-path/with/components.elf\", 0x1000, 0x2000)"
+        );
+        let src = Source::from(mapped);
+        assert_eq!(
+            format!("{src:?}"),
+            "MappedElf(\"/a-path/with/components.elf\", 0x1000, 0x2000)"
+        );
+
+        let macho = MachO::new("/a-path/with/components.dylib");
+        assert_eq!(
+            format!("{macho:?}"),
+            "MachO(\"/a-path/with/components.dylib\")"
+        );
+        let src = Source::from(macho);
+        assert_eq!(
+            format!("{src:?}"),
+            "MachO(\"/a-path/with/components.dylib\")"
+        );
+
+        let pe = Pe::new("/a-path/with/components.dll");
+        assert_eq!(format!("{pe:?}"), "Pe(\"/a-path/with/components.dll\")");
+        let src = Source::from(pe);
+        assert_eq!(format!("{src:?}"), "Pe(\"/a-path/with/components.dll\")");
+
         let process = Process::new(Pid::Slf);
         assert_eq!(format!("{process:?}"), "Process(self)");
         let process = Process::new(Pid::from(1234));
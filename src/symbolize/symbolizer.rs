@@ -2,15 +2,22 @@ use std::borrow::Cow;
 use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::fs::File;
+use std::io::Read as _;
+use std::io::Seek as _;
+use std::io::SeekFrom;
 use std::mem::take;
+use std::ops::ControlFlow;
 use std::ops::Deref as _;
 use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 
+use crate::archive;
 #[cfg(feature = "breakpad")]
 use crate::breakpad::BreakpadResolver;
+#[cfg(feature = "dwarf")]
+use crate::dwarf::read_debug_link;
 use crate::elf::ElfParser;
 use crate::elf::ElfResolver;
 use crate::elf::ElfResolverData;
@@ -20,18 +27,34 @@ use crate::file_cache::FileCache;
 #[cfg(feature = "gsym")]
 use crate::gsym::GsymResolver;
 use crate::insert_map::InsertMap;
+#[cfg(not(windows))]
+use crate::kernel::find_module_files;
+#[cfg(not(windows))]
+use crate::kernel::load_modules;
+#[cfg(not(windows))]
+use crate::kernel::KernelModule;
 use crate::kernel::KernelResolver;
+#[cfg(not(windows))]
+use crate::kernel::LIB_MODULES_DIR;
+#[cfg(not(windows))]
+use crate::kernel::SYS_MODULE_DIR;
 use crate::ksym::KSymResolver;
 use crate::ksym::KALLSYMS;
 use crate::log;
+#[cfg(feature = "macho")]
+use crate::macho::MachOResolver;
 use crate::maps;
 use crate::maps::EntryPath;
 use crate::maps::MapsEntry;
 use crate::maps::PathName;
+use crate::maps::Perm;
 use crate::mmap::Mmap;
 use crate::normalize;
+use crate::normalize::buildid::read_build_id;
 use crate::normalize::normalize_sorted_user_addrs_with_entries;
 use crate::normalize::Handler as _;
+#[cfg(feature = "pe")]
+use crate::pe::PeResolver;
 use crate::symbolize::InlinedFn;
 use crate::symbolize::Resolve;
 use crate::symbolize::TranslateFileOffset;
@@ -44,6 +67,7 @@ use crate::util::Hexify;
 #[cfg(feature = "apk")]
 use crate::zip;
 use crate::Addr;
+use crate::BuildId;
 use crate::Error;
 use crate::ErrorExt as _;
 use crate::ErrorKind;
@@ -51,9 +75,11 @@ use crate::IntoError as _;
 use crate::Pid;
 use crate::Result;
 
+use super::jit_dump::JitDump;
 use super::perf_map::PerfMap;
 #[cfg(feature = "apk")]
 use super::source::Apk;
+use super::source::Archive;
 #[cfg(feature = "breakpad")]
 use super::source::Breakpad;
 use super::source::Elf;
@@ -64,14 +90,24 @@ use super::source::GsymData;
 #[cfg(feature = "gsym")]
 use super::source::GsymFile;
 use super::source::Kernel;
+#[cfg(feature = "macho")]
+use super::source::MachO;
+use super::source::MappedElf;
+#[cfg(feature = "pe")]
+use super::source::Pe;
 use super::source::Process;
 use super::source::Source;
+use super::CodeInfo;
+use super::DebugInfo;
 use super::FindSymOpts;
 use super::Input;
 use super::Reason;
 use super::ResolvedSym;
+use super::SourceInfo;
+use super::SrcFormat;
 use super::SrcLang;
 use super::Sym;
+use super::SymSource;
 use super::Symbolize;
 use super::Symbolized;
 
@@ -129,6 +165,151 @@ fn maybe_demangle(name: Cow<'_, str>, _language: SrcLang) -> Cow<'_, str> {
 }
 
 
+/// The number of addresses symbolized at a time by
+/// [`Symbolizer::symbolize_iter`] and, with a progress handler
+/// installed, [`Symbolizer::symbolize`].
+const SYMBOLIZE_BATCH_SIZE: usize = 4096;
+
+
+/// Create a [`SourceInfo`] object for a source format that carries none
+/// of the ELF specific meta data.
+fn non_elf_source_info(format: SrcFormat) -> SourceInfo {
+    SourceInfo {
+        format,
+        arch: None,
+        has_symtab: false,
+        has_dynsym: false,
+        has_debug_line: false,
+        build_id: None,
+        debug_info: DebugInfo::None,
+        sym_stats: None,
+        _non_exhaustive: (),
+    }
+}
+
+
+/// Determine the availability of debug information for the ELF file
+/// represented by `parser`.
+fn elf_debug_info(parser: &ElfParser, build_id: &Option<BuildId<'static>>) -> Result<DebugInfo> {
+    // Legacy GNU style compressed debug information is decompressed
+    // transparently and counts just the same.
+    if parser
+        .find_section_matching(|name| name == ".debug_info" || name == ".zdebug_info")?
+        .is_some()
+    {
+        return Ok(DebugInfo::Present)
+    }
+
+    #[cfg(feature = "dwarf")]
+    if let Some((file, checksum)) = read_debug_link(parser)? {
+        let info = DebugInfo::External {
+            debug_link: PathBuf::from(file),
+            checksum,
+            build_id: build_id.clone(),
+        };
+        return Ok(info)
+    }
+    #[cfg(not(feature = "dwarf"))]
+    let _build_id = build_id;
+
+    Ok(DebugInfo::None)
+}
+
+
+/// Read the build ID of the ELF file backing `resolver`, if any.
+///
+/// A failure to read the build ID is not fatal to symbolization and
+/// only gets logged.
+fn elf_build_id(resolver: &ElfResolver) -> Option<BuildId<'_>> {
+    read_build_id(resolver.parser()).unwrap_or_else(|err| {
+        log::debug!("failed to read build ID of {:?}: {err}", resolver.path());
+        None
+    })
+}
+
+/// Annotate a symbolization result with information about the module
+/// in which the symbol(s) reside.
+fn set_module<'src>(
+    symbolized: &mut Symbolized<'src>,
+    module: &Option<Cow<'src, Path>>,
+    build_id: &Option<BuildId<'src>>,
+) {
+    if let Symbolized::Sym(sym) = symbolized {
+        let () = sym.other_syms.iter_mut().for_each(|other| {
+            other.module = module.clone();
+            other.build_id = build_id.clone();
+        });
+        sym.module = module.clone();
+        sym.build_id = build_id.clone();
+    }
+}
+
+/// Collapse runs of adjacent inlined functions with identical name and
+/// source code location information into a single entry.
+fn dedup_inlined_fns(inlined: Box<[InlinedFn<'_>]>) -> Box<[InlinedFn<'_>]> {
+    let mut inlined = Vec::from(inlined);
+    let () =
+        inlined.dedup_by(|next, prev| next.name == prev.name && next.code_info == prev.code_info);
+    inlined.into_boxed_slice()
+}
+
+/// Limit `inlined` to the `depth` outermost inlined functions.
+///
+/// The source code location of the symbolized address itself is
+/// reported as part of the innermost inlined function. It is retained
+/// by moving it to the last function kept (or into `code_info`, if no
+/// inlined function is kept).
+fn truncate_inlined_fns<'src>(
+    code_info: &mut Option<CodeInfo<'src>>,
+    inlined: Box<[InlinedFn<'src>]>,
+    depth: usize,
+) -> Box<[InlinedFn<'src>]> {
+    if inlined.len() <= depth {
+        return inlined
+    }
+
+    let mut inlined = Vec::from(inlined);
+    // SANITY: `inlined` contains more than `depth` elements and so
+    //         can't be empty.
+    let location = inlined.last_mut().unwrap().code_info.take();
+    let () = inlined.truncate(depth);
+    match inlined.last_mut() {
+        Some(inlined_fn) => inlined_fn.code_info = location,
+        None => *code_info = location,
+    }
+    inlined.into_boxed_slice()
+}
+
+/// Rewrite `path` according to the first matching entry of `remap`,
+/// replacing the matched prefix with the corresponding substitute.
+fn remap_path<'src>(path: Cow<'src, Path>, remap: &[(PathBuf, PathBuf)]) -> Cow<'src, Path> {
+    for (from, to) in remap {
+        if let Ok(rest) = path.strip_prefix(from) {
+            return Cow::Owned(to.join(rest))
+        }
+    }
+    path
+}
+
+/// Rewrite the source path reported by `info` according to `remap`.
+fn remap_code_info(info: &mut CodeInfo<'_>, remap: &[(PathBuf, PathBuf)]) {
+    if Path::new(&info.file).is_absolute() {
+        // An absolute file name makes the directory irrelevant.
+        let file = match take(&mut info.file) {
+            Cow::Borrowed(file) => Cow::Borrowed(Path::new(file)),
+            Cow::Owned(file) => Cow::Owned(PathBuf::from(file)),
+        };
+        info.file = match remap_path(file, remap) {
+            Cow::Borrowed(file) => Cow::Borrowed(file.as_os_str()),
+            Cow::Owned(file) => Cow::Owned(file.into_os_string()),
+        };
+    } else if let Some(dir) = info.dir.take() {
+        let dir = remap_path(dir, remap);
+        info.dir = (!dir.as_os_str().is_empty()).then_some(dir);
+    }
+}
+
+
 /// Information about a member inside an APK.
 ///
 /// This type is used in conjunction with the APK "dispatcher" infrastructure;
@@ -181,17 +362,56 @@ impl<F> ProcessDispatch for F where F: Fn(ProcessMemberInfo<'_>) -> Result<Optio
 fn default_apk_dispatcher(
     info: ApkMemberInfo<'_>,
     debug_dirs: Option<&[PathBuf]>,
+    sym_source: SymSource,
+    compiler_local_syms: bool,
 ) -> Result<Box<dyn Resolve>> {
     // Create an Android-style binary-in-APK path for
     // reporting purposes.
     let apk_elf_path = create_apk_elf_path(info.apk_path, info.member_path)?;
-    let parser = Rc::new(ElfParser::from_mmap(info.member_mmap, Some(apk_elf_path)));
-    let resolver = ElfResolver::from_parser(parser, debug_dirs)?;
+    let mut parser = ElfParser::from_mmap(info.member_mmap, Some(apk_elf_path));
+    let () = parser.set_sym_source(sym_source);
+    let () = parser.set_compiler_local_syms(compiler_local_syms);
+    let resolver = ElfResolver::from_parser(Rc::new(parser), debug_dirs)?;
     let resolver = Box::new(resolver);
     Ok(resolver)
 }
 
 
+/// The signature of a custom demangling function.
+///
+/// A demangler receives a (potentially mangled) symbol name. If it
+/// returns `Some` name, this name is reported. If `None` is returned,
+/// the built-in demangling logic is used instead. See
+/// [`Builder::set_demangler`].
+// TODO: Use a trait alias once stable.
+pub trait Demangle: Fn(&str) -> Option<String> {}
+
+impl<F> Demangle for F where F: Fn(&str) -> Option<String> {}
+
+
+/// The signature of a progress reporting function.
+///
+/// The function receives the number of addresses processed so far as
+/// well as the total number of addresses to symbolize. Returning
+/// [`ControlFlow::Break`] aborts symbolization. See
+/// [`Builder::set_progress_handler`].
+// TODO: Use a trait alias once stable.
+pub trait Progress: Fn(usize, usize) -> ControlFlow<()> {}
+
+impl<F> Progress for F where F: Fn(usize, usize) -> ControlFlow<()> {}
+
+
+/// The signature of an address filter function.
+///
+/// The function receives an absolute address as provided by the user
+/// and returns the address to use for symbolization. See
+/// [`Builder::set_addr_filter`].
+// TODO: Use a trait alias once stable.
+pub trait AddrFilter: Fn(Addr) -> Addr {}
+
+impl<F> AddrFilter for F where F: Fn(Addr) -> Addr {}
+
+
 /// Information about an address space member of a process.
 #[derive(Clone, Debug)]
 pub struct ProcessMemberInfo<'dat> {
@@ -219,12 +439,28 @@ pub struct Builder {
     code_info: bool,
     /// Whether to report inlined functions as part of symbolization.
     inlined_fns: bool,
+    /// The maximum number of inlined function frames to report.
+    max_inline_depth: Option<usize>,
+    /// Whether to collapse adjacent identical inlined function frames.
+    dedup_inlined_fns: bool,
+    /// Source path prefixes to rewrite, along with their substitutes.
+    src_path_remap: Vec<(PathBuf, PathBuf)>,
     /// Whether or not to transparently demangle symbols.
     ///
     /// Demangling happens on a best-effort basis. Currently supported
     /// languages are Rust and C++ and the flag will have no effect if
     /// the underlying language does not mangle symbols (such as C).
     demangle: bool,
+    /// Whether to report all symbols covering an address.
+    all_syms: bool,
+    /// The ELF symbol table(s) to consult.
+    sym_source: SymSource,
+    /// Whether to report compiler generated local ELF symbols.
+    compiler_local_syms: bool,
+    /// Whether to report the label closest to an address.
+    labels: bool,
+    /// Whether to intern reported names.
+    intern_names: bool,
     /// List of additional directories in which split debug information
     /// is looked for.
     #[cfg(feature = "dwarf")]
@@ -236,6 +472,12 @@ pub struct Builder {
     /// The "dispatch" function to use when symbolizing addresses
     /// mapping to members of a process.
     process_dispatch: Option<Dbg<Box<dyn ProcessDispatch>>>,
+    /// A custom demangler consulted before the built-in ones.
+    demangler: Option<Dbg<Box<dyn Demangle>>>,
+    /// The function to report batch symbolization progress to.
+    progress_handler: Option<Dbg<Box<dyn Progress>>>,
+    /// A function transforming absolute addresses before symbolization.
+    addr_filter: Option<Dbg<Box<dyn AddrFilter>>>,
 }
 
 impl Builder {
@@ -261,6 +503,74 @@ impl Builder {
         self
     }
 
+    /// Set the maximum number of inlined functions to report per
+    /// symbol.
+    ///
+    /// If more inlined functions are present, the most deeply inlined
+    /// ones are omitted and the source code location of the address is
+    /// reported as part of the innermost function retained. Where
+    /// possible, omitted functions are not looked up to begin with.
+    /// A value of `Some(0)` is equivalent to
+    /// disabling inlined function reporting. `None` (the default)
+    /// imposes no limit.
+    ///
+    /// This option only has an effect if `inlined_fns` is `true`.
+    pub fn set_max_inline_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_inline_depth = depth;
+        self
+    }
+
+    /// Enable/disable collapsing of adjacent inlined functions that
+    /// share both name and source code location information into a
+    /// single entry.
+    ///
+    /// Recursive inlining can produce long runs of such entries.
+    /// Deduplication happens before [`set_max_inline_depth`] is
+    /// applied. It is disabled by default.
+    ///
+    /// This option only has an effect if `inlined_fns` is `true`.
+    ///
+    /// [`set_max_inline_depth`]: Self::set_max_inline_depth
+    pub fn enable_inlined_fn_dedup(mut self, enable: bool) -> Self {
+        self.dedup_inlined_fns = enable;
+        self
+    }
+
+    /// Set a list of source path prefixes to rewrite in reported source
+    /// code location information.
+    ///
+    /// Each entry consists of a prefix and its substitute. Paths are
+    /// matched component-wise and the first matching entry is applied.
+    /// Remapping affects the [`CodeInfo`] of both the symbol itself and
+    /// all of its inlined functions. It can be used to hide build host
+    /// specific directories, for example.
+    ///
+    /// Setting a list overwrites any previously configured remapping,
+    /// including prefixes added via [`strip_src_prefix`].
+    ///
+    /// [`strip_src_prefix`]: Self::strip_src_prefix
+    pub fn set_src_path_remap(mut self, remap: Vec<(PathBuf, PathBuf)>) -> Self {
+        self.src_path_remap = remap;
+        self
+    }
+
+    /// Strip the provided prefix from reported source code paths.
+    ///
+    /// This is a convenience wrapper around remapping the prefix to an
+    /// empty path (see [`set_src_path_remap`]). Prefixes are
+    /// accumulated when the method is invoked repeatedly.
+    ///
+    /// [`set_src_path_remap`]: Self::set_src_path_remap
+    pub fn strip_src_prefix<P>(mut self, prefix: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let () = self
+            .src_path_remap
+            .push((prefix.as_ref().to_path_buf(), PathBuf::new()));
+        self
+    }
+
     /// Enable/disable transparent demangling of symbol names.
     ///
     /// Demangling happens on a best-effort basis. Currently supported languages
@@ -271,6 +581,68 @@ impl Builder {
         self
     }
 
+    /// Enable/disable reporting of all symbols covering an address.
+    ///
+    /// By default only a single symbol is reported per address. If
+    /// enabled, additional symbols starting at the same address as the
+    /// reported one and covering the address in question (e.g.,
+    /// aliases) are reported as part of [`Sym::other_syms`]. Symbols
+    /// starting at a lower address, such as an enclosing function, are
+    /// not reported. These symbols never carry source code or inlined
+    /// function information.
+    pub fn enable_all_syms(mut self, enable: bool) -> Self {
+        self.all_syms = enable;
+        self
+    }
+
+    /// Set the ELF symbol table(s) to consult when symbolizing.
+    ///
+    /// By default (i.e., with [`SymSource::Auto`]), `.symtab` is
+    /// preferred and `.dynsym` used as a fall back.
+    pub fn set_sym_source(mut self, sym_source: SymSource) -> Self {
+        self.sym_source = sym_source;
+        self
+    }
+
+    /// Enable/disable reporting of compiler generated local ELF
+    /// symbols.
+    ///
+    /// Such symbols are local ones that are either unnamed or represent
+    /// assembler local labels (e.g., `.L*` or `LBB*`). They can be
+    /// useful for fine-grained disassembly, but otherwise tend to
+    /// shadow the function actually containing an address. They are
+    /// not reported by default.
+    pub fn enable_compiler_local_syms(mut self, enable: bool) -> Self {
+        self.compiler_local_syms = enable;
+        self
+    }
+
+    /// Enable/disable reporting of labels inside of symbols.
+    ///
+    /// Hand-written assembly commonly contains a function symbol along
+    /// with a number of (`STT_NOTYPE`) labels inside of it. If enabled,
+    /// the label closest to (but not past) the address in question is
+    /// reported as part of [`Sym::label`]. Only ELF based sources
+    /// support labels. Reporting is disabled by default.
+    pub fn enable_labels(mut self, enable: bool) -> Self {
+        self.labels = enable;
+        self
+    }
+
+    /// Enable/disable interning of reported names.
+    ///
+    /// When symbolizing large numbers of addresses, the same names tend
+    /// to be reported over and over again. Names that would otherwise
+    /// be allocated anew for every result (e.g., because they were
+    /// demangled) are shared across results if interning is enabled.
+    /// Interned names are kept alive until [`Symbolizer::clear_cache`]
+    /// is called or the [`Symbolizer`] is dropped. Interning is
+    /// disabled by default.
+    pub fn enable_name_interning(mut self, enable: bool) -> Self {
+        self.intern_names = enable;
+        self
+    }
+
     /// Set debug directories to search for split debug information.
     ///
     /// These directories will be consulted (in given order) when resolving
@@ -325,18 +697,75 @@ impl Builder {
         self
     }
 
+    /// Set a custom demangler to use for symbol names.
+    ///
+    /// The demangler is consulted before any of the built-in demanglers
+    /// and only if demangling is enabled (see
+    /// [`Builder::enable_demangling`]). If it returns `None`, the
+    /// default demangling logic is applied.
+    pub fn set_demangler<D>(mut self, demangler: D) -> Self
+    where
+        D: Demangle + 'static,
+    {
+        self.demangler = Some(Dbg(Box::new(demangler)));
+        self
+    }
+
+    /// Set a function to report progress of batch symbolization to.
+    ///
+    /// If set, [`Symbolizer::symbolize`] processes addresses in
+    /// batches and invokes the function after each one with the number
+    /// of addresses processed so far and the total number of addresses.
+    /// If the function returns [`ControlFlow::Break`], symbolization
+    /// stops and the results gathered up to this point are returned.
+    /// Without a handler, addresses are symbolized in a single batch.
+    pub fn set_progress_handler<P>(mut self, progress: P) -> Self
+    where
+        P: Progress + 'static,
+    {
+        self.progress_handler = Some(Dbg(Box::new(progress)));
+        self
+    }
+
+    /// Set a function to transform absolute addresses with before
+    /// symbolizing them.
+    ///
+    /// The function is applied to every address provided as part of
+    /// [`Input::AbsAddr`], prior to any normalization or lookup. A
+    /// common use case is stripping pointer authentication bits from
+    /// return addresses captured on AArch64, e.g.,
+    /// `|addr| addr & 0x0000_ffff_ffff_ffff`.
+    pub fn set_addr_filter<F>(mut self, filter: F) -> Self
+    where
+        F: AddrFilter + 'static,
+    {
+        self.addr_filter = Some(Dbg(Box::new(filter)));
+        self
+    }
+
     /// Create the [`Symbolizer`] object.
     pub fn build(self) -> Symbolizer {
         let Self {
             auto_reload,
             code_info,
             inlined_fns,
+            max_inline_depth,
+            dedup_inlined_fns,
+            src_path_remap,
             demangle,
+            all_syms,
+            sym_source,
+            compiler_local_syms,
+            labels,
+            intern_names,
             #[cfg(feature = "dwarf")]
             debug_dirs,
             #[cfg(feature = "apk")]
             apk_dispatch,
             process_dispatch,
+            demangler,
+            progress_handler,
+            addr_filter,
         } = self;
 
         let find_sym_opts = match (code_info, inlined_fns) {
@@ -349,27 +778,51 @@ impl Builder {
                 FindSymOpts::Basic
             }
             (true, false) => FindSymOpts::CodeInfo,
-            (true, true) => FindSymOpts::CodeInfoAndInlined,
+            (true, true) => match max_inline_depth {
+                Some(0) => FindSymOpts::CodeInfo,
+                // Deduplication has to see all inlined functions, so
+                // we can only bound the lookup without it.
+                Some(depth) if !dedup_inlined_fns => FindSymOpts::CodeInfoAndInlinedUpTo(depth),
+                Some(..) | None => FindSymOpts::CodeInfoAndInlined,
+            },
         };
 
         Symbolizer {
             #[cfg(feature = "apk")]
             apk_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
+            archive_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
             #[cfg(feature = "breakpad")]
             breakpad_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
             elf_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
             #[cfg(feature = "gsym")]
             gsym_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
             ksym_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
+            #[cfg(feature = "macho")]
+            macho_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
+            #[cfg(feature = "pe")]
+            pe_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
+            jit_dump_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
             perf_map_cache: FileCache::builder().enable_auto_reload(auto_reload).build(),
             process_cache: InsertMap::new(),
+            names: InsertMap::new(),
             find_sym_opts,
+            max_inline_depth,
+            dedup_inlined_fns,
+            src_path_remap,
             demangle,
+            all_syms,
+            sym_source,
+            compiler_local_syms,
+            labels,
+            intern_names,
             #[cfg(feature = "dwarf")]
             debug_dirs,
             #[cfg(feature = "apk")]
             apk_dispatch,
             process_dispatch,
+            demangler,
+            progress_handler,
+            addr_filter,
         }
     }
 }
@@ -380,7 +833,15 @@ impl Default for Builder {
             auto_reload: true,
             code_info: true,
             inlined_fns: true,
+            max_inline_depth: None,
+            dedup_inlined_fns: false,
+            src_path_remap: Vec::new(),
             demangle: true,
+            all_syms: false,
+            sym_source: SymSource::default(),
+            compiler_local_syms: false,
+            labels: false,
+            intern_names: false,
             #[cfg(feature = "dwarf")]
             debug_dirs: DEFAULT_DEBUG_DIRS
                 .iter()
@@ -389,11 +850,35 @@ impl Default for Builder {
             #[cfg(feature = "apk")]
             apk_dispatch: None,
             process_dispatch: None,
+            demangler: None,
+            progress_handler: None,
+            addr_filter: None,
         }
     }
 }
 
 
+/// Create an [`ElfResolver`] for the VDSO of the process with the
+/// given PID, reading it out of the process' address space.
+fn create_vdso_resolver(pid: Pid, range: &Range<Addr>) -> Result<ElfResolver> {
+    let path = PathBuf::from(format!("/proc/{pid}/mem"));
+    let mut file =
+        File::open(&path).with_context(|| format!("failed to open `{}`", path.display()))?;
+    let len = usize::try_from(range.end - range.start)
+        .map_err(Error::with_invalid_data)
+        .context("VDSO is too large")?;
+    let mut data = vec![0; len];
+    let _pos = file.seek(SeekFrom::Start(range.start))?;
+    let () = file
+        .read_exact(&mut data)
+        .with_context(|| format!("failed to read VDSO from `{}`", path.display()))?;
+
+    let parser = ElfParser::from_bytes(&data, None)?;
+    let resolver = ElfResolver::from_parser(Rc::new(parser), None)?;
+    Ok(resolver)
+}
+
+
 struct SymbolizeHandler<'sym> {
     /// The "outer" `Symbolizer` instance.
     symbolizer: &'sym Symbolizer,
@@ -402,12 +887,20 @@ struct SymbolizeHandler<'sym> {
     /// Whether or not to consult debug symbols to satisfy the request
     /// (if present).
     debug_syms: bool,
-    /// Whether or not to consult the process' perf map (if any) to
-    /// satisfy the request.
+    /// Whether or not to consult the process' perf map and JIT dump (if
+    /// any) to satisfy the request.
     perf_map: bool,
     /// Whether to work with `/proc/<pid>/map_files/` entries or with
     /// symbolic paths mentioned in `/proc/<pid>/maps` instead.
     map_files: bool,
+    /// The path to the maps file of the process.
+    maps_file: PathBuf,
+    /// The path to the process' JIT dump, if any. Only discovered when
+    /// needed.
+    jit_dump_path: Option<Option<PathBuf>>,
+    /// A resolver for the process' VDSO, if it could be read. Only
+    /// created when needed.
+    vdso: Option<Option<Rc<ElfResolver>>>,
     /// Symbols representing the symbolized addresses.
     all_symbols: Vec<Symbolized<'sym>>,
 }
@@ -444,16 +937,54 @@ impl SymbolizeHandler<'_> {
             &entry_path.symbolic_path
         };
 
-        let resolver = self
-            .symbolizer
-            .elf_cache
-            .elf_resolver(path, self.symbolizer.maybe_debug_dirs(self.debug_syms))?;
+        let resolver = self.symbolizer.elf_cache.elf_resolver(
+            path,
+            self.symbolizer.maybe_debug_dirs(self.debug_syms),
+            self.symbolizer.sym_source,
+            self.symbolizer.compiler_local_syms,
+        )?;
 
         match resolver.file_offset_to_virt_offset(file_off)? {
             Some(addr) => {
-                let symbol = self
+                let mut symbol = self
                     .symbolizer
                     .symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))?;
+                let module = Some(Cow::Owned(entry_path.symbolic_path.clone()));
+                let () = set_module(&mut symbol, &module, &elf_build_id(resolver));
+                let () = self.all_symbols.push(symbol);
+            }
+            None => self.handle_unknown_addr(addr, Reason::InvalidFileOffset),
+        }
+        Ok(())
+    }
+
+    fn handle_vdso_addr(&mut self, addr: Addr, entry: &MapsEntry) -> Result<()> {
+        if self.vdso.is_none() {
+            let resolver = match create_vdso_resolver(self.pid, &entry.range) {
+                Ok(resolver) => Some(Rc::new(resolver)),
+                Err(err) => {
+                    // The process may have exited in the meantime or we
+                    // may lack permission to read its memory.
+                    log::debug!("failed to read VDSO of process {}: {err}", self.pid);
+                    None
+                }
+            };
+            self.vdso = Some(resolver);
+        }
+
+        let resolver = if let Some(Some(resolver)) = &self.vdso {
+            resolver.clone()
+        } else {
+            let () = self.handle_unknown_addr(addr, Reason::Unsupported);
+            return Ok(())
+        };
+
+        let file_off = addr - entry.range.start + entry.offset;
+        match resolver.file_offset_to_virt_offset(file_off)? {
+            Some(addr) => {
+                let symbol = self
+                    .symbolizer
+                    .symbolize_with_resolver(addr, &Resolver::Uncached(resolver.deref()))?;
                 let () = self.all_symbols.push(symbol);
             }
             None => self.handle_unknown_addr(addr, Reason::InvalidFileOffset),
@@ -461,12 +992,69 @@ impl SymbolizeHandler<'_> {
         Ok(())
     }
 
-    fn handle_perf_map_addr(&mut self, addr: Addr) -> Result<()> {
-        if let Some(perf_map) = self.symbolizer.perf_map(self.pid)? {
+    /// Retrieve the JIT dump of the process, if any.
+    ///
+    /// JIT dump files have no fixed location, but the JIT is required
+    /// to map them into its address space, which is how we discover
+    /// them.
+    fn jit_dump(&mut self) -> Result<Option<&'sym JitDump>> {
+        if self.jit_dump_path.is_none() {
+            let mut path = None;
+            for result in maps::parse_path(&self.maps_file, self.pid)? {
+                let entry = result?;
+                if let Some(PathName::Path(entry_path)) = entry.path_name {
+                    if JitDump::is_jit_dump(&entry_path.symbolic_path, self.pid) {
+                        path = Some(if self.map_files {
+                            entry_path.maps_file
+                        } else {
+                            entry_path.symbolic_path
+                        });
+                        break
+                    }
+                }
+            }
+            self.jit_dump_path = Some(path);
+        }
+
+        let symbolizer = self.symbolizer;
+        match &self.jit_dump_path {
+            Some(Some(path)) => symbolizer.jit_dump(path),
+            Some(None) | None => Ok(None),
+        }
+    }
+
+    fn handle_perf_map_addr(&mut self, addr: Addr, entry: &MapsEntry) -> Result<()> {
+        let perf_map = self.symbolizer.perf_map(self.pid)?;
+        if let Some(perf_map) = perf_map {
             let symbolized = self
                 .symbolizer
                 .symbolize_with_resolver(addr, &Resolver::Cached(perf_map))?;
+            if let Symbolized::Sym(..) = symbolized {
+                let () = self.all_symbols.push(symbolized);
+                return Ok(())
+            }
+        }
+
+        let jit_dump = match self.jit_dump() {
+            Ok(jit_dump) => jit_dump,
+            Err(err) => {
+                // A JIT dump is only a supplementary source of
+                // symbols. Don't let a malformed one get in the way of
+                // symbolizing everything else.
+                log::debug!("failed to load JIT dump of process {}: {err:#}", self.pid);
+                // Don't bother trying again for other addresses.
+                self.jit_dump_path = Some(None);
+                None
+            }
+        };
+
+        if let Some(jit_dump) = jit_dump {
+            let symbolized = self
+                .symbolizer
+                .symbolize_with_resolver(addr, &Resolver::Cached(jit_dump))?;
             let () = self.all_symbols.push(symbolized);
+        } else if perf_map.is_none() {
+            let () = self.handle_unknown_addr(addr, anon_reason(entry));
         } else {
             let () = self.handle_unknown_addr(addr, Reason::UnknownAddr);
         }
@@ -474,6 +1062,16 @@ impl SymbolizeHandler<'_> {
     }
 }
 
+/// Determine the reason to report for an unresolvable address in a
+/// `/proc/<pid>/maps` entry without a path.
+fn anon_reason(entry: &MapsEntry) -> Reason {
+    if entry.is_anon_exec() {
+        Reason::AnonMapping
+    } else {
+        Reason::UnknownAddr
+    }
+}
+
 impl normalize::Handler<Reason> for SymbolizeHandler<'_> {
     #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(addr = format_args!("{_addr:#x}"))))]
     fn handle_unknown_addr(&mut self, _addr: Addr, reason: Reason) {
@@ -518,6 +1116,9 @@ impl normalize::Handler<Reason> for SymbolizeHandler<'_> {
                     _ => self.handle_elf_addr(addr, file_off, entry_path),
                 }
             }
+            Some(PathName::Component(component)) if component == "vdso" => {
+                self.handle_vdso_addr(addr, entry)
+            }
             Some(PathName::Component(..)) => {
                 let () = self.handle_unknown_addr(addr, Reason::Unsupported);
                 Ok(())
@@ -525,12 +1126,12 @@ impl normalize::Handler<Reason> for SymbolizeHandler<'_> {
             // If there is no path associated with this entry, we don't
             // really have any idea what the address may belong to. But
             // there is a chance that the address is part of the perf
-            // map, so check that.
+            // map or JIT dump, so check that.
             // TODO: It's not entirely clear if a perf map could also
             //       cover addresses belonging to entries with a path.
-            None if self.perf_map => self.handle_perf_map_addr(addr),
+            None if self.perf_map => self.handle_perf_map_addr(addr, entry),
             None => {
-                let () = self.handle_unknown_addr(addr, Reason::UnknownAddr);
+                let () = self.handle_unknown_addr(addr, anon_reason(entry));
                 Ok(())
             }
         }
@@ -572,26 +1173,54 @@ enum Resolver<'tmp, 'slf> {
 ///
 /// # Notes
 /// Please note that demangling results are not cached.
+///
+/// Addresses are symbolized serially. Because cached data are shared
+/// using single threaded reference counting and interior mutability,
+/// `Symbolizer` is neither `Send` nor `Sync`. Users wishing to
+/// symbolize large numbers of addresses concurrently should partition
+/// them and use a separate `Symbolizer` instance per thread.
 #[derive(Debug)]
 pub struct Symbolizer {
     #[allow(clippy::type_complexity)]
     #[cfg(feature = "apk")]
     apk_cache: FileCache<(zip::Archive, InsertMap<Range<u64>, Box<dyn Resolve>>)>,
+    #[allow(clippy::type_complexity)]
+    archive_cache: FileCache<(
+        archive::Archive,
+        InsertMap<(PathBuf, bool), Rc<ElfResolver>>,
+    )>,
     #[cfg(feature = "breakpad")]
     breakpad_cache: FileCache<BreakpadResolver>,
     elf_cache: FileCache<ElfResolverData>,
     #[cfg(feature = "gsym")]
     gsym_cache: FileCache<GsymResolver<'static>>,
     ksym_cache: FileCache<Rc<KSymResolver>>,
+    #[cfg(feature = "macho")]
+    macho_cache: FileCache<MachOResolver>,
+    #[cfg(feature = "pe")]
+    pe_cache: FileCache<PeResolver>,
+    jit_dump_cache: FileCache<JitDump>,
     perf_map_cache: FileCache<PerfMap>,
     process_cache: InsertMap<PathName, Option<Box<dyn Resolve>>>,
     find_sym_opts: FindSymOpts,
+    max_inline_depth: Option<usize>,
+    dedup_inlined_fns: bool,
+    src_path_remap: Vec<(PathBuf, PathBuf)>,
     demangle: bool,
+    all_syms: bool,
+    sym_source: SymSource,
+    compiler_local_syms: bool,
+    labels: bool,
+    intern_names: bool,
+    names: InsertMap<Box<str>, ()>,
     #[cfg(feature = "dwarf")]
     debug_dirs: Vec<PathBuf>,
     #[cfg(feature = "apk")]
     apk_dispatch: Option<Dbg<Box<dyn ApkDispatch>>>,
     process_dispatch: Option<Dbg<Box<dyn ProcessDispatch>>>,
+    demangler: Option<Dbg<Box<dyn Demangle>>>,
+    progress_handler: Option<Dbg<Box<dyn Progress>>>,
+    addr_filter: Option<Dbg<Box<dyn AddrFilter>>>,
 }
 
 impl Symbolizer {
@@ -606,15 +1235,126 @@ impl Symbolizer {
         Builder::default()
     }
 
+    /// Clear all cached symbolization sources and associated data.
+    ///
+    /// Long running processes may use this method to release memory
+    /// held on behalf of sources that are no longer of interest. Data
+    /// will be re-parsed transparently as required by subsequent
+    /// symbolization requests.
+    pub fn clear_cache(&mut self) {
+        #[cfg(feature = "apk")]
+        let () = self.apk_cache.clear();
+        let () = self.archive_cache.clear();
+        #[cfg(feature = "breakpad")]
+        let () = self.breakpad_cache.clear();
+        let () = self.elf_cache.clear();
+        #[cfg(feature = "gsym")]
+        let () = self.gsym_cache.clear();
+        let () = self.ksym_cache.clear();
+        #[cfg(feature = "macho")]
+        let () = self.macho_cache.clear();
+        #[cfg(feature = "pe")]
+        let () = self.pe_cache.clear();
+        let () = self.jit_dump_cache.clear();
+        let () = self.perf_map_cache.clear();
+        let () = self.process_cache.clear();
+        let () = self.names.clear();
+    }
+
     /// Demangle the provided symbol if asked for and possible.
     fn maybe_demangle<'sym>(&self, symbol: Cow<'sym, str>, language: SrcLang) -> Cow<'sym, str> {
         if self.demangle {
+            if let Some(Dbg(demangler)) = &self.demangler {
+                if let Some(name) = demangler(symbol.as_ref()) {
+                    return Cow::Owned(name)
+                }
+            }
             maybe_demangle(symbol, language)
         } else {
             symbol
         }
     }
 
+    /// Find the symbol for the given address, along with all other
+    /// symbols covering it if so configured.
+    fn find_syms<'src>(
+        &self,
+        resolver: &'src dyn Symbolize,
+        addr: Addr,
+    ) -> Result<Result<(ResolvedSym<'src>, Vec<ResolvedSym<'src>>), Reason>> {
+        if !self.all_syms {
+            let result = resolver.find_sym(addr, &self.find_sym_opts)?;
+            return Ok(result.map(|sym| (sym, Vec::new())))
+        }
+
+        match resolver.find_syms(addr, &self.find_sym_opts)? {
+            Ok(syms) => {
+                let mut syms = syms.into_iter();
+                let sym = syms
+                    .next()
+                    .ok_or_invalid_data(|| format!("no symbol reported for address {addr:#x}"))?;
+                Ok(Ok((sym, syms.collect())))
+            }
+            Err(reason) => Ok(Err(reason)),
+        }
+    }
+
+    /// Convert `name` into one with the lifetime of the `Symbolizer`,
+    /// interning it if so configured.
+    fn maybe_intern<'slf>(&'slf self, name: Cow<'_, str>) -> Cow<'slf, str> {
+        if self.intern_names {
+            Cow::Borrowed(self.names.intern(name.as_ref()))
+        } else {
+            Cow::Owned(name.into_owned())
+        }
+    }
+
+    /// Find the label closest to `addr` inside `sym`, if reporting of
+    /// labels is enabled.
+    fn find_label<'src>(
+        &self,
+        resolver: &'src dyn Symbolize,
+        addr: Addr,
+        sym: &ResolvedSym<'src>,
+    ) -> Result<Option<&'src str>> {
+        if !self.labels {
+            return Ok(None)
+        }
+        resolver.find_label(addr, sym)
+    }
+
+    /// Convert an additional symbol covering `addr` into a [`Sym`].
+    fn other_sym<'src>(&self, addr: Addr, sym: ResolvedSym<'src>) -> Sym<'src> {
+        let ResolvedSym {
+            name,
+            addr: sym_addr,
+            size,
+            lang,
+            section,
+            code_info: _,
+            inlined: _,
+        } = sym;
+
+        let demangled = self.maybe_demangle(Cow::Borrowed(name), lang);
+        let mangled_name = (demangled != name).then_some(Cow::Borrowed(name));
+        Sym {
+            name: demangled,
+            mangled_name,
+            addr: sym_addr,
+            offset: (addr - sym_addr) as usize,
+            size,
+            lang,
+            section: section.map(Cow::Borrowed),
+            label: None,
+            module: None,
+            build_id: None,
+            code_info: None,
+            inlined: Box::new([]),
+            other_syms: Box::new([]),
+            _non_exhaustive: (),
+        }
+    }
+
     /// Symbolize an address using the provided [`SymResolver`].
     #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(addr = format_args!("{addr:#x}"), resolver = ?resolver)))]
     fn symbolize_with_resolver<'slf>(
@@ -622,76 +1362,196 @@ impl Symbolizer {
         addr: Addr,
         resolver: &Resolver<'_, 'slf>,
     ) -> Result<Symbolized<'slf>> {
-        let (sym_name, sym_addr, sym_size, code_info, inlined) = match resolver {
-            Resolver::Uncached(resolver) => match resolver.find_sym(addr, &self.find_sym_opts)? {
-                Ok(sym) => {
+        let (
+            sym_name,
+            mangled_name,
+            sym_addr,
+            sym_size,
+            lang,
+            section,
+            label,
+            mut code_info,
+            mut inlined,
+            other_syms,
+        ) = match resolver {
+            Resolver::Uncached(resolver) => match self.find_syms(*resolver, addr)? {
+                Ok((sym, others)) => {
+                    let label = self
+                        .find_label(*resolver, addr, &sym)?
+                        .map(|label| Cow::Owned(label.to_string()));
                     let ResolvedSym {
                         name,
                         addr,
                         size,
                         lang,
-                        code_info,
-                        inlined,
+                        section,
+                        mut code_info,
+                        mut inlined,
                     } = sym;
 
-                    let name =
-                        Cow::Owned(self.maybe_demangle(Cow::Borrowed(name), lang).into_owned());
+                    if self.dedup_inlined_fns {
+                        inlined = dedup_inlined_fns(inlined);
+                    }
+
+                    if let Some(depth) = self.max_inline_depth {
+                        inlined = truncate_inlined_fns(&mut code_info, inlined, depth);
+                    }
+
+                    let demangled = self.maybe_demangle(Cow::Borrowed(name), lang);
+                    let mangled_name =
+                        (demangled != name).then(|| self.maybe_intern(Cow::Borrowed(name)));
+                    let name = self.maybe_intern(demangled);
+                    let section = section.map(|section| Cow::Owned(section.to_string()));
                     let code_info = code_info.map(|info| info.to_owned());
                     let inlined = Vec::from(inlined)
                         .into_iter()
                         .map(|inlined_fn| {
                             let InlinedFn {
                                 name,
+                                addr,
+                                offset,
                                 code_info,
                                 _non_exhaustive: (),
                             } = inlined_fn;
                             InlinedFn {
-                                name: Cow::Owned(self.maybe_demangle(name, lang).into_owned()),
+                                name: self.maybe_intern(self.maybe_demangle(name, lang)),
+                                addr,
+                                offset,
                                 code_info: code_info.map(|info| info.to_owned()),
                                 _non_exhaustive: (),
                             }
                         })
                         .collect::<Vec<_>>()
                         .into_boxed_slice();
+                    let other_syms = others
+                        .into_iter()
+                        .map(|other| self.other_sym(addr, other).to_owned())
+                        .collect::<Vec<_>>()
+                        .into_boxed_slice();
 
-                    (name, addr, size, code_info, inlined)
+                    (
+                        name,
+                        mangled_name,
+                        addr,
+                        size,
+                        lang,
+                        section,
+                        label,
+                        code_info,
+                        inlined,
+                        other_syms,
+                    )
                 }
                 Err(reason) => return Ok(Symbolized::Unknown(reason)),
             },
-            Resolver::Cached(resolver) => match resolver.find_sym(addr, &self.find_sym_opts)? {
-                Ok(sym) => {
+            Resolver::Cached(resolver) => match self.find_syms(*resolver, addr)? {
+                Ok((sym, others)) => {
+                    let label = self.find_label(*resolver, addr, &sym)?.map(Cow::Borrowed);
                     let ResolvedSym {
                         name,
                         addr,
                         size,
                         lang,
-                        code_info,
+                        section,
+                        mut code_info,
                         mut inlined,
                     } = sym;
 
-                    let name = self.maybe_demangle(Cow::Borrowed(name), lang);
+                    if self.dedup_inlined_fns {
+                        inlined = dedup_inlined_fns(inlined);
+                    }
+
+                    if let Some(depth) = self.max_inline_depth {
+                        inlined = truncate_inlined_fns(&mut code_info, inlined, depth);
+                    }
+
+                    let demangled = self.maybe_demangle(Cow::Borrowed(name), lang);
+                    let mangled_name = (demangled != name).then_some(Cow::Borrowed(name));
+                    let name = match demangled {
+                        name @ Cow::Owned(..) => self.maybe_intern(name),
+                        name @ Cow::Borrowed(..) => name,
+                    };
                     let () = inlined.iter_mut().for_each(|inlined_fn| {
                         let name = take(&mut inlined_fn.name);
-                        inlined_fn.name = self.maybe_demangle(name, lang);
+                        inlined_fn.name = match self.maybe_demangle(name, lang) {
+                            name @ Cow::Owned(..) => self.maybe_intern(name),
+                            name @ Cow::Borrowed(..) => name,
+                        };
                     });
-                    (name, addr, size, code_info, inlined)
+                    let section = section.map(Cow::Borrowed);
+                    let other_syms = others
+                        .into_iter()
+                        .map(|other| self.other_sym(addr, other))
+                        .collect::<Vec<_>>()
+                        .into_boxed_slice();
+                    (
+                        name,
+                        mangled_name,
+                        addr,
+                        size,
+                        lang,
+                        section,
+                        label,
+                        code_info,
+                        inlined,
+                        other_syms,
+                    )
                 }
                 Err(reason) => return Ok(Symbolized::Unknown(reason)),
             },
         };
 
+        if !self.src_path_remap.is_empty() {
+            let remap = self.src_path_remap.as_slice();
+            let () = code_info
+                .iter_mut()
+                .chain(
+                    inlined
+                        .iter_mut()
+                        .filter_map(|inlined_fn| inlined_fn.code_info.as_mut()),
+                )
+                .for_each(|info| remap_code_info(info, remap));
+        }
+
         let sym = Sym {
             name: sym_name,
+            mangled_name,
             addr: sym_addr,
             offset: (addr - sym_addr) as usize,
             size: sym_size,
+            lang,
+            section,
+            label,
+            module: None,
+            build_id: None,
             code_info,
             inlined,
+            other_syms,
             _non_exhaustive: (),
         };
         Ok(Symbolized::Sym(sym))
     }
 
+    /// Symbolize an absolute address inside an ELF file mapped at
+    /// `base`, spanning `len` bytes.
+    fn symbolize_mapped_addr<'slf>(
+        &'slf self,
+        addr: Addr,
+        base: Addr,
+        len: u64,
+        resolver: &'slf ElfResolver,
+    ) -> Result<Symbolized<'slf>> {
+        let file_off = match addr.checked_sub(base) {
+            Some(file_off) if file_off < len => file_off,
+            _ => return Ok(Symbolized::Unknown(Reason::Unmapped)),
+        };
+
+        match resolver.file_offset_to_virt_offset(file_off)? {
+            Some(addr) => self.symbolize_with_resolver(addr, &Resolver::Cached(resolver)),
+            None => Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
+        }
+    }
+
     /// Symbolize a list of addresses using the provided [`SymResolver`].
     fn symbolize_addrs<'slf>(
         &'slf self,
@@ -753,10 +1613,20 @@ impl Symbolizer {
                         if let Some(resolver) = (apk_dispatch)(info.clone())? {
                             resolver
                         } else {
-                            default_apk_dispatcher(info, debug_dirs)?
+                            default_apk_dispatcher(
+                                info,
+                                debug_dirs,
+                                self.sym_source,
+                                self.compiler_local_syms,
+                            )?
                         }
                     } else {
-                        default_apk_dispatcher(info, debug_dirs)?
+                        default_apk_dispatcher(
+                            info,
+                            debug_dirs,
+                            self.sym_source,
+                            self.compiler_local_syms,
+                        )?
                     };
 
                     Ok(resolver)
@@ -792,19 +1662,104 @@ impl Symbolizer {
         result
     }
 
-    #[cfg(feature = "breakpad")]
-    fn create_breakpad_resolver(&self, path: &Path, file: &File) -> Result<BreakpadResolver> {
-        let resolver = BreakpadResolver::from_file(path.to_path_buf(), file)?;
-        Ok(resolver)
-    }
-
-    #[cfg(feature = "breakpad")]
+    fn create_archive_resolver(
+        &self,
+        archive: &archive::Archive,
+        path: &Path,
+        member: &Path,
+        debug_syms: bool,
+    ) -> Result<ElfResolver> {
+        let entry = archive.find_member(member)?.ok_or_invalid_input(|| {
+            format!(
+                "archive `{}` does not contain member `{}`",
+                path.display(),
+                member.display()
+            )
+        })?;
+
+        let mut parser = if let Some(range) = entry.data {
+            let mmap = archive.mmap().constrain(range).ok_or_invalid_input(|| {
+                format!("archive member `{}` is out of bounds", member.display())
+            })?;
+            // Report members in the customary `archive(member)` notation.
+            let mut module = path.as_os_str().to_os_string();
+            let () = module.push("(");
+            let () = module.push(member.as_os_str());
+            let () = module.push(")");
+            ElfParser::from_mmap(mmap, Some(PathBuf::from(module)))
+        } else {
+            // Members of thin archives are referenced relative to the
+            // directory containing the archive.
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            ElfParser::open(&dir.join(entry.name))?
+        };
+        let () = parser.set_sym_source(self.sym_source);
+        let () = parser.set_compiler_local_syms(self.compiler_local_syms);
+
+        let debug_dirs = self.maybe_debug_dirs(debug_syms);
+        let resolver = ElfResolver::from_parser(Rc::new(parser), debug_dirs)?;
+        Ok(resolver)
+    }
+
+    fn archive_resolver<'slf>(
+        &'slf self,
+        path: &Path,
+        member: &Path,
+        debug_syms: bool,
+    ) -> Result<&'slf Rc<ElfResolver>> {
+        let (file, cell) = self.archive_cache.entry(path)?;
+        let (archive, resolvers) = cell.get_or_try_init(|| {
+            let archive = archive::Archive::with_mmap(Mmap::builder().map(file)?)?;
+            let resolvers = InsertMap::new();
+            Result::<_, Error>::Ok((archive, resolvers))
+        })?;
+
+        let resolver = resolvers.get_or_try_insert((member.to_path_buf(), debug_syms), || {
+            let resolver = self.create_archive_resolver(archive, path, member, debug_syms)?;
+            Result::<_, Error>::Ok(Rc::new(resolver))
+        })?;
+        Ok(resolver)
+    }
+
+    #[cfg(feature = "breakpad")]
+    fn create_breakpad_resolver(&self, path: &Path, file: &File) -> Result<BreakpadResolver> {
+        let resolver = BreakpadResolver::from_file(path.to_path_buf(), file)?;
+        Ok(resolver)
+    }
+
+    #[cfg(feature = "breakpad")]
     fn breakpad_resolver<'slf>(&'slf self, path: &Path) -> Result<&'slf BreakpadResolver> {
         let (file, cell) = self.breakpad_cache.entry(path)?;
         let resolver = cell.get_or_try_init(|| self.create_breakpad_resolver(path, file))?;
         Ok(resolver)
     }
 
+    #[cfg(feature = "macho")]
+    fn create_macho_resolver(&self, path: &Path, file: &File) -> Result<MachOResolver> {
+        let resolver = MachOResolver::from_file(path.to_path_buf(), file)?;
+        Ok(resolver)
+    }
+
+    #[cfg(feature = "macho")]
+    fn macho_resolver<'slf>(&'slf self, path: &Path) -> Result<&'slf MachOResolver> {
+        let (file, cell) = self.macho_cache.entry(path)?;
+        let resolver = cell.get_or_try_init(|| self.create_macho_resolver(path, file))?;
+        Ok(resolver)
+    }
+
+    #[cfg(feature = "pe")]
+    fn create_pe_resolver(&self, path: &Path, file: &File) -> Result<PeResolver> {
+        let resolver = PeResolver::from_file(path.to_path_buf(), file)?;
+        Ok(resolver)
+    }
+
+    #[cfg(feature = "pe")]
+    fn pe_resolver<'slf>(&'slf self, path: &Path) -> Result<&'slf PeResolver> {
+        let (file, cell) = self.pe_cache.entry(path)?;
+        let resolver = cell.get_or_try_init(|| self.create_pe_resolver(path, file))?;
+        Ok(resolver)
+    }
+
     fn create_perf_map(&self, path: &Path, file: &File) -> Result<PerfMap> {
         let perf_map = PerfMap::from_file(path, file)?;
         Ok(perf_map)
@@ -823,6 +1778,23 @@ impl Symbolizer {
         }
     }
 
+    fn create_jit_dump(&self, path: &Path, file: &File) -> Result<JitDump> {
+        let jit_dump = JitDump::from_file(path, file)?;
+        Ok(jit_dump)
+    }
+
+    fn jit_dump(&self, path: &Path) -> Result<Option<&JitDump>> {
+        match self.jit_dump_cache.entry(path) {
+            Ok((file, cell)) => {
+                let jit_dump = cell.get_or_try_init(|| self.create_jit_dump(path, file))?;
+                Ok(Some(jit_dump))
+            }
+            // The process may have exited in the meantime.
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("failed to open JIT dump `{path:?}`")),
+        }
+    }
+
     fn process_dispatch_resolver<'slf>(
         &'slf self,
         range: Range<Addr>,
@@ -854,8 +1826,16 @@ impl Symbolizer {
         debug_syms: bool,
         perf_map: bool,
         map_files: bool,
+        maps_override: Option<&Path>,
     ) -> Result<Vec<Symbolized>> {
-        let mut entry_iter = maps::parse(pid)?;
+        let default_maps;
+        let maps_file = if let Some(maps_file) = maps_override {
+            maps_file
+        } else {
+            default_maps = maps::maps_path(pid);
+            &default_maps
+        };
+        let mut entry_iter = maps::parse_path(maps_file, pid)?;
         let entries = |_addr| entry_iter.next();
 
         let mut handler = SymbolizeHandler {
@@ -864,6 +1844,9 @@ impl Symbolizer {
             debug_syms,
             perf_map,
             map_files,
+            maps_file: maps_file.to_path_buf(),
+            jit_dump_path: None,
+            vdso: None,
             all_symbols: Vec::with_capacity(addrs.len()),
         };
 
@@ -898,6 +1881,7 @@ impl Symbolizer {
             kallsyms,
             kernel_image,
             debug_syms,
+            module_syms,
             _non_exhaustive: (),
         } = src;
 
@@ -920,9 +1904,12 @@ impl Symbolizer {
         };
 
         let elf_resolver = if let Some(image) = kernel_image {
-            let resolver = self
-                .elf_cache
-                .elf_resolver(image, self.maybe_debug_dirs(*debug_syms))?;
+            let resolver = self.elf_cache.elf_resolver(
+                image,
+                self.maybe_debug_dirs(*debug_syms),
+                self.sym_source,
+                self.compiler_local_syms,
+            )?;
             Some(resolver)
         } else {
             let release = uname_release()?.to_str().unwrap().to_string();
@@ -934,9 +1921,12 @@ impl Symbolizer {
             });
 
             if let Some(image) = kernel_image {
-                let result = self
-                    .elf_cache
-                    .elf_resolver(&image, self.maybe_debug_dirs(*debug_syms));
+                let result = self.elf_cache.elf_resolver(
+                    &image,
+                    self.maybe_debug_dirs(*debug_syms),
+                    self.sym_source,
+                    self.compiler_local_syms,
+                );
                 match result {
                     Ok(resolver) => Some(resolver),
                     Err(err) => {
@@ -952,7 +1942,36 @@ impl Symbolizer {
             }
         };
 
-        KernelResolver::new(ksym_resolver.cloned(), elf_resolver.cloned())
+        let modules = if *module_syms {
+            match self.kernel_modules(*debug_syms) {
+                Ok(modules) => modules,
+                Err(err) => {
+                    log::warn!("failed to load kernel modules: {err}; ignoring...");
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        KernelResolver::new(ksym_resolver.cloned(), elf_resolver.cloned(), modules)
+    }
+
+    /// Load all kernel modules of the running kernel.
+    #[cfg(not(windows))]
+    fn kernel_modules(&self, debug_syms: bool) -> Result<Vec<KernelModule>> {
+        let release = uname_release()?.to_str().unwrap().to_string();
+        let dir = Path::new(LIB_MODULES_DIR).join(release);
+        let files = find_module_files(&dir)?;
+        load_modules(Path::new(SYS_MODULE_DIR), &files, |path| {
+            let resolver = self.elf_cache.elf_resolver(
+                path,
+                self.maybe_debug_dirs(debug_syms),
+                self.sym_source,
+                self.compiler_local_syms,
+            )?;
+            Ok(Rc::clone(resolver))
+        })
     }
 
     #[cfg(windows)]
@@ -992,11 +2011,59 @@ impl Symbolizer {
     /// | Ksym     | symbol size                      | no                   | N/A                    |
     /// |          | source code location information | no                   | N/A                    |
     /// |          | inlined function information     | no                   | N/A                    |
+    /// | Mach-O   | symbol size                      | no                   | yes (inferred)         |
+    /// |          | source code location information | no                   | N/A                    |
+    /// |          | inlined function information     | no                   | N/A                    |
+    /// | PE       | symbol size                      | no                   | yes (inferred)         |
+    /// |          | source code location information | no                   | N/A                    |
+    /// |          | inlined function information     | no                   | N/A                    |
     #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(src = ?src, addrs = ?input.map(Hexify))))]
     pub fn symbolize<'slf>(
         &'slf self,
         src: &Source,
         input: Input<&[u64]>,
+    ) -> Result<Vec<Symbolized<'slf>>> {
+        let filtered = self.filter_addrs(input);
+        let input = input.map(|_addrs| filtered.as_ref());
+
+        let progress = if let Some(Dbg(progress)) = &self.progress_handler {
+            progress
+        } else {
+            return self.symbolize_batch(src, input)
+        };
+
+        let addrs = *input.as_inner_ref();
+        let total = addrs.len();
+        let mut results = Vec::with_capacity(total);
+        for batch in addrs.chunks(SYMBOLIZE_BATCH_SIZE) {
+            let symbolized = self.symbolize_batch(src, input.map(|_addrs| batch))?;
+            let () = results.extend(symbolized);
+            if progress(results.len(), total).is_break() {
+                break
+            }
+        }
+        Ok(results)
+    }
+
+    /// Apply the configured address filter, if any, to the absolute
+    /// addresses in `input`.
+    fn filter_addrs<'inp>(&self, input: Input<&'inp [u64]>) -> Cow<'inp, [u64]> {
+        match (&self.addr_filter, input) {
+            (Some(Dbg(filter)), Input::AbsAddr(addrs)) => {
+                Cow::Owned(addrs.iter().map(|addr| filter(*addr)).collect())
+            }
+            (_, input) => Cow::Borrowed(input.into_inner()),
+        }
+    }
+
+    /// Symbolize a batch of addresses in one go.
+    ///
+    /// In contrast to [`Symbolizer::symbolize`], neither the address
+    /// filter is applied nor is progress reported.
+    fn symbolize_batch<'slf>(
+        &'slf self,
+        src: &Source,
+        input: Input<&[u64]>,
     ) -> Result<Vec<Symbolized<'slf>>> {
         match src {
             #[cfg(feature = "apk")]
@@ -1028,6 +2095,46 @@ impl Symbolizer {
                     )
                     .collect(),
             },
+            Source::Archive(Archive {
+                path,
+                member,
+                debug_syms,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.archive_resolver(path, member, *debug_syms)?;
+                let mut symbols = match input {
+                    Input::VirtOffset(addrs) => addrs
+                        .iter()
+                        .map(|addr| {
+                            self.symbolize_with_resolver(*addr, &Resolver::Cached(resolver.deref()))
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    Input::AbsAddr(..) => {
+                        return Err(Error::with_unsupported(
+                            "archive symbolization does not support absolute address inputs",
+                        ))
+                    }
+                    Input::FileOffset(offsets) => offsets
+                        .iter()
+                        .map(
+                            |offset| match resolver.file_offset_to_virt_offset(*offset)? {
+                                Some(addr) => self.symbolize_with_resolver(
+                                    addr,
+                                    &Resolver::Cached(resolver.deref()),
+                                ),
+                                None => Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
+                            },
+                        )
+                        .collect::<Result<Vec<_>>>()?,
+                };
+
+                let module = resolver.path().map(Cow::Borrowed);
+                let build_id = elf_build_id(resolver);
+                let () = symbols
+                    .iter_mut()
+                    .for_each(|symbolized| set_module(symbolized, &module, &build_id));
+                Ok(symbols)
+            }
             #[cfg(feature = "breakpad")]
             Source::Breakpad(Breakpad {
                 path,
@@ -1054,22 +2161,38 @@ impl Symbolizer {
             Source::Elf(Elf {
                 path,
                 debug_syms,
+                base_addr,
                 _non_exhaustive: (),
             }) => {
-                let resolver = self
-                    .elf_cache
-                    .elf_resolver(path, self.maybe_debug_dirs(*debug_syms))?;
-                match input {
+                let resolver = self.elf_cache.elf_resolver(
+                    path,
+                    self.maybe_debug_dirs(*debug_syms),
+                    self.sym_source,
+                    self.compiler_local_syms,
+                )?;
+                let mut symbols = match input {
                     Input::VirtOffset(addrs) => addrs
                         .iter()
                         .map(|addr| {
                             self.symbolize_with_resolver(*addr, &Resolver::Cached(resolver.deref()))
                         })
-                        .collect(),
-                    Input::AbsAddr(..) => {
-                        return Err(Error::with_unsupported(
-                            "ELF symbolization does not support absolute address inputs",
-                        ))
+                        .collect::<Result<Vec<_>>>()?,
+                    Input::AbsAddr(addrs) => {
+                        let base_addr = base_addr.ok_or_else(|| {
+                            Error::with_unsupported(
+                                "ELF symbolization requires a base address for absolute address inputs",
+                            )
+                        })?;
+                        addrs
+                            .iter()
+                            .map(|addr| match addr.checked_sub(base_addr) {
+                                Some(addr) => self.symbolize_with_resolver(
+                                    addr,
+                                    &Resolver::Cached(resolver.deref()),
+                                ),
+                                None => Ok(Symbolized::Unknown(Reason::Unmapped)),
+                            })
+                            .collect::<Result<Vec<_>>>()?
                     }
                     Input::FileOffset(offsets) => offsets
                         .iter()
@@ -1082,8 +2205,15 @@ impl Symbolizer {
                                 None => Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
                             },
                         )
-                        .collect(),
-                }
+                        .collect::<Result<Vec<_>>>()?,
+                };
+
+                let module = resolver.path().map(Cow::Borrowed);
+                let build_id = elf_build_id(resolver);
+                let () = symbols
+                    .iter_mut()
+                    .for_each(|symbolized| set_module(symbolized, &module, &build_id));
+                Ok(symbols)
             }
             Source::Kernel(kernel) => {
                 let addrs = match input {
@@ -1104,11 +2234,149 @@ impl Symbolizer {
                 let symbols = self.symbolize_addrs(addrs, &Resolver::Uncached(resolver.deref()))?;
                 Ok(symbols)
             }
+            #[cfg(feature = "macho")]
+            Source::MachO(MachO {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.macho_resolver(path)?;
+                match input {
+                    Input::VirtOffset(addrs) => {
+                        self.symbolize_addrs(addrs, &Resolver::Cached(resolver))
+                    }
+                    Input::AbsAddr(..) => Err(Error::with_unsupported(
+                        "Mach-O symbolization does not support absolute address inputs",
+                    )),
+                    Input::FileOffset(offsets) => offsets
+                        .iter()
+                        .map(
+                            |offset| match resolver.file_offset_to_virt_offset(*offset)? {
+                                Some(addr) => {
+                                    self.symbolize_with_resolver(addr, &Resolver::Cached(resolver))
+                                }
+                                None => Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
+                            },
+                        )
+                        .collect(),
+                }
+            }
+            #[cfg(feature = "pe")]
+            Source::Pe(Pe {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.pe_resolver(path)?;
+                match input {
+                    Input::VirtOffset(addrs) => {
+                        self.symbolize_addrs(addrs, &Resolver::Cached(resolver))
+                    }
+                    Input::AbsAddr(..) => Err(Error::with_unsupported(
+                        "PE symbolization does not support absolute address inputs",
+                    )),
+                    Input::FileOffset(offsets) => offsets
+                        .iter()
+                        .map(
+                            |offset| match resolver.file_offset_to_virt_offset(*offset)? {
+                                Some(addr) => {
+                                    self.symbolize_with_resolver(addr, &Resolver::Cached(resolver))
+                                }
+                                None => Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
+                            },
+                        )
+                        .collect(),
+                }
+            }
+            Source::Fallback(srcs) => {
+                let mut symbols = None::<Vec<Symbolized<'_>>>;
+                let mut last_err = None;
+                for src in srcs {
+                    let result = if let Some(symbols) = &mut symbols {
+                        let (idxs, addrs) = symbols
+                            .iter()
+                            .zip(input.into_inner())
+                            .enumerate()
+                            .filter_map(|(idx, (symbolized, addr))| {
+                                matches!(symbolized, Symbolized::Unknown(..))
+                                    .then_some((idx, *addr))
+                            })
+                            .unzip::<_, _, Vec<_>, Vec<_>>();
+                        if idxs.is_empty() {
+                            break
+                        }
+
+                        self.symbolize_batch(src, input.map(|_| addrs.as_slice()))
+                            .map(|fallback| {
+                                for (idx, symbolized) in idxs.into_iter().zip(fallback) {
+                                    symbols[idx] = symbolized;
+                                }
+                            })
+                    } else {
+                        self.symbolize_batch(src, input)
+                            .map(|syms| symbols = Some(syms))
+                    };
+
+                    // An error with one source is treated like the
+                    // source being unable to symbolize the addresses
+                    // at hand.
+                    if let Err(err) = result {
+                        log::debug!("failed to symbolize using {src:?}: {err:#}");
+                        last_err = Some(err);
+                    }
+                }
+
+                match (symbols, last_err) {
+                    (Some(symbols), _) => Ok(symbols),
+                    (None, Some(err)) => Err(err),
+                    (None, None) => Err(Error::with_invalid_input(
+                        "no sources provided for fallback symbolization",
+                    )),
+                }
+            }
+            Source::MappedElf(MappedElf {
+                path,
+                base,
+                len,
+                debug_syms,
+                _non_exhaustive: (),
+            }) => {
+                let addrs = match input {
+                    Input::AbsAddr(addrs) => addrs,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "mapped ELF symbolization does not support virtual offset inputs",
+                        ))
+                    }
+                    Input::FileOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "mapped ELF symbolization does not support file offset inputs",
+                        ))
+                    }
+                };
+
+                let resolver = self.elf_cache.elf_resolver(
+                    path,
+                    self.maybe_debug_dirs(*debug_syms),
+                    self.sym_source,
+                    self.compiler_local_syms,
+                )?;
+                let module = resolver.path().map(Cow::Borrowed);
+                let build_id = elf_build_id(resolver);
+                addrs
+                    .iter()
+                    .map(|addr| {
+                        let mut symbolized =
+                            self.symbolize_mapped_addr(*addr, *base, *len, resolver)?;
+                        let () = set_module(&mut symbolized, &module, &build_id);
+                        Ok(symbolized)
+                    })
+                    .collect()
+            }
             Source::Process(Process {
                 pid,
                 debug_syms,
                 perf_map,
                 map_files,
+                maps_override,
                 _non_exhaustive: (),
             }) => {
                 let addrs = match input {
@@ -1125,7 +2393,14 @@ impl Symbolizer {
                     }
                 };
 
-                self.symbolize_user_addrs(addrs, *pid, *debug_syms, *perf_map, *map_files)
+                self.symbolize_user_addrs(
+                    addrs,
+                    *pid,
+                    *debug_syms,
+                    *perf_map,
+                    *map_files,
+                    maps_override.as_deref(),
+                )
             }
             #[cfg(feature = "gsym")]
             Source::Gsym(Gsym::Data(GsymData {
@@ -1177,6 +2452,127 @@ impl Symbolizer {
         }
     }
 
+    /// Symbolize a list of addresses, lazily.
+    ///
+    /// This method works like [`symbolize`][Self::symbolize], but
+    /// instead of returning all results at once, it returns an iterator
+    /// yielding one [`Symbolized`] object for each input address, in
+    /// the order of input addresses. Addresses are symbolized in
+    /// batches as the iterator is advanced, meaning that memory usage
+    /// is bounded by the batch size and not by the number of input
+    /// addresses.
+    ///
+    /// If symbolization of a batch fails, the error is reported and the
+    /// iterator is exhausted.
+    pub fn symbolize_iter<'slf, 'inp>(
+        &'slf self,
+        src: &'inp Source<'inp>,
+        input: Input<&'inp [u64]>,
+    ) -> impl Iterator<Item = Result<Symbolized<'slf>>> + 'inp
+    where
+        'slf: 'inp,
+    {
+        let (addrs, into_input): (_, fn(&[u64]) -> Input<&[u64]>) = match input {
+            Input::AbsAddr(addrs) => (addrs, Input::AbsAddr),
+            Input::VirtOffset(addrs) => (addrs, Input::VirtOffset),
+            Input::FileOffset(addrs) => (addrs, Input::FileOffset),
+        };
+
+        let mut failed = false;
+        addrs
+            .chunks(SYMBOLIZE_BATCH_SIZE)
+            .map_while(move |batch| {
+                if failed {
+                    return None
+                }
+                let batch = into_input(batch);
+                let filtered = self.filter_addrs(batch);
+                let result = self.symbolize_batch(src, batch.map(|_batch| filtered.as_ref()));
+                failed = result.is_err();
+                Some(result)
+            })
+            .flat_map(|result| {
+                let (syms, err) = match result {
+                    Ok(syms) => (syms, None),
+                    Err(err) => (Vec::new(), Some(err)),
+                };
+                syms.into_iter().map(Ok).chain(err.map(Err))
+            })
+    }
+
+    /// Symbolize a list of offsets relative to the start of an ELF
+    /// section.
+    ///
+    /// Each offset is converted into a virtual offset by adding it to
+    /// the address of the section called `section` (e.g., `.text`).
+    /// Symbolization then proceeds as it would for
+    /// [`Input::VirtOffset`]. Offsets not within the section are
+    /// reported as [`Reason::UnknownAddr`].
+    ///
+    /// Only [`Source::Elf`] is supported.
+    pub fn symbolize_section_offsets<'slf>(
+        &'slf self,
+        src: &Source,
+        section: &str,
+        offsets: &[u64],
+    ) -> Result<Vec<Symbolized<'slf>>> {
+        let (path, debug_syms) = match src {
+            Source::Elf(Elf {
+                path,
+                debug_syms,
+                base_addr: _,
+                _non_exhaustive: (),
+            }) => (path, *debug_syms),
+            _ => {
+                return Err(Error::with_unsupported(
+                    "section relative symbolization is only supported for ELF sources",
+                ))
+            }
+        };
+
+        let resolver = self.elf_cache.elf_resolver(
+            path,
+            self.maybe_debug_dirs(debug_syms),
+            self.sym_source,
+            self.compiler_local_syms,
+        )?;
+        let parser = resolver.parser();
+        let idx = parser.find_section(section)?.ok_or_else(|| {
+            Error::with_not_found(format!(
+                "ELF section `{section}` not found in {}",
+                path.display()
+            ))
+        })?;
+        let shdr = parser
+            .section_headers()?
+            .get(idx)
+            .ok_or_invalid_data(|| format!("ELF section index ({idx}) out of bounds"))?;
+
+        let addrs = offsets
+            .iter()
+            .map(|offset| {
+                if *offset < shdr.sh_size {
+                    shdr.sh_addr.checked_add(*offset)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let valid = addrs.iter().flatten().copied().collect::<Vec<_>>();
+        let mut symbolized = self.symbolize(src, Input::VirtOffset(&valid))?.into_iter();
+        // NB: Symbolization may have been aborted early by the progress
+        //     handler, in which case we only report results up to that
+        //     point.
+        let results = addrs
+            .iter()
+            .map_while(|addr| match addr {
+                Some(_) => symbolized.next(),
+                None => Some(Symbolized::Unknown(Reason::UnknownAddr)),
+            })
+            .collect();
+        Ok(results)
+    }
+
     /// Symbolize a single input address/offset.
     ///
     /// In general, it is more performant to symbolize addresses in batches
@@ -1187,6 +2583,20 @@ impl Symbolizer {
         &'slf self,
         src: &Source,
         input: Input<u64>,
+    ) -> Result<Symbolized<'slf>> {
+        let input = match (&self.addr_filter, input) {
+            (Some(Dbg(filter)), Input::AbsAddr(addr)) => Input::AbsAddr(filter(addr)),
+            (_, input) => input,
+        };
+        self.symbolize_single_impl(src, input)
+    }
+
+    /// Symbolize a single input address/offset, without applying the
+    /// address filter.
+    fn symbolize_single_impl<'slf>(
+        &'slf self,
+        src: &Source,
+        input: Input<u64>,
     ) -> Result<Symbolized<'slf>> {
         match src {
             #[cfg(feature = "apk")]
@@ -1213,6 +2623,34 @@ impl Symbolizer {
                     None => return Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
                 },
             },
+            Source::Archive(Archive {
+                path,
+                member,
+                debug_syms,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.archive_resolver(path, member, *debug_syms)?;
+                let addr = match input {
+                    Input::VirtOffset(addr) => addr,
+                    Input::AbsAddr(..) => {
+                        return Err(Error::with_unsupported(
+                            "archive symbolization does not support absolute address inputs",
+                        ))
+                    }
+                    Input::FileOffset(offset) => {
+                        match resolver.file_offset_to_virt_offset(offset)? {
+                            Some(addr) => addr,
+                            None => return Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
+                        }
+                    }
+                };
+
+                let mut symbolized =
+                    self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))?;
+                let module = resolver.path().map(Cow::Borrowed);
+                let () = set_module(&mut symbolized, &module, &elf_build_id(resolver));
+                Ok(symbolized)
+            }
             #[cfg(feature = "breakpad")]
             Source::Breakpad(Breakpad {
                 path,
@@ -1238,17 +2676,27 @@ impl Symbolizer {
             Source::Elf(Elf {
                 path,
                 debug_syms,
+                base_addr,
                 _non_exhaustive: (),
             }) => {
-                let resolver = self
-                    .elf_cache
-                    .elf_resolver(path, self.maybe_debug_dirs(*debug_syms))?;
+                let resolver = self.elf_cache.elf_resolver(
+                    path,
+                    self.maybe_debug_dirs(*debug_syms),
+                    self.sym_source,
+                    self.compiler_local_syms,
+                )?;
                 let addr = match input {
                     Input::VirtOffset(addr) => addr,
-                    Input::AbsAddr(..) => {
-                        return Err(Error::with_unsupported(
-                            "ELF symbolization does not support absolute address inputs",
-                        ))
+                    Input::AbsAddr(addr) => {
+                        let base_addr = base_addr.ok_or_else(|| {
+                            Error::with_unsupported(
+                                "ELF symbolization requires a base address for absolute address inputs",
+                            )
+                        })?;
+                        match addr.checked_sub(base_addr) {
+                            Some(addr) => addr,
+                            None => return Ok(Symbolized::Unknown(Reason::Unmapped)),
+                        }
                     }
                     Input::FileOffset(offset) => {
                         match resolver.file_offset_to_virt_offset(offset)? {
@@ -1258,7 +2706,11 @@ impl Symbolizer {
                     }
                 };
 
-                self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))
+                let mut symbolized =
+                    self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))?;
+                let module = resolver.path().map(Cow::Borrowed);
+                let () = set_module(&mut symbolized, &module, &elf_build_id(resolver));
+                Ok(symbolized)
             }
             Source::Kernel(kernel) => {
                 let addr = match input {
@@ -1278,11 +2730,112 @@ impl Symbolizer {
                 let resolver = Rc::new(self.create_kernel_resolver(kernel)?);
                 self.symbolize_with_resolver(addr, &Resolver::Uncached(resolver.deref()))
             }
-            Source::Process(Process {
+            #[cfg(feature = "macho")]
+            Source::MachO(MachO {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.macho_resolver(path)?;
+                let addr = match input {
+                    Input::VirtOffset(addr) => addr,
+                    Input::AbsAddr(..) => {
+                        return Err(Error::with_unsupported(
+                            "Mach-O symbolization does not support absolute address inputs",
+                        ))
+                    }
+                    Input::FileOffset(offset) => {
+                        match resolver.file_offset_to_virt_offset(offset)? {
+                            Some(addr) => addr,
+                            None => return Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
+                        }
+                    }
+                };
+
+                self.symbolize_with_resolver(addr, &Resolver::Cached(resolver))
+            }
+            #[cfg(feature = "pe")]
+            Source::Pe(Pe {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.pe_resolver(path)?;
+                let addr = match input {
+                    Input::VirtOffset(addr) => addr,
+                    Input::AbsAddr(..) => {
+                        return Err(Error::with_unsupported(
+                            "PE symbolization does not support absolute address inputs",
+                        ))
+                    }
+                    Input::FileOffset(offset) => {
+                        match resolver.file_offset_to_virt_offset(offset)? {
+                            Some(addr) => addr,
+                            None => return Ok(Symbolized::Unknown(Reason::InvalidFileOffset)),
+                        }
+                    }
+                };
+
+                self.symbolize_with_resolver(addr, &Resolver::Cached(resolver))
+            }
+            Source::Fallback(srcs) => {
+                let mut symbolized = None;
+                let mut last_err = None;
+                for src in srcs {
+                    match self.symbolize_single_impl(src, input) {
+                        Ok(result @ Symbolized::Sym(..)) => return Ok(result),
+                        Ok(result) => symbolized = Some(result),
+                        Err(err) => {
+                            log::debug!("failed to symbolize using {src:?}: {err:#}");
+                            last_err = Some(err);
+                        }
+                    }
+                }
+
+                match (symbolized, last_err) {
+                    (Some(symbolized), _) => Ok(symbolized),
+                    (None, Some(err)) => Err(err),
+                    (None, None) => Err(Error::with_invalid_input(
+                        "no sources provided for fallback symbolization",
+                    )),
+                }
+            }
+            Source::MappedElf(MappedElf {
+                path,
+                base,
+                len,
+                debug_syms,
+                _non_exhaustive: (),
+            }) => {
+                let addr = match input {
+                    Input::AbsAddr(addr) => addr,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "mapped ELF symbolization does not support virtual offset inputs",
+                        ))
+                    }
+                    Input::FileOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "mapped ELF symbolization does not support file offset inputs",
+                        ))
+                    }
+                };
+
+                let resolver = self.elf_cache.elf_resolver(
+                    path,
+                    self.maybe_debug_dirs(*debug_syms),
+                    self.sym_source,
+                    self.compiler_local_syms,
+                )?;
+                let mut symbolized = self.symbolize_mapped_addr(addr, *base, *len, resolver)?;
+                let module = resolver.path().map(Cow::Borrowed);
+                let () = set_module(&mut symbolized, &module, &elf_build_id(resolver));
+                Ok(symbolized)
+            }
+            Source::Process(Process {
                 pid,
                 debug_syms,
                 perf_map,
                 map_files,
+                maps_override,
                 _non_exhaustive: (),
             }) => {
                 let addr = match input {
@@ -1299,8 +2852,14 @@ impl Symbolizer {
                     }
                 };
 
-                let mut symbols =
-                    self.symbolize_user_addrs(&[addr], *pid, *debug_syms, *perf_map, *map_files)?;
+                let mut symbols = self.symbolize_user_addrs(
+                    &[addr],
+                    *pid,
+                    *debug_syms,
+                    *perf_map,
+                    *map_files,
+                    maps_override.as_deref(),
+                )?;
                 debug_assert!(symbols.len() == 1, "{symbols:#?}");
                 // SANITY: `symbolize_user_addrs` should *always* return
                 //         one result for one input (except on error
@@ -1355,6 +2914,213 @@ impl Symbolizer {
         }
     }
 
+    /// Eagerly load and parse all data required for symbolizing
+    /// addresses using the provided source.
+    ///
+    /// Symbolization data are loaded lazily and cached for later use,
+    /// meaning that the first request for a given source may take
+    /// considerably longer than subsequent ones. This method can be used
+    /// to pay this cost up front, e.g., before entering a latency
+    /// sensitive phase. Note that with code information and inlined
+    /// function reporting enabled, all debug information is parsed,
+    /// which can be expensive for large binaries.
+    ///
+    /// For [`Source::Process`], all executable files currently mapped
+    /// into the process are loaded. Files that cannot be loaded are
+    /// skipped. Only [`Source::Elf`], [`Source::MappedElf`], and
+    /// [`Source::Process`] are supported.
+    #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(src = ?src)))]
+    pub fn warmup(&self, src: &Source) -> Result<()> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_syms,
+                base_addr: _,
+                _non_exhaustive: (),
+            })
+            | Source::MappedElf(MappedElf {
+                path,
+                base: _,
+                len: _,
+                debug_syms,
+                _non_exhaustive: (),
+            }) => self.warmup_elf(path, *debug_syms),
+            Source::Process(Process {
+                pid,
+                debug_syms,
+                perf_map: _,
+                map_files,
+                maps_override,
+                _non_exhaustive: (),
+            }) => self.warmup_process(*pid, *debug_syms, *map_files, maps_override.as_deref()),
+            _ => Err(Error::with_unsupported(
+                "warmup is only supported for ELF and process sources",
+            )),
+        }
+    }
+
+    fn warmup_elf(&self, path: &Path, debug_syms: bool) -> Result<()> {
+        let resolver = self.elf_cache.elf_resolver(
+            path,
+            self.maybe_debug_dirs(debug_syms),
+            self.sym_source,
+            self.compiler_local_syms,
+        )?;
+        resolver.warmup(&self.find_sym_opts)
+    }
+
+    fn warmup_process(
+        &self,
+        pid: Pid,
+        debug_syms: bool,
+        map_files: bool,
+        maps_override: Option<&Path>,
+    ) -> Result<()> {
+        let default_maps;
+        let maps_file = if let Some(maps_file) = maps_override {
+            maps_file
+        } else {
+            default_maps = maps::maps_path(pid);
+            &default_maps
+        };
+
+        for result in maps::parse_path(maps_file, pid)? {
+            let entry = result?;
+            if (entry.perm & Perm::X) == Perm::default() {
+                continue
+            }
+
+            if let Some(path_name) = &entry.path_name {
+                if self
+                    .process_dispatch_resolver(entry.range.clone(), path_name)?
+                    .is_some()
+                {
+                    continue
+                }
+            }
+
+            if let Some(PathName::Path(entry_path)) = &entry.path_name {
+                let path = if map_files {
+                    &entry_path.maps_file
+                } else {
+                    &entry_path.symbolic_path
+                };
+                if let Err(err) = self.warmup_elf(path, debug_syms) {
+                    log::debug!("failed to warm up {}: {err}", path.display());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Inspect a symbolization source without symbolizing any addresses.
+    ///
+    /// This method can be used to check up front whether a source is
+    /// usable and what kind of data it contains. E.g., a user interface
+    /// may report that a shared object is stripped and does not contain
+    /// debug information before attempting symbolization.
+    ///
+    /// Only file based sources ([`Source::Elf`], [`Source::MappedElf`],
+    /// [`Source::Breakpad`], [`Source::Gsym`], `Source::MachO`, and
+    /// `Source::Pe`) are supported.
+    #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(src = ?src)))]
+    pub fn inspect(&self, src: &Source) -> Result<SourceInfo> {
+        match src {
+            #[cfg(feature = "apk")]
+            Source::Apk(..) => Err(Error::with_unsupported(
+                "APK sources do not support inspection",
+            )),
+            Source::Archive(..) => Err(Error::with_unsupported(
+                "archive sources do not support inspection",
+            )),
+            #[cfg(feature = "breakpad")]
+            Source::Breakpad(Breakpad {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let _resolver = self.breakpad_resolver(path)?;
+                Ok(non_elf_source_info(SrcFormat::Breakpad))
+            }
+            Source::Elf(Elf {
+                path,
+                debug_syms: _,
+                base_addr: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_cache.elf_resolver(
+                    path,
+                    None,
+                    self.sym_source,
+                    self.compiler_local_syms,
+                )?;
+                let parser = resolver.parser();
+                let build_id =
+                    read_build_id(parser)?.map(|build_id| Cow::Owned(build_id.into_owned()));
+                let info = SourceInfo {
+                    format: SrcFormat::Elf,
+                    arch: Some(parser.machine()?),
+                    has_symtab: parser.find_section(".symtab")?.is_some(),
+                    has_dynsym: parser.find_section(".dynsym")?.is_some(),
+                    has_debug_line: parser.find_section(".debug_line")?.is_some(),
+                    debug_info: elf_debug_info(parser, &build_id)?,
+                    build_id,
+                    sym_stats: Some(parser.sym_stats()?),
+                    _non_exhaustive: (),
+                };
+                Ok(info)
+            }
+            Source::Fallback(..) => Err(Error::with_unsupported(
+                "fallback sources do not support inspection",
+            )),
+            Source::Kernel(..) => Err(Error::with_unsupported(
+                "kernel sources do not support inspection",
+            )),
+            #[cfg(feature = "macho")]
+            Source::MachO(MachO {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let _resolver = self.macho_resolver(path)?;
+                Ok(non_elf_source_info(SrcFormat::MachO))
+            }
+            #[cfg(feature = "pe")]
+            Source::Pe(Pe {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let _resolver = self.pe_resolver(path)?;
+                Ok(non_elf_source_info(SrcFormat::Pe))
+            }
+            Source::MappedElf(MappedElf {
+                path,
+                base: _,
+                len: _,
+                debug_syms: _,
+                _non_exhaustive: (),
+            }) => self.inspect(&Source::Elf(Elf::new(path))),
+            Source::Process(..) => Err(Error::with_unsupported(
+                "process sources do not support inspection",
+            )),
+            #[cfg(feature = "gsym")]
+            Source::Gsym(Gsym::Data(GsymData {
+                data,
+                _non_exhaustive: (),
+            })) => {
+                let _resolver = GsymResolver::with_data(data)?;
+                Ok(non_elf_source_info(SrcFormat::Gsym))
+            }
+            #[cfg(feature = "gsym")]
+            Source::Gsym(Gsym::File(GsymFile {
+                path,
+                _non_exhaustive: (),
+            })) => {
+                let _resolver = self.gsym_resolver(path)?;
+                Ok(non_elf_source_info(SrcFormat::Gsym))
+            }
+            Source::Phantom(()) => unreachable!(),
+        }
+    }
+
     fn maybe_debug_dirs(&self, debug_syms: bool) -> Option<&[PathBuf]> {
         #[cfg(feature = "dwarf")]
         let debug_dirs = &self.debug_dirs;
@@ -1376,12 +3142,16 @@ impl Default for Symbolizer {
 mod tests {
     use super::*;
 
+    use std::cell::RefCell;
+    use std::ptr;
+
     use test_log::test;
 
-    use crate::maps::Perm;
+    use crate::inspect::FindAddrOpts;
     use crate::symbolize;
-    use crate::symbolize::CodeInfo;
+    use crate::symbolize::SymTable;
     use crate::test_helper::find_the_answer_fn_in_zip;
+    use crate::SymType;
 
 
     /// Exercise the `Debug` representation of various types.
@@ -1402,6 +3172,91 @@ mod tests {
         assert_ne!(format!("{resolver:?}"), "");
     }
 
+    /// Check that we collapse adjacent identical inlined functions.
+    #[test]
+    fn inlined_fn_dedup() {
+        let code_info = |line| CodeInfo {
+            dir: None,
+            file: Cow::Borrowed(OsStr::new("test.c")),
+            line: Some(line),
+            column: None,
+            discriminator: None,
+            _non_exhaustive: (),
+        };
+        let inlined_fn = |name, line| InlinedFn {
+            name: Cow::Borrowed(name),
+            addr: None,
+            offset: None,
+            code_info: Some(code_info(line)),
+            _non_exhaustive: (),
+        };
+
+        let inlined = Box::new([
+            inlined_fn("f", 1),
+            inlined_fn("g", 2),
+            inlined_fn("g", 2),
+            inlined_fn("g", 2),
+            inlined_fn("g", 3),
+            inlined_fn("f", 1),
+        ]);
+        let names = dedup_inlined_fns(inlined)
+            .iter()
+            .map(|inlined_fn| {
+                (
+                    inlined_fn.name.to_string(),
+                    inlined_fn.code_info.as_ref().unwrap().line,
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = [("f", 1), ("g", 2), ("g", 3), ("f", 1)]
+            .into_iter()
+            .map(|(name, line)| (name.to_string(), Some(line)))
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected);
+    }
+
+    /// Check that source paths get remapped as expected.
+    #[test]
+    fn src_path_remapping() {
+        let remap = [
+            (PathBuf::from("/build/abc/src"), PathBuf::from("src")),
+            (PathBuf::from("/build"), PathBuf::new()),
+        ];
+        let mut info = CodeInfo {
+            dir: Some(Cow::Borrowed(Path::new("/build/abc/src/lib"))),
+            file: Cow::Borrowed(OsStr::new("test.c")),
+            line: None,
+            column: None,
+            discriminator: None,
+            _non_exhaustive: (),
+        };
+        let () = remap_code_info(&mut info, &remap);
+        assert_eq!(info.to_path(), Path::new("src/lib/test.c"));
+
+        // Only full components are matched.
+        info.dir = Some(Cow::Borrowed(Path::new("/build/abcd")));
+        let () = remap_code_info(&mut info, &remap);
+        assert_eq!(info.to_path(), Path::new("abcd/test.c"));
+
+        // A directory that is stripped completely is removed.
+        info.dir = Some(Cow::Borrowed(Path::new("/build")));
+        let () = remap_code_info(&mut info, &remap);
+        assert_eq!(info.dir, None);
+        assert_eq!(info.to_path(), Path::new("test.c"));
+
+        // Absolute file names are remapped directly.
+        info.dir = Some(Cow::Borrowed(Path::new("/usr/src")));
+        info.file = Cow::Borrowed(OsStr::new("/build/abc/src/main.c"));
+        let () = remap_code_info(&mut info, &remap);
+        assert_eq!(info.dir.as_deref(), Some(Path::new("/usr/src")));
+        assert_eq!(info.file, OsStr::new("src/main.c"));
+
+        // Paths not matching any prefix are left alone.
+        info.file = Cow::Borrowed(OsStr::new("/tmp/main.c"));
+        let () = remap_code_info(&mut info, &remap);
+        assert_eq!(info.to_path(), Path::new("/tmp/main.c"));
+    }
+
     /// Check that we can create a path to an ELF inside an APK as expected.
     #[test]
     fn elf_apk_path_creation() {
@@ -1422,6 +3277,7 @@ mod tests {
             file: Cow::Borrowed(OsStr::new("source.c")),
             line: Some(1),
             column: Some(2),
+            discriminator: None,
             _non_exhaustive: (),
         };
         assert_eq!(info.to_path(), Path::new("source.c"));
@@ -1437,6 +3293,16 @@ mod tests {
         let name = maybe_demangle(symbol, SrcLang::Rust);
         assert_eq!(name, "core::panicking::panic_fmt");
 
+        // v0 mangled Rust symbol.
+        let symbol = Cow::Borrowed("_RNvC6_123foo3bar");
+        let name = maybe_demangle(symbol, SrcLang::Rust);
+        assert_eq!(name, "123foo::bar");
+
+        // Symbols that fail to demangle should be reported unchanged.
+        let symbol = Cow::Borrowed("_ZN4core9panicking");
+        let name = maybe_demangle(symbol, SrcLang::Rust);
+        assert_eq!(name, "_ZN4core9panicking");
+
         let symbol = Cow::Borrowed("_ZStlsISt11char_traitsIcEERSt13basic_ostreamIcT_ES5_PKc");
         let name = maybe_demangle(symbol, SrcLang::Cpp);
         assert_eq!(
@@ -1445,6 +3311,230 @@ mod tests {
         );
     }
 
+    /// Check that a custom demangler is consulted before the built-in
+    /// ones.
+    #[test]
+    fn custom_demangler() {
+        let symbolizer = Symbolizer::builder()
+            .set_demangler(|name: &str| name.strip_prefix("__dsl_").map(str::to_string))
+            .build();
+        let name = symbolizer.maybe_demangle(Cow::Borrowed("__dsl_foo"), SrcLang::Unknown);
+        assert_eq!(name, "foo");
+
+        // Names the custom demangler does not handle should go through
+        // the default logic.
+        let symbol = Cow::Borrowed("_ZN4core9panicking9panic_fmt17h5f1a6fd39197ad62E");
+        let name = symbolizer.maybe_demangle(symbol, SrcLang::Rust);
+        assert_eq!(name, "core::panicking::panic_fmt");
+
+        // With demangling disabled the custom demangler is not used.
+        let symbolizer = Symbolizer::builder()
+            .enable_demangling(false)
+            .set_demangler(|_name: &str| Some("bar".to_string()))
+            .build();
+        let name = symbolizer.maybe_demangle(Cow::Borrowed("__dsl_foo"), SrcLang::Unknown);
+        assert_eq!(name, "__dsl_foo");
+    }
+
+    /// Check that batch symbolization reports progress and can be
+    /// aborted.
+    #[test]
+    fn progress_reporting() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let src = Source::Elf(Elf::new(path));
+        let addrs = vec![0x2000100; 2 * SYMBOLIZE_BATCH_SIZE + 1];
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let calls_ = calls.clone();
+        let symbolizer = Symbolizer::builder()
+            .set_progress_handler(move |processed, total| {
+                let () = calls_.borrow_mut().push((processed, total));
+                ControlFlow::Continue(())
+            })
+            .build();
+        let results = symbolizer
+            .symbolize(&src, Input::VirtOffset(&addrs))
+            .unwrap();
+        assert_eq!(results.len(), addrs.len());
+        assert_eq!(
+            calls.borrow().as_slice(),
+            &[
+                (SYMBOLIZE_BATCH_SIZE, addrs.len()),
+                (2 * SYMBOLIZE_BATCH_SIZE, addrs.len()),
+                (addrs.len(), addrs.len()),
+            ]
+        );
+
+        // Aborting should report the partial results.
+        let symbolizer = Symbolizer::builder()
+            .set_progress_handler(|_processed, _total| ControlFlow::Break(()))
+            .build();
+        let results = symbolizer
+            .symbolize(&src, Input::VirtOffset(&addrs))
+            .unwrap();
+        assert_eq!(results.len(), SYMBOLIZE_BATCH_SIZE);
+        assert!(results
+            .iter()
+            .all(|symbolized| symbolized.as_sym().unwrap().name == "factorial"));
+    }
+
+    /// Check that progress is only reported for the top-level request
+    /// when symbolizing using fallback sources.
+    #[test]
+    fn progress_reporting_fallback() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let src = Source::Fallback(vec![
+            Source::Elf(Elf::new(&path)),
+            Source::Elf(Elf::new(&path)),
+        ]);
+        // The unknown address causes the fallback source to be
+        // consulted.
+        let addrs = [0x2000100, 0x0, 0x2000100];
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let calls_ = calls.clone();
+        let symbolizer = Symbolizer::builder()
+            .set_progress_handler(move |processed, total| {
+                let () = calls_.borrow_mut().push((processed, total));
+                ControlFlow::Continue(())
+            })
+            .build();
+        let results = symbolizer
+            .symbolize(&src, Input::VirtOffset(&addrs))
+            .unwrap();
+        assert_eq!(results.len(), addrs.len());
+        assert_eq!(calls.borrow().as_slice(), &[(addrs.len(), addrs.len())]);
+    }
+
+    /// Check that errors from individual fallback sources do not
+    /// prevent symbolization using the remaining ones.
+    #[test]
+    fn fallback_source_errors() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let missing = Source::Elf(Elf::new("/does/not/exist"));
+        let src = Source::Fallback(vec![missing.clone(), Source::Elf(Elf::new(&path))]);
+        let addrs = [0x2000100, 0x0];
+
+        let symbolizer = Symbolizer::new();
+        let results = symbolizer
+            .symbolize(&src, Input::VirtOffset(&addrs))
+            .unwrap();
+        assert_eq!(results.len(), addrs.len());
+        assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+        assert!(matches!(results[1], Symbolized::Unknown(..)));
+
+        let result = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap();
+        assert_eq!(result.as_sym().unwrap().name, "factorial");
+
+        // An error with a later source does not mask results of an
+        // earlier one.
+        let src = Source::Fallback(vec![Source::Elf(Elf::new(&path)), missing.clone()]);
+        let results = symbolizer
+            .symbolize(&src, Input::VirtOffset(&addrs))
+            .unwrap();
+        assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+        assert!(matches!(results[1], Symbolized::Unknown(..)));
+
+        // Only if all sources fail do we report an error.
+        let src = Source::Fallback(vec![missing.clone(), missing]);
+        let err = symbolizer
+            .symbolize(&src, Input::VirtOffset(&addrs))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    /// Check that we can symbolize offsets relative to an ELF section.
+    #[test]
+    fn section_offset_symbolization() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let parser = ElfParser::open(&path).unwrap();
+        let idx = parser.find_section(".text").unwrap().unwrap();
+        let shdr = &parser.section_headers().unwrap()[idx];
+
+        let src = Source::Elf(Elf::new(&path));
+        let symbolizer = Symbolizer::new();
+        let expected = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap();
+        let expected = expected.as_sym().unwrap();
+
+        let offsets = [
+            0x2000100 - shdr.sh_addr,
+            shdr.sh_size,
+            shdr.sh_size + 1,
+            u64::MAX,
+            0x2000100 - shdr.sh_addr,
+        ];
+        let results = symbolizer
+            .symbolize_section_offsets(&src, ".text", &offsets)
+            .unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].as_sym(), Some(expected));
+        assert_eq!(results[1], Symbolized::Unknown(Reason::UnknownAddr));
+        assert_eq!(results[2], Symbolized::Unknown(Reason::UnknownAddr));
+        assert_eq!(results[3], Symbolized::Unknown(Reason::UnknownAddr));
+        assert_eq!(results[4].as_sym(), Some(expected));
+
+        let err = symbolizer
+            .symbolize_section_offsets(&src, ".does-not-exist", &offsets)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    /// Check that we can warm up symbolization sources.
+    #[test]
+    fn source_warmup() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.bin");
+        let src = Source::Elf(Elf::new(path));
+        let symbolizer = Symbolizer::new();
+        let () = symbolizer.warmup(&src).unwrap();
+        let sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+
+        let src = Source::Process(Process::new(Pid::Slf));
+        let () = symbolizer.warmup(&src).unwrap();
+
+        let src = Source::Kernel(Kernel::default());
+        let err = symbolizer.warmup(&src).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    /// Check that names are interned if so configured.
+    #[test]
+    fn name_interning() {
+        let symbolizer = Symbolizer::builder().enable_name_interning(true).build();
+        let name1 = symbolizer.maybe_intern(Cow::Owned("foo".to_string()));
+        let name2 = symbolizer.maybe_intern(Cow::Borrowed("foo"));
+        assert_eq!(name1, "foo");
+        assert!(matches!(name1, Cow::Borrowed(..)));
+        assert!(ptr::eq(name1.as_ref(), name2.as_ref()));
+
+        let symbolizer = Symbolizer::new();
+        let name = symbolizer.maybe_intern(Cow::Borrowed("foo"));
+        assert_eq!(name, "foo");
+        assert!(matches!(name, Cow::Owned(..)));
+    }
+
     /// Make sure that we error out as expected on certain input
     /// variants.
     #[test]
@@ -1517,6 +3607,80 @@ mod tests {
         }
     }
 
+    /// Check that we can inspect symbolization sources.
+    #[test]
+    fn source_inspection() {
+        let symbolizer = Symbolizer::new();
+
+        let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let src = symbolize::Source::Elf(symbolize::Elf::new(test_so));
+        let info = symbolizer.inspect(&src).unwrap();
+        assert_eq!(info.format, SrcFormat::Elf);
+        assert!(info.arch.is_some());
+        assert!(info.has_dynsym);
+        // The file contains a sha1 build ID, which is always 20 bytes long.
+        assert_eq!(info.build_id.as_deref().map(<[u8]>::len), Some(20));
+
+        let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs-no-dwarf.bin");
+        let src = symbolize::Source::Elf(symbolize::Elf::new(test_elf));
+        let info = symbolizer.inspect(&src).unwrap();
+        assert!(info.has_symtab);
+        assert!(!info.has_debug_line);
+        assert_eq!(info.debug_info, DebugInfo::None);
+        let stats = info.sym_stats.unwrap();
+        assert_eq!(stats.table, Some(SymTable::SymTab));
+        assert!(stats.funcs > 0);
+        assert!(stats.total >= stats.funcs);
+        assert!(!stats.stripped);
+
+        let test_gsym = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addrs.gsym");
+        let src =
+            symbolize::Source::Gsym(symbolize::Gsym::File(symbolize::GsymFile::new(test_gsym)));
+        let info = symbolizer.inspect(&src).unwrap();
+        assert_eq!(info.format, SrcFormat::Gsym);
+        assert_eq!(info.build_id, None);
+        assert_eq!(info.debug_info, DebugInfo::None);
+
+        let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+        let err = symbolizer.inspect(&src).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    /// Check that we correctly report the availability of debug
+    /// information as part of source inspection.
+    #[cfg(feature = "dwarf")]
+    #[test]
+    fn source_inspection_debug_info() {
+        let symbolizer = Symbolizer::new();
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+
+        let src = symbolize::Source::Elf(symbolize::Elf::new(data.join("test-stable-addrs.bin")));
+        let info = symbolizer.inspect(&src).unwrap();
+        assert_eq!(info.debug_info, DebugInfo::Present);
+
+        let src = symbolize::Source::Elf(symbolize::Elf::new(
+            data.join("test-stable-addrs-stripped-with-link.bin"),
+        ));
+        let info = symbolizer.inspect(&src).unwrap();
+        match info.debug_info {
+            DebugInfo::External {
+                debug_link,
+                checksum: _,
+                build_id,
+            } => {
+                assert_eq!(debug_link, Path::new("test-stable-addrs-dwarf-only.dbg"));
+                assert_eq!(build_id, info.build_id);
+            }
+            info => panic!("encountered unexpected debug information: {info:?}"),
+        }
+    }
+
     /// Check that we do not normalize addresses belonging to a
     /// "component" (as opposed to a file).
     #[test]
@@ -1591,6 +3755,56 @@ mod tests {
         assert_eq!(result.addr, sym.addr);
     }
 
+    /// Check that we can symbolize a file offset relative to the start
+    /// of an APK.
+    #[test]
+    fn symbolize_apk_file_offset() {
+        let test_zip = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test.zip");
+
+        let mmap = Mmap::builder().open(&test_zip).unwrap();
+        let archive = zip::Archive::with_mmap(mmap.clone()).unwrap();
+        let so = archive
+            .entries()
+            .find_map(|entry| {
+                let entry = entry.unwrap();
+                (entry.path == Path::new("libtest-so.so")).then_some(entry)
+            })
+            .unwrap();
+        // The member is preceded by (at least) its local file header.
+        assert_ne!(so.data_offset, 0);
+
+        let elf_mmap = mmap
+            .constrain(so.data_offset..so.data_offset + so.data.len() as u64)
+            .unwrap();
+        let parser = ElfParser::from_mmap(elf_mmap, None);
+        let opts = FindAddrOpts {
+            offset_in_file: true,
+            sym_type: SymType::Function,
+        };
+        let syms = parser.find_addr("the_answer", &opts).unwrap();
+        assert_eq!(syms.len(), 1);
+        let file_off = so.data_offset + syms[0].addr;
+
+        let src = symbolize::Source::Apk(symbolize::Apk::new(&test_zip));
+        let symbolizer = Symbolizer::new();
+        let result = symbolizer
+            .symbolize_single(&src, Input::FileOffset(file_off))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(result.name, "the_answer");
+        assert_eq!(result.offset, 0);
+
+        // An offset inside the member's local file header does not
+        // map to any ELF data.
+        let result = symbolizer
+            .symbolize_single(&src, Input::FileOffset(so.data_offset - 1))
+            .unwrap();
+        assert_eq!(result, Symbolized::Unknown(Reason::InvalidFileOffset));
+    }
+
     /// Check that we can symbolize an address residing in a zip archive, using
     /// a custom APK dispatcher.
     #[test]
@@ -0,0 +1,68 @@
+use std::cmp::min;
+
+use crate::symbolize::Reason;
+use crate::util::find_match_or_lower_bound_by_key;
+use crate::Addr;
+
+
+/// A symbol as extracted from a symbol table that does not record
+/// symbol sizes, such as those of Mach-O and PE files.
+#[derive(Debug)]
+pub(crate) struct Symbol {
+    /// The symbol's name.
+    pub name: Box<str>,
+    /// The symbol's address.
+    pub addr: Addr,
+    /// The symbol's size, as inferred from the address of the next
+    /// symbol and the end of the containing section.
+    pub size: Option<usize>,
+}
+
+
+/// Infer the sizes of the provided symbols, each of which is paired
+/// with the end address of the section it resides in.
+///
+/// `syms` is expected to be sorted by address. The size of a symbol
+/// is inferred from the start of the next symbol at a higher address,
+/// but it never extends past the end of the containing section.
+pub(crate) fn infer_sym_sizes(mut syms: Vec<(Symbol, Addr)>) -> Vec<Symbol> {
+    let mut bound = None;
+    let mut prev_addr = None;
+    for (sym, section_end) in syms.iter_mut().rev() {
+        if let Some(prev_addr) = prev_addr {
+            if prev_addr > sym.addr {
+                bound = Some(prev_addr);
+            }
+        }
+        let end = bound.map_or(*section_end, |bound| min(bound, *section_end));
+        sym.size = end
+            .checked_sub(sym.addr)
+            .filter(|size| *size > 0)
+            .map(|size| usize::try_from(size).unwrap_or(usize::MAX));
+        prev_addr = Some(sym.addr);
+    }
+
+    syms.into_iter().map(|(sym, _end)| sym).collect()
+}
+
+
+/// Find the symbol covering `addr` in `syms`, which is expected to be
+/// sorted by address.
+pub(crate) fn find_sym(syms: &[Symbol], addr: Addr) -> Result<&Symbol, Reason> {
+    let result = find_match_or_lower_bound_by_key(syms, addr, |sym| sym.addr)
+        .and_then(|idx| syms.get(idx))
+        .filter(|sym| match sym.size {
+            Some(size) => addr - sym.addr < size as Addr,
+            None => true,
+        });
+    match result {
+        Some(sym) => Ok(sym),
+        None => {
+            if syms.is_empty() {
+                Err(Reason::MissingSyms)
+            } else {
+                Err(Reason::UnknownAddr)
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
+use std::mem::size_of;
 use std::mem::transmute;
 use std::path::Path;
 use std::path::PathBuf;
+use std::slice;
 
 use crate::elf::ElfParser;
 use crate::inspect;
+use crate::util::Pod;
 use crate::zip;
 use crate::Addr;
 use crate::Mmap;
@@ -56,3 +59,32 @@ pub(crate) fn find_the_answer_fn_in_zip(mmap: &Mmap) -> (inspect::SymInfo<'stati
     let (sym, the_answer_addr) = find_the_answer_fn(&elf_mmap);
     (sym, the_answer_addr)
 }
+
+
+/// View the in-memory representation of `value` as a byte slice.
+fn pod_bytes<T>(value: &T) -> &[u8]
+where
+    T: Pod,
+{
+    // SAFETY: `T` is `Pod` and so it is fine to view it as a byte
+    //         slice.
+    unsafe { slice::from_raw_parts((value as *const T).cast::<u8>(), size_of::<T>()) }
+}
+
+/// Append the in-memory representation of `value` to `data`.
+pub(crate) fn push_pod<T>(data: &mut Vec<u8>, value: &T)
+where
+    T: Pod,
+{
+    let () = data.extend_from_slice(pod_bytes(value));
+}
+
+/// Write the in-memory representation of `value` to `data` at
+/// `offset`.
+pub(crate) fn write_pod<T>(data: &mut [u8], offset: usize, value: &T)
+where
+    T: Pod,
+{
+    let bytes = pod_bytes(value);
+    let () = data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
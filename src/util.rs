@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::ffi::CStr;
 use std::ffi::CString;
@@ -487,6 +488,45 @@ pub(crate) trait ReadRaw<'data> {
         }
     }
 
+    /// Read something implementing `Pod`, borrowing it if the
+    /// underlying data are suitably aligned and copying it otherwise.
+    #[inline]
+    fn read_pod_cow<T>(&mut self) -> Option<Cow<'data, T>>
+    where
+        T: Clone + Pod,
+    {
+        let data = self.read_slice(size_of::<T>())?;
+
+        let mut borrowed = data;
+        if let Some(value) = borrowed.read_pod_ref::<T>() {
+            return Some(Cow::Borrowed(value))
+        }
+
+        let mut owned = data;
+        owned.read_pod::<T>().map(Cow::Owned)
+    }
+
+    /// Read `count` objects implementing `Pod`, borrowing them if the
+    /// underlying data are suitably aligned and copying them otherwise.
+    #[inline]
+    fn read_pod_slice_cow<T>(&mut self, count: usize) -> Option<Cow<'data, [T]>>
+    where
+        T: Clone + Pod,
+    {
+        let data = self.read_slice(size_of::<T>().checked_mul(count)?)?;
+
+        let mut borrowed = data;
+        if let Some(values) = borrowed.read_pod_slice_ref::<T>(count) {
+            return Some(Cow::Borrowed(values))
+        }
+
+        let mut owned = data;
+        (0..count)
+            .map(|_| owned.read_pod::<T>())
+            .collect::<Option<Vec<_>>>()
+            .map(Cow::Owned)
+    }
+
     /// Read a `u8` value.
     #[inline]
     fn read_u8(&mut self) -> Option<u8> {
@@ -879,6 +919,47 @@ mod tests {
         assert_eq!(unaligned.read_pod_ref::<u64>(), None);
     }
 
+    /// Check that we can read `Pod`s irrespective of the alignment of
+    /// the underlying data.
+    #[tag(miri)]
+    #[test]
+    fn pod_cow_reading() {
+        let buffer = [0u64, 1337, 42, 0];
+        let buffer = unsafe {
+            slice::from_raw_parts(
+                buffer.as_ptr().cast::<u8>(),
+                buffer.len() * size_of_val(&buffer[0]),
+            )
+        };
+
+        let mut aligned = &buffer[8..];
+        let value = aligned.read_pod_cow::<u64>().unwrap();
+        assert!(matches!(value, Cow::Borrowed(1337)), "{value:?}");
+        let values = aligned.read_pod_slice_cow::<u64>(2).unwrap();
+        assert!(matches!(values, Cow::Borrowed([42, 0])), "{values:?}");
+
+        // Shift everything by one byte so that the data are guaranteed
+        // to be unaligned.
+        let mut storage = [0u64; 5];
+        let data = unsafe {
+            slice::from_raw_parts_mut(
+                storage.as_mut_ptr().cast::<u8>(),
+                storage.len() * size_of_val(&storage[0]),
+            )
+        };
+        let () = data[1..=buffer.len()].copy_from_slice(buffer);
+        let mut unaligned = &data[9..];
+        let value = unaligned.read_pod_cow::<u64>().unwrap();
+        assert!(matches!(value, Cow::Owned(1337)), "{value:?}");
+        let values = unaligned.read_pod_slice_cow::<u64>(2).unwrap();
+        assert_eq!(values.as_ref(), [42, 0]);
+
+        // Make sure that we fail if there is insufficient space.
+        assert_eq!(unaligned.read_pod_cow::<u64>(), None);
+        let mut unaligned = &data[9..];
+        assert_eq!(unaligned.read_pod_slice_cow::<u64>(4), None);
+    }
+
     /// Test reading of signed and unsigned 16 and 32 bit values against known
     /// results.
     #[tag(miri)]
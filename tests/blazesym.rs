@@ -11,6 +11,8 @@ use std::ffi::CString;
 use std::ffi::OsStr;
 use std::fs::copy;
 use std::fs::read as read_file;
+use std::fs::remove_file;
+use std::fs::write as write_file;
 use std::io::Error;
 use std::io::Read as _;
 use std::io::Write as _;
@@ -21,6 +23,8 @@ use std::path::Path;
 use std::process::Command;
 use std::process::Stdio;
 use std::str;
+use std::thread::sleep;
+use std::time::Duration;
 
 use blazesym::helper::read_elf_build_id;
 use blazesym::helper::ElfResolver;
@@ -112,6 +116,13 @@ fn symbolize_elf_dwarf_gsym() {
             .collect::<Vec<_>>();
         assert_eq!(results.len(), addrs.len());
 
+        // Single address symbolization should report the same result as
+        // batched symbolization.
+        let single = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(addrs[0]))
+            .unwrap();
+        assert_eq!(single, results[0]);
+
         for (i, symbolized) in results.into_iter().enumerate() {
             let result = symbolized.into_sym().unwrap();
             assert_eq!(result.name, "factorial");
@@ -165,6 +176,206 @@ fn symbolize_elf_dwarf_gsym() {
     test(src, true);
 }
 
+
+/// Check that the original symbol name is reported when demangling
+/// changed it.
+#[tag(windows)]
+#[test]
+fn symbolize_mangled_name() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs-no-dwarf.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+
+    let symbolizer = Symbolizer::builder()
+        .set_demangler(|name: &str| Some(format!("demangled::{name}")))
+        .build();
+    let sym = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym.name, "demangled::factorial");
+    assert_eq!(sym.mangled_name.as_deref(), Some("factorial"));
+
+    // Demangling leaves the name untouched, so no mangled name should
+    // be reported.
+    let symbolizer = Symbolizer::new();
+    let sym = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.mangled_name, None);
+}
+
+/// Check that we honor the configured ELF symbol table source.
+#[test]
+fn symbolize_elf_sym_source() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs-no-dwarf.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+
+    for sym_source in [symbolize::SymSource::Auto, symbolize::SymSource::SymTab] {
+        let symbolizer = Symbolizer::builder().set_sym_source(sym_source).build();
+        let sym = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+    }
+
+    // The binary does not contain a `.dynsym` section and so we should
+    // not find any symbols when restricting ourselves to it.
+    let symbolizer = Symbolizer::builder()
+        .set_sym_source(symbolize::SymSource::DynSymOnly)
+        .build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap();
+    assert_eq!(result, Symbolized::Unknown(Reason::MissingSyms));
+}
+
+/// Check that we can symbolize absolute addresses in an ELF file when
+/// a base address is provided.
+#[test]
+fn symbolize_elf_base_addr() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs.bin");
+    let mut elf = symbolize::Elf::new(path);
+    let symbolizer = Symbolizer::new();
+
+    // Without a base address absolute addresses are not supported.
+    let src = symbolize::Source::Elf(elf.clone());
+    let _err = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(0x2000100))
+        .unwrap_err();
+
+    let base_addr = 0x7f0000000000;
+    elf.base_addr = Some(base_addr);
+    let src = symbolize::Source::Elf(elf);
+    let sym = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(base_addr + 0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.addr, 0x2000100);
+
+    let results = symbolizer
+        .symbolize(
+            &src,
+            symbolize::Input::AbsAddr([base_addr + 0x2000100, 0x1].as_slice()),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+    assert_eq!(results[1], Symbolized::Unknown(Reason::Unmapped));
+}
+
+/// Check that we can symbolize addresses using the `async` API.
+#[test(tokio::test)]
+async fn symbolize_elf_async() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+    let addrs = vec![0x2000100, 0x2000200];
+
+    let async_symbolizer = symbolize::AsyncSymbolizer::new().unwrap();
+    let results = async_symbolizer
+        .symbolize(src.clone(), symbolize::Input::VirtOffset(addrs.clone()))
+        .await
+        .unwrap();
+    let symbolizer = Symbolizer::new();
+    let expected = symbolizer
+        .symbolize(&src, symbolize::Input::VirtOffset(&addrs))
+        .unwrap();
+    assert_eq!(results, expected);
+    assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+}
+
+/// Check that clearing the symbolizer's cache does not affect
+/// symbolization results.
+#[test]
+fn symbolize_after_cache_clear() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+    let mut symbolizer = Symbolizer::new();
+    let sym1 = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap()
+        .to_owned();
+    assert_eq!(sym1.name, "factorial");
+
+    let () = symbolizer.clear_cache();
+
+    let sym2 = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym2, sym1);
+}
+
+/// Check that we pick up changes to a file on disk when auto reloading
+/// is enabled, and only then.
+#[test]
+fn symbolize_after_file_change() {
+    fn test(auto_reload: bool) {
+        let dir = tempdir().unwrap();
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+        let path = dir.path().join("test.bin");
+        let _count = copy(data.join("test-stable-addrs.bin"), &path).unwrap();
+
+        let src = symbolize::Source::Elf(symbolize::Elf::new(&path));
+        let symbolizer = Symbolizer::builder()
+            .enable_auto_reload(auto_reload)
+            .build();
+        let sym = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+
+        // Sleep briefly to make sure that file times will end up being
+        // different.
+        let () = sleep(Duration::from_millis(10));
+
+        // Replace the file with a stripped version of the binary, which
+        // can't be symbolized.
+        let () = remove_file(&path).unwrap();
+        let _count = copy(
+            data.join("test-stable-addrs-stripped-with-link-non-existent.bin"),
+            &path,
+        )
+        .unwrap();
+
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym();
+        if auto_reload {
+            assert_eq!(result, None);
+        } else {
+            assert_eq!(result.unwrap().name, "factorial");
+        }
+    }
+
+    for auto_reload in [false, true] {
+        let () = test(auto_reload);
+    }
+}
+
 /// Check that we correctly symbolize zero sized symbols.
 // TODO: Extend this test to more formats.
 #[tag(windows)]
@@ -349,6 +560,54 @@ fn symbolize_elf_stripped() {
     assert_eq!(result, Symbolized::Unknown(Reason::MissingSyms));
 }
 
+/// Check that we can symbolize addresses in members of regular and
+/// thin `ar` archives.
+#[test]
+fn symbolize_archive_member() {
+    fn header(name: &str, size: usize) -> String {
+        format!("{name:<16}{:<12}{:<6}{:<6}{:<8}{size:<10}`\n", 0, 0, 0, 644)
+    }
+
+    let dir = tempdir().unwrap();
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs.bin");
+    let elf = read_file(&path).unwrap();
+    let _count = copy(&path, dir.path().join("test-stable-addrs.bin")).unwrap();
+
+    let mut archive = b"!<arch>\n".to_vec();
+    let () = archive.extend_from_slice(header("test-stable-addrs.bin/", elf.len()).as_bytes());
+    let () = archive.extend_from_slice(&elf);
+    let archive_path = dir.path().join("libtest.a");
+    let () = write_file(&archive_path, &archive).unwrap();
+
+    let mut thin = b"!<thin>\n".to_vec();
+    let () = thin.extend_from_slice(header("test-stable-addrs.bin/", elf.len()).as_bytes());
+    let thin_path = dir.path().join("libtest-thin.a");
+    let () = write_file(&thin_path, &thin).unwrap();
+
+    let symbolizer = Symbolizer::new();
+    for archive_path in [archive_path, thin_path] {
+        let src = symbolize::Source::from(symbolize::Archive::new(
+            &archive_path,
+            "test-stable-addrs.bin",
+        ));
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(result.name, "factorial");
+        assert_eq!(result.addr, 0x2000100);
+
+        let src = symbolize::Source::from(symbolize::Archive::new(&archive_path, "missing.o"));
+        let err = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
+
 /// Make sure that we report (enabled) or don't report (disabled) inlined
 /// functions with DWARF and Gsym sources.
 #[tag(windows)]
@@ -417,6 +676,109 @@ fn symbolize_dwarf_gsym_inlined() {
     }
 }
 
+/// Check that inlined functions reported via DWARF carry the address
+/// of the inlined code they represent.
+#[tag(windows)]
+#[test]
+fn symbolize_dwarf_inlined_addr() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs-stripped-elf-with-dwarf.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+    let addr = 0x200020a;
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+
+    assert!(result.is_inlined_site());
+    assert_eq!(result.inlined.len(), 2);
+    for inlined_fn in result.inlined.iter() {
+        let inlined_addr = inlined_fn.addr.unwrap();
+        let offset = inlined_fn.offset.unwrap();
+        assert!(inlined_addr >= result.addr, "{inlined_fn:#?}");
+        assert_eq!(inlined_addr + offset as Addr, addr);
+    }
+}
+
+/// Check that we honor the configured maximum inlined function depth.
+#[tag(windows)]
+#[test]
+fn symbolize_inlined_max_depth() {
+    fn test(src: symbolize::Source) {
+        let symbolizer = Symbolizer::builder().set_max_inline_depth(Some(1)).build();
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+
+        assert_eq!(result.name, "factorial_inline_test");
+        let code_info = result.code_info.as_ref().unwrap();
+        assert_eq!(code_info.line, Some(34));
+        assert_eq!(result.inlined.len(), 1, "{:#?}", result.inlined);
+        assert_eq!(result.inlined[0].name, "factorial_inline_wrapper");
+        // The last inlined function reported should carry the source
+        // code location of the address itself.
+        let frame = result.inlined[0].code_info.as_ref().unwrap();
+        assert_eq!(frame.line, Some(23));
+
+        // A depth of zero should behave as if inlined function
+        // reporting were disabled.
+        let symbolizer = Symbolizer::builder().set_max_inline_depth(Some(0)).build();
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        let code_info = result.code_info.as_ref().unwrap();
+        assert_eq!(code_info.line, Some(23));
+        assert!(result.inlined.is_empty(), "{:#?}", result.inlined);
+
+        // Irrespective of the depth, the innermost source code location
+        // reported should be the one reported without a limit.
+        let location = |sym: &symbolize::Sym| {
+            sym.inlined
+                .last()
+                .map(|inlined_fn| inlined_fn.code_info.as_ref())
+                .unwrap_or(sym.code_info.as_ref())
+                .map(|code_info| (code_info.file.clone(), code_info.line))
+        };
+        let symbolizer = Symbolizer::new();
+        let full = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(full.inlined.len(), 2, "{:#?}", full.inlined);
+
+        for depth in 0..=full.inlined.len() + 1 {
+            let symbolizer = Symbolizer::builder()
+                .set_max_inline_depth(Some(depth))
+                .build();
+            let result = symbolizer
+                .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
+                .unwrap()
+                .into_sym()
+                .unwrap();
+            assert_eq!(result.inlined.len(), depth.min(full.inlined.len()));
+            assert_eq!(location(&result), location(&full), "{depth}");
+        }
+    }
+
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs.gsym");
+    test(symbolize::Source::from(symbolize::GsymFile::new(path)));
+
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs-stripped-elf-with-dwarf.bin");
+    test(symbolize::Source::from(symbolize::Elf::new(path)));
+}
+
 /// Make sure that we fail loading linked debug information on CRC
 /// mismatch.
 #[tag(windows)]
@@ -629,6 +991,7 @@ fn symbolize_dwarf_demangle() {
             .unwrap();
 
         assert_eq!(result.name, "test::test_function");
+        assert_eq!(result.lang, symbolize::SrcLang::Rust);
         assert_eq!(result.inlined.len(), 1, "{:#?}", result.inlined);
         assert_eq!(result.inlined[0].name, "test::inlined_call");
         Ok(())
@@ -696,6 +1059,326 @@ fn symbolize_process() {
     );
 }
 
+
+/// Check that a configured address filter is applied to absolute
+/// addresses before symbolization.
+#[test]
+fn symbolize_process_addr_filter() {
+    // Emulate pointer authentication bits in the upper part of the
+    // address.
+    const TAG_MASK: Addr = 0xffff_0000_0000_0000;
+    const TAG: Addr = 0x0012_0000_0000_0000;
+
+    let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+    let addr = symbolize_process_addr_filter as Addr | TAG;
+
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(addr))
+        .unwrap();
+    assert!(result.as_sym().is_none(), "{result:x?}");
+
+    let symbolizer = Symbolizer::builder()
+        .set_addr_filter(|addr| addr & !TAG_MASK)
+        .build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(addr))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert!(
+        result.name.contains("symbolize_process_addr_filter"),
+        "{result:x?}"
+    );
+
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::AbsAddr(&[addr]))
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    let result = results[0].as_sym().unwrap();
+    assert!(
+        result.name.contains("symbolize_process_addr_filter"),
+        "{result:x?}"
+    );
+}
+
+
+/// Check that the address filter is applied exactly once, even when
+/// symbolizing using (nested) fallback sources.
+#[test]
+fn symbolize_fallback_addr_filter() {
+    let process = || symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+    let src = symbolize::Source::Fallback(vec![symbolize::Source::Fallback(vec![process()])]);
+    let addr = symbolize_fallback_addr_filter as Addr;
+
+    // A filter that is not idempotent, as may be used for turning
+    // return addresses into call addresses, for example.
+    let symbolizer = Symbolizer::builder()
+        .set_addr_filter(|addr| addr - 1)
+        .build();
+
+    let check = |sym: &symbolize::Sym| {
+        assert!(
+            sym.name.contains("symbolize_fallback_addr_filter"),
+            "{sym:x?}"
+        );
+        assert_eq!(sym.offset, 0);
+    };
+
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(addr + 1))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    let () = check(&result);
+
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::AbsAddr(&[addr + 1]))
+        .unwrap();
+    let () = check(results[0].as_sym().unwrap());
+
+    let results = symbolizer
+        .symbolize_iter(&src, symbolize::Input::AbsAddr(&[addr + 1]))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let () = check(results[0].as_sym().unwrap());
+}
+
+/// Check that we fall back to subsequent sources when symbolization
+/// using one fails.
+#[test]
+fn symbolize_fallback() {
+    let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+    let stripped = symbolize::Elf::new(data.join("test-stable-addrs-stripped.bin"));
+    let no_dwarf = symbolize::Elf::new(data.join("test-stable-addrs-no-dwarf.bin"));
+    let src = symbolize::Source::Fallback(vec![
+        symbolize::Source::Elf(stripped.clone()),
+        symbolize::Source::Elf(no_dwarf),
+    ]);
+    let symbolizer = Symbolizer::new();
+    let addrs = [0x2000100, 0x1];
+
+    let result = symbolizer
+        .symbolize_single(
+            &symbolize::Source::Elf(stripped),
+            symbolize::Input::VirtOffset(addrs[0]),
+        )
+        .unwrap();
+    assert!(result.as_sym().is_none(), "{result:?}");
+
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::VirtOffset(&addrs))
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+    // The reason of the last source consulted should be reported.
+    assert_eq!(results[1], Symbolized::Unknown(Reason::UnknownAddr));
+
+    for (addr, expected) in addrs.iter().zip(&results) {
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(*addr))
+            .unwrap();
+        assert_eq!(&result, expected);
+    }
+
+    let src = symbolize::Source::Fallback(Vec::new());
+    let err = symbolizer
+        .symbolize(&src, symbolize::Input::VirtOffset(&addrs))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+/// Check that we can symbolize addresses in a manually mapped ELF
+/// file.
+#[test]
+fn symbolize_mapped_elf() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs-no-dwarf.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let file_offset = Inspector::new()
+        .lookup(&src, &["factorial"])
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .next()
+        .unwrap()
+        .file_offset
+        .unwrap();
+
+    let base = 0x7f0000000000;
+    let len = 0x10000000;
+    let src = symbolize::Source::MappedElf(symbolize::MappedElf::new(&path, base, len));
+    let symbolizer = Symbolizer::new();
+    let addrs = [base + file_offset + 1, base - 1, base + len];
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::AbsAddr(&addrs))
+        .unwrap();
+    assert_eq!(results.len(), 3);
+
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.addr, 0x2000100);
+    assert_eq!(sym.offset, 1);
+    assert_eq!(sym.module.as_deref(), Some(path.as_path()));
+    assert_eq!(results[1], Symbolized::Unknown(Reason::Unmapped));
+    assert_eq!(results[2], Symbolized::Unknown(Reason::Unmapped));
+
+    let single = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(addrs[0]))
+        .unwrap();
+    assert_eq!(single, results[0]);
+
+    let err = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
+/// Check that symbols are annotated with the module they reside in.
+#[test]
+fn symbolize_sym_module() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(&path));
+    let symbolizer = Symbolizer::new();
+    let sym = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym.module.as_deref(), Some(path.as_path()));
+    let build_id = symbolizer.inspect(&src).unwrap().build_id;
+    assert_eq!(sym.build_id, build_id);
+
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::VirtOffset(&[0x2000100]))
+        .unwrap();
+    assert_eq!(results[0].as_sym(), Some(&sym));
+
+    let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+    let sym = symbolizer
+        .symbolize_single(
+            &src,
+            symbolize::Input::AbsAddr(symbolize_sym_module as Addr),
+        )
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    let exe = env::current_exe().unwrap();
+    assert_eq!(
+        sym.module
+            .as_deref()
+            .map(Path::canonicalize)
+            .transpose()
+            .unwrap(),
+        Some(exe.canonicalize().unwrap())
+    );
+}
+
+/// Check that lazy symbolization produces the same results as the
+/// batch API.
+#[test]
+fn symbolize_iter() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addrs-no-dwarf.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+    let addrs = [0x2000100, 0x1, 0x2000200];
+
+    let expected = symbolizer
+        .symbolize(&src, symbolize::Input::VirtOffset(&addrs))
+        .unwrap();
+    let results = symbolizer
+        .symbolize_iter(&src, symbolize::Input::VirtOffset(&addrs))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(results, expected);
+    assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+
+    // Errors should be reported once and end the iteration.
+    let mut iter = symbolizer.symbolize_iter(&src, symbolize::Input::AbsAddr(&addrs));
+    let err = iter.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+    assert!(iter.next().is_none());
+}
+
+/// Check that we can symbolize addresses inside our own process when
+/// reading memory mappings from a user provided maps file.
+#[test]
+fn symbolize_process_with_maps_override() {
+    let maps = read_file("/proc/self/maps").unwrap();
+    let mut file = NamedTempFile::new().unwrap();
+    let () = file.write_all(&maps).unwrap();
+
+    let mut process = symbolize::Process::new(Pid::Slf);
+    process.map_files = false;
+    process.maps_override = Some(file.path().to_path_buf());
+    let src = symbolize::Source::Process(process);
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(
+            &src,
+            symbolize::Input::AbsAddr(symbolize_process_with_maps_override as Addr),
+        )
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert!(
+        result.name.contains("symbolize_process_with_maps_override"),
+        "{result:x?}"
+    );
+
+    // An empty maps file means that we have no information about any
+    // addresses.
+    let file = NamedTempFile::new().unwrap();
+    let mut process = symbolize::Process::new(Pid::Slf);
+    process.maps_override = Some(file.path().to_path_buf());
+    let src = symbolize::Source::Process(process);
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(symbolize_process as Addr))
+        .unwrap();
+    assert_eq!(result, Symbolized::Unknown(Reason::Unmapped));
+}
+
+/// Check that we can symbolize addresses inside our process' VDSO.
+#[cfg(target_os = "linux")]
+#[test]
+fn symbolize_process_vdso() {
+    let handle = unsafe {
+        libc::dlopen(
+            "linux-vdso.so.1\0".as_ptr().cast(),
+            libc::RTLD_LAZY | libc::RTLD_NOLOAD,
+        )
+    };
+    if handle.is_null() {
+        // No VDSO present or visible to the dynamic linker; nothing to
+        // test.
+        return
+    }
+    defer!({
+        let rc = unsafe { libc::dlclose(handle) };
+        assert_eq!(rc, 0, "{}", Error::last_os_error());
+    });
+
+    let addr = ["__vdso_clock_gettime\0", "__kernel_clock_gettime\0"]
+        .into_iter()
+        .map(|name| unsafe { libc::dlsym(handle, name.as_ptr().cast()) })
+        .find(|addr| !addr.is_null());
+    let addr = if let Some(addr) = addr { addr } else { return };
+
+    let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(addr as Addr))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert!(result.name.contains("clock_gettime"), "{result:x?}");
+}
+
 /// Check that we can symbolize an address in a process using a binary
 /// located in a local mount namespace.
 #[cfg(not(windows))]
@@ -918,6 +1601,37 @@ fn normalize_build_id_reading() {
 }
 
 
+/// Check that we can enumerate the file backed memory mappings of a
+/// process.
+#[cfg(not(windows))]
+#[test]
+fn process_maps() {
+    let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+    let so_cstr = CString::new(test_so.clone().into_os_string().into_vec()).unwrap();
+    let handle = unsafe { libc::dlopen(so_cstr.as_ptr(), libc::RTLD_NOW) };
+    assert!(!handle.is_null());
+
+    let the_answer_addr = unsafe { libc::dlsym(handle, "the_answer\0".as_ptr().cast()) };
+    assert!(!the_answer_addr.is_null());
+
+    let process = symbolize::Process::new(Pid::Slf);
+    let maps = process.maps().unwrap();
+
+    let rc = unsafe { libc::dlclose(handle) };
+    assert_eq!(rc, 0, "{}", Error::last_os_error());
+
+    let entry = maps
+        .iter()
+        .find(|entry| entry.range.contains(&(the_answer_addr as Addr)))
+        .unwrap();
+    assert_eq!(entry.path.symbolic_path, test_so);
+    let expected = read_elf_build_id(&test_so).unwrap().unwrap();
+    assert_eq!(entry.build_id.as_ref().unwrap(), &expected);
+}
+
+
 /// Check that we can look up an address.
 #[test]
 fn inspect_elf() {
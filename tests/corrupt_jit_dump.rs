@@ -0,0 +1,55 @@
+#![allow(
+    clippy::fn_to_numeric_cast,
+    clippy::let_and_return,
+    clippy::let_unit_value
+)]
+
+use std::fs::remove_file;
+use std::fs::File;
+use std::io::Write as _;
+use std::process;
+
+use memmap2::Mmap;
+use scopeguard::defer;
+
+use blazesym::symbolize::Input;
+use blazesym::symbolize::Process;
+use blazesym::symbolize::Source;
+use blazesym::symbolize::Symbolized;
+use blazesym::symbolize::Symbolizer;
+use blazesym::Addr;
+use blazesym::Pid;
+
+
+/// Make sure that we do not fail symbolization when a corrupt JIT
+/// dump is present.
+#[test]
+fn symbolize_with_corrupt_jit_dump() {
+    let heap = vec![0; 4096];
+    let path = format!("/tmp/jit-{}.dump", process::id());
+    let mut file = File::options()
+        .create_new(true)
+        .write(true)
+        .read(true)
+        .open(&path)
+        .unwrap();
+    defer!({
+        let _result = remove_file(&path);
+    });
+    let () = file.write_all(&[0xff; 64]).unwrap();
+
+    // JIT dumps are discovered by virtue of being mapped into the
+    // process.
+    let _mmap = unsafe { Mmap::map(&file) }.unwrap();
+
+    let src = Source::Process(Process::new(Pid::Slf));
+    // We attempt symbolization of an address inside the heap, whose
+    // corresponding proc maps entry is likely "unnamed". That
+    // should trigger the perf map and JIT dump symbolization path,
+    // the latter of which is corrupt.
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(&src, Input::AbsAddr(heap.as_slice().as_ptr() as Addr))
+        .unwrap();
+    assert!(matches!(result, Symbolized::Unknown(..)));
+}